@@ -0,0 +1,122 @@
+//! In-memory caching for upstream API responses
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// A small TTL cache with a fixed capacity
+///
+/// Entries expire after `ttl`. When the cache is full, expired entries are
+/// purged first and, if that is not enough, the oldest entry is evicted.
+#[derive(Debug)]
+pub struct TtlCache<V> {
+    entries: HashMap<String, (Instant, V)>,
+    ttl: Duration,
+    capacity: usize,
+}
+
+impl<V: Clone> TtlCache<V> {
+    /// Create a new cache. A capacity or TTL of zero disables caching.
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            ttl,
+            capacity,
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.capacity > 0 && !self.ttl.is_zero()
+    }
+
+    /// Get a clone of the cached value if present and not expired
+    pub fn get(&mut self, key: &str) -> Option<V> {
+        let expired = match self.entries.get(key) {
+            Some((inserted, _)) => inserted.elapsed() >= self.ttl,
+            None => return None,
+        };
+
+        if expired {
+            self.entries.remove(key);
+            return None;
+        }
+
+        self.entries.get(key).map(|(_, value)| value.clone())
+    }
+
+    /// Insert or replace a value
+    pub fn insert(&mut self, key: String, value: V) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            let ttl = self.ttl;
+            self.entries
+                .retain(|_, (inserted, _)| inserted.elapsed() < ttl);
+
+            if self.entries.len() >= self.capacity {
+                let oldest = self
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, (inserted, _))| *inserted)
+                    .map(|(k, _)| k.clone());
+                if let Some(oldest) = oldest {
+                    self.entries.remove(&oldest);
+                }
+            }
+        }
+
+        self.entries.insert(key, (Instant::now(), value));
+    }
+
+    /// Number of entries currently held (including not yet purged expired ones)
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the cache holds no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_and_get() {
+        let mut cache = TtlCache::new(Duration::from_secs(60), 10);
+        cache.insert("a".to_string(), 1);
+        assert_eq!(cache.get("a"), Some(1));
+        assert_eq!(cache.get("b"), None);
+    }
+
+    #[test]
+    fn test_expired_entries_are_dropped() {
+        let mut cache = TtlCache::new(Duration::from_millis(1), 10);
+        cache.insert("a".to_string(), 1);
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(cache.get("a"), None);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_capacity_evicts_oldest() {
+        let mut cache = TtlCache::new(Duration::from_secs(60), 2);
+        cache.insert("a".to_string(), 1);
+        std::thread::sleep(Duration::from_millis(2));
+        cache.insert("b".to_string(), 2);
+        cache.insert("c".to_string(), 3);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get("a"), None);
+        assert_eq!(cache.get("c"), Some(3));
+    }
+
+    #[test]
+    fn test_zero_capacity_disables_cache() {
+        let mut cache = TtlCache::new(Duration::from_secs(60), 0);
+        cache.insert("a".to_string(), 1);
+        assert_eq!(cache.get("a"), None);
+    }
+}
@@ -20,6 +20,222 @@ pub struct JobsucheConfig {
     /// Maximum page size allowed
     #[serde(default = "default_max_page_size")]
     pub max_page_size: u64,
+
+    /// How long fetched job details stay cached, in seconds (0 disables the cache)
+    #[serde(default = "default_details_cache_ttl_secs")]
+    pub details_cache_ttl_secs: u64,
+
+    /// Maximum number of job details kept in the cache
+    #[serde(default = "default_details_cache_capacity")]
+    pub details_cache_capacity: usize,
+
+    /// Maximum size of an API response body in bytes
+    #[serde(default = "default_max_response_bytes")]
+    pub max_response_bytes: usize,
+
+    /// File used to persist saved favorites (kept in memory only if not set)
+    #[serde(default)]
+    pub favorites_path: Option<String>,
+
+    /// Maximum idle HTTP connections kept per host
+    #[serde(default = "default_pool_max_idle_per_host")]
+    pub pool_max_idle_per_host: usize,
+
+    /// Seconds an idle HTTP connection is kept alive before closing
+    #[serde(default = "default_pool_idle_timeout_secs")]
+    pub pool_idle_timeout_secs: u64,
+
+    /// Accept invalid TLS certificates. DEVELOPMENT ONLY, e.g. behind an intercepting corporate proxy; never enable in production
+    #[serde(default = "default_danger_accept_invalid_certs")]
+    pub danger_accept_invalid_certs: bool,
+
+    /// How pagination is expressed in API queries (`page`/`size` or `offset`/`limit`)
+    #[serde(default = "default_pagination_style")]
+    pub pagination_style: PaginationStyle,
+
+    /// Seconds to wait for in-flight requests on shutdown
+    #[serde(default = "default_shutdown_grace_secs")]
+    pub shutdown_grace_secs: u64,
+
+    /// Path to a JSON file with extra job title synonym groups
+    #[serde(default)]
+    pub synonyms_path: Option<String>,
+
+    /// Page size used by search_jobs when none is given (falls back to default_page_size)
+    #[serde(default)]
+    pub default_search_page_size: Option<u64>,
+
+    /// Page size of the connectivity probe in get_server_status
+    #[serde(default = "default_status_probe_page_size")]
+    pub status_probe_page_size: u64,
+
+    /// Number of job details remembered by get_recently_viewed
+    #[serde(default = "default_recently_viewed_capacity")]
+    pub recently_viewed_capacity: usize,
+
+    /// Retry a search once with a cache-busting parameter if its response cannot be parsed
+    #[serde(default = "default_retry_on_parse_error")]
+    pub retry_on_parse_error: bool,
+
+    /// How long search results stay cached, in seconds (0 disables the cache)
+    #[serde(default = "default_search_cache_ttl_secs")]
+    pub search_cache_ttl_secs: u64,
+
+    /// Maximum number of cached searches (0 disables the cache)
+    #[serde(default = "default_search_cache_capacity")]
+    pub search_cache_capacity: usize,
+
+    /// Detect the language of job descriptions in get_job_details
+    #[serde(default = "default_detect_description_language")]
+    pub detect_description_language: bool,
+
+    /// Maximum number of concurrent requests to the API across all tools
+    #[serde(default = "default_max_concurrent_requests")]
+    pub max_concurrent_requests: usize,
+
+    /// Characters of an unparseable response body to log (0 disables body logging)
+    #[serde(default = "default_log_body_snippet_len")]
+    pub log_body_snippet_len: usize,
+
+    /// Overall time limit for search_jobs, get_job_details and batch_search_jobs, in seconds
+    #[serde(default = "default_tool_timeout_secs")]
+    pub tool_timeout_secs: u64,
+
+    /// Clean up job titles in search results unless a search says otherwise
+    #[serde(default = "default_clean_titles")]
+    pub clean_titles: bool,
+
+    /// Title used for listings without title, occupation and employer
+    #[serde(default = "default_fallback_title")]
+    pub fallback_title: String,
+
+    /// Secret for signing requests with X-Signature/X-Timestamp headers (HMAC-SHA256)
+    #[serde(default)]
+    pub signing_secret: Option<String>,
+
+    /// Reject out-of-range page_size, radius_km and published_since_days instead of clamping them
+    #[serde(default = "default_strict_params")]
+    pub strict_params: bool,
+
+    /// Probe without the location when a located search finds nothing, to flag possibly mistyped places
+    #[serde(default = "default_detect_invalid_location")]
+    pub detect_invalid_location: bool,
+
+    /// Longest Retry-After delay honored on 429/503 responses; longer values are clamped
+    #[serde(default = "default_max_retry_after_secs")]
+    pub max_retry_after_secs: u64,
+
+    /// Fail search and details calls whose responses contain fields this server does not know
+    #[serde(default = "default_strict_schema")]
+    pub strict_schema: bool,
+
+    /// Radius applied to searches with a location but no radius_km
+    #[serde(default)]
+    pub default_radius_km: Option<u64>,
+
+    /// Key naming of search_jobs and get_job_details output
+    #[serde(default = "default_output_case")]
+    pub output_case: OutputCase,
+
+    /// Most job details a single search may look up for detail-based filters
+    #[serde(default = "default_max_detail_fetches_per_search")]
+    pub max_detail_fetches_per_search: usize,
+
+    /// Employment types applied when a search names none, e.g. ["mini_job"]
+    #[serde(default)]
+    pub default_employment_type: Option<Vec<String>>,
+
+    /// Clean up job descriptions in get_job_details by default
+    #[serde(default = "default_clean_description")]
+    pub clean_description: bool,
+
+    /// Order in which application URLs are preferred: "external", "partner", "internal"
+    #[serde(default)]
+    pub application_url_priority: Option<Vec<String>>,
+
+    /// Run one validating search at startup and fail if the API is unreachable or rejects the key
+    #[serde(default = "default_run_startup_check")]
+    pub run_startup_check: bool,
+
+    /// Number of the first page in the upstream API (0 or 1); tool pages always start at 1
+    #[serde(default = "default_api_page_base")]
+    pub api_page_base: u64,
+
+    /// `arbeitszeit` codes sent to the API; others are dropped (default: all codes the server knows)
+    #[serde(default)]
+    pub arbeitszeit_allowlist: Option<Vec<String>>,
+
+    /// Reject batches over the search and detail limits instead of truncating them
+    #[serde(default = "default_strict_batch_limits")]
+    pub strict_batch_limits: bool,
+
+    /// Retry requests that fail with connection errors (resets, DNS failures, timeouts)
+    #[serde(default = "default_retry_on_connection_errors")]
+    pub retry_on_connection_errors: bool,
+
+    /// Secondary API base URL tried when the primary `api_url` is unreachable or answers with a server error
+    #[serde(default)]
+    pub fallback_api_url: Option<String>,
+
+    /// Seed for retry jitter; unset picks a random seed per client. Set it to make retry timing reproducible in tests
+    #[serde(default)]
+    pub random_seed: Option<u64>,
+
+    /// Let fetch_application_page fetch pages on loopback addresses, for testing against local servers. Private, link-local and other non-public addresses stay blocked
+    #[serde(default = "default_allow_loopback_application_pages")]
+    pub allow_loopback_application_pages: bool,
+}
+
+/// How pagination is expressed in search queries
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PaginationStyle {
+    /// `page` and `size` parameters (current API)
+    #[default]
+    Page,
+    /// `offset` and `limit` parameters, with offset computed from page and size
+    Offset,
+}
+
+impl std::str::FromStr for PaginationStyle {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "page" => Ok(Self::Page),
+            "offset" => Ok(Self::Offset),
+            other => anyhow::bail!(
+                "Unknown pagination style '{}' (expected 'page' or 'offset')",
+                other
+            ),
+        }
+    }
+}
+
+/// Naming convention for the keys of tool output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputCase {
+    /// `reference_number` (field names as declared)
+    #[default]
+    Snake,
+    /// `referenceNumber`
+    Camel,
+}
+
+impl std::str::FromStr for OutputCase {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "snake" => Ok(Self::Snake),
+            "camel" => Ok(Self::Camel),
+            other => anyhow::bail!(
+                "Unknown output case '{}' (expected 'snake' or 'camel')",
+                other
+            ),
+        }
+    }
 }
 
 fn default_page_size() -> u64 {
@@ -30,6 +246,130 @@ fn default_max_page_size() -> u64 {
     100
 }
 
+fn default_details_cache_ttl_secs() -> u64 {
+    300
+}
+
+fn default_details_cache_capacity() -> usize {
+    500
+}
+
+fn default_max_response_bytes() -> usize {
+    10 * 1024 * 1024
+}
+
+fn default_pool_max_idle_per_host() -> usize {
+    16
+}
+
+fn default_pool_idle_timeout_secs() -> u64 {
+    90
+}
+
+fn default_danger_accept_invalid_certs() -> bool {
+    false
+}
+
+fn default_pagination_style() -> PaginationStyle {
+    PaginationStyle::Page
+}
+
+fn default_shutdown_grace_secs() -> u64 {
+    30
+}
+
+fn default_status_probe_page_size() -> u64 {
+    1
+}
+
+fn default_recently_viewed_capacity() -> usize {
+    20
+}
+
+fn default_retry_on_parse_error() -> bool {
+    true
+}
+
+fn default_search_cache_ttl_secs() -> u64 {
+    60
+}
+
+fn default_search_cache_capacity() -> usize {
+    200
+}
+
+fn default_detect_description_language() -> bool {
+    true
+}
+
+fn default_max_concurrent_requests() -> usize {
+    8
+}
+
+fn default_log_body_snippet_len() -> usize {
+    500
+}
+
+fn default_tool_timeout_secs() -> u64 {
+    60
+}
+
+fn default_clean_titles() -> bool {
+    false
+}
+
+fn default_fallback_title() -> String {
+    "Stellenangebot".to_string()
+}
+
+fn default_strict_params() -> bool {
+    false
+}
+
+fn default_detect_invalid_location() -> bool {
+    false
+}
+
+fn default_max_retry_after_secs() -> u64 {
+    30
+}
+
+fn default_strict_schema() -> bool {
+    false
+}
+
+fn default_output_case() -> OutputCase {
+    OutputCase::Snake
+}
+
+fn default_max_detail_fetches_per_search() -> usize {
+    25
+}
+
+fn default_clean_description() -> bool {
+    false
+}
+
+fn default_run_startup_check() -> bool {
+    false
+}
+
+fn default_api_page_base() -> u64 {
+    1
+}
+
+fn default_strict_batch_limits() -> bool {
+    false
+}
+
+fn default_retry_on_connection_errors() -> bool {
+    true
+}
+
+fn default_allow_loopback_application_pages() -> bool {
+    false
+}
+
 impl Default for JobsucheConfig {
     fn default() -> Self {
         Self {
@@ -37,6 +377,47 @@ impl Default for JobsucheConfig {
             api_key: None,
             default_page_size: default_page_size(),
             max_page_size: default_max_page_size(),
+            details_cache_ttl_secs: default_details_cache_ttl_secs(),
+            details_cache_capacity: default_details_cache_capacity(),
+            max_response_bytes: default_max_response_bytes(),
+            favorites_path: None,
+            pool_max_idle_per_host: default_pool_max_idle_per_host(),
+            pool_idle_timeout_secs: default_pool_idle_timeout_secs(),
+            danger_accept_invalid_certs: default_danger_accept_invalid_certs(),
+            pagination_style: default_pagination_style(),
+            shutdown_grace_secs: default_shutdown_grace_secs(),
+            synonyms_path: None,
+            default_search_page_size: None,
+            status_probe_page_size: default_status_probe_page_size(),
+            recently_viewed_capacity: default_recently_viewed_capacity(),
+            retry_on_parse_error: default_retry_on_parse_error(),
+            search_cache_ttl_secs: default_search_cache_ttl_secs(),
+            search_cache_capacity: default_search_cache_capacity(),
+            detect_description_language: default_detect_description_language(),
+            max_concurrent_requests: default_max_concurrent_requests(),
+            log_body_snippet_len: default_log_body_snippet_len(),
+            tool_timeout_secs: default_tool_timeout_secs(),
+            clean_titles: default_clean_titles(),
+            fallback_title: default_fallback_title(),
+            signing_secret: None,
+            strict_params: default_strict_params(),
+            detect_invalid_location: default_detect_invalid_location(),
+            max_retry_after_secs: default_max_retry_after_secs(),
+            strict_schema: default_strict_schema(),
+            default_radius_km: None,
+            output_case: default_output_case(),
+            max_detail_fetches_per_search: default_max_detail_fetches_per_search(),
+            default_employment_type: None,
+            clean_description: default_clean_description(),
+            application_url_priority: None,
+            run_startup_check: default_run_startup_check(),
+            api_page_base: default_api_page_base(),
+            arbeitszeit_allowlist: None,
+            strict_batch_limits: default_strict_batch_limits(),
+            retry_on_connection_errors: default_retry_on_connection_errors(),
+            fallback_api_url: None,
+            random_seed: None,
+            allow_loopback_application_pages: default_allow_loopback_application_pages(),
         }
     }
 }
@@ -49,6 +430,47 @@ impl JobsucheConfig {
     /// - `JOBSUCHE_API_KEY`: API key (optional, uses default if not specified)
     /// - `JOBSUCHE_DEFAULT_PAGE_SIZE`: Default page size (optional, defaults to 25)
     /// - `JOBSUCHE_MAX_PAGE_SIZE`: Maximum page size (optional, defaults to 100)
+    /// - `JOBSUCHE_DETAILS_CACHE_TTL_SECS`: Job details cache TTL (optional, defaults to 300)
+    /// - `JOBSUCHE_DETAILS_CACHE_CAPACITY`: Job details cache size (optional, defaults to 500)
+    /// - `JOBSUCHE_MAX_RESPONSE_BYTES`: Maximum API response size in bytes (optional, defaults to 10 MiB)
+    /// - `JOBSUCHE_FAVORITES_PATH`: File to persist favorites in (optional, in-memory if not set)
+    /// - `JOBSUCHE_POOL_MAX_IDLE_PER_HOST`: Idle connections kept per host (optional, defaults to 16)
+    /// - `JOBSUCHE_POOL_IDLE_TIMEOUT_SECS`: Idle connection keep-alive in seconds (optional, defaults to 90)
+    /// - `JOBSUCHE_DANGER_ACCEPT_INVALID_CERTS`: Disable TLS certificate verification, development only (optional, defaults to false)
+    /// - `JOBSUCHE_PAGINATION_STYLE`: `page` or `offset` pagination query parameters (optional, defaults to page)
+    /// - `JOBSUCHE_SHUTDOWN_GRACE_SECS`: Shutdown grace period in seconds (optional, defaults to 30)
+    /// - `JOBSUCHE_SYNONYMS_PATH`: Path to a JSON file with extra job title synonym groups (optional)
+    /// - `JOBSUCHE_DEFAULT_SEARCH_PAGE_SIZE`: Default page size for search_jobs (optional, defaults to JOBSUCHE_DEFAULT_PAGE_SIZE)
+    /// - `JOBSUCHE_STATUS_PROBE_PAGE_SIZE`: Page size of the get_server_status connectivity probe (optional, defaults to 1)
+    /// - `JOBSUCHE_RECENTLY_VIEWED_CAPACITY`: Number of viewed jobs remembered by get_recently_viewed (optional, defaults to 20)
+    /// - `JOBSUCHE_RETRY_ON_PARSE_ERROR`: Retry unparseable search responses once (optional, defaults to true)
+    /// - `JOBSUCHE_SEARCH_CACHE_TTL_SECS`: Search cache TTL in seconds (optional, defaults to 60, 0 disables)
+    /// - `JOBSUCHE_SEARCH_CACHE_CAPACITY`: Maximum number of cached searches (optional, defaults to 200, 0 disables)
+    /// - `JOBSUCHE_DETECT_DESCRIPTION_LANGUAGE`: Detect the language of job descriptions (optional, defaults to true)
+    /// - `JOBSUCHE_MAX_CONCURRENT_REQUESTS`: Maximum concurrent API requests (optional, defaults to 8)
+    /// - `JOBSUCHE_LOG_BODY_SNIPPET_LEN`: Characters of unparseable response bodies to log (optional, defaults to 500, 0 disables)
+    /// - `JOBSUCHE_TOOL_TIMEOUT_SECS`: Overall tool time limit in seconds (optional, defaults to 60)
+    /// - `JOBSUCHE_CLEAN_TITLES`: Clean up job titles in search results by default (optional, defaults to false)
+    /// - `JOBSUCHE_FALLBACK_TITLE`: Placeholder title for listings without any title information (optional, defaults to Stellenangebot)
+    /// - `JOBSUCHE_SIGNING_SECRET`: Sign requests with HMAC-SHA256 for gateway authentication (optional)
+    /// - `JOBSUCHE_STRICT_PARAMS`: Reject out-of-range parameters with an error instead of clamping them (optional, defaults to false)
+    /// - `JOBSUCHE_DETECT_INVALID_LOCATION`: Flag possibly mistyped locations with an extra probe search when a located search finds nothing (optional, defaults to false)
+    /// - `JOBSUCHE_MAX_RETRY_AFTER_SECS`: Longest honored Retry-After delay in seconds (optional, defaults to 30)
+    /// - `JOBSUCHE_STRICT_SCHEMA`: Fail on unknown top-level fields in API responses to detect upstream changes (optional, defaults to false)
+    /// - `JOBSUCHE_DEFAULT_RADIUS_KM`: Radius in km for searches with a location but no explicit radius (optional, no radius by default)
+    /// - `JOBSUCHE_OUTPUT_CASE`: `snake` or `camel` keys in search and job details output (optional, defaults to snake)
+    /// - `JOBSUCHE_MAX_DETAIL_FETCHES_PER_SEARCH`: Most job detail lookups per search for detail-based filters such as recently_updated_only (optional, defaults to 25)
+    /// - `JOBSUCHE_DEFAULT_EMPLOYMENT_TYPE`: Comma-separated employment types applied to searches that set none, e.g. `mini_job` (optional)
+    /// - `JOBSUCHE_CLEAN_DESCRIPTION`: Clean up job descriptions in get_job_details by default (optional, defaults to false)
+    /// - `JOBSUCHE_APPLICATION_URL_PRIORITY`: Comma-separated preference order of application URLs from `external`, `partner` and `internal` (optional, defaults to external,partner,internal)
+    /// - `JOBSUCHE_RUN_STARTUP_CHECK`: Check at startup that the API is reachable and accepts the key (optional, defaults to false)
+    /// - `JOBSUCHE_API_PAGE_BASE`: Number the upstream API gives its first page, 0 or 1 (optional, defaults to 1)
+    /// - `JOBSUCHE_ARBEITSZEIT_ALLOWLIST`: Comma-separated `arbeitszeit` codes the API accepts, e.g. `vz,tz` (optional, defaults to vz,tz,minijob,ho,snw)
+    /// - `JOBSUCHE_STRICT_BATCH_LIMITS`: Reject batches with more than 5 searches or `max_details_per_search` above 5 instead of truncating them (optional, defaults to false)
+    /// - `JOBSUCHE_RETRY_ON_CONNECTION_ERRORS`: Retry requests that fail before an HTTP response, such as connection resets, DNS failures and timeouts (optional, defaults to true)
+    /// - `JOBSUCHE_FALLBACK_API_URL`: Secondary API base URL, used when `JOBSUCHE_API_URL` is unreachable or answers with a 5xx after retries (optional)
+    /// - `JOBSUCHE_RANDOM_SEED`: Seed for retry jitter, making retry timing reproducible (optional, defaults to a random seed)
+    /// - `JOBSUCHE_ALLOW_LOOPBACK_APPLICATION_PAGES`: Let `fetch_application_page` fetch pages on loopback addresses, for local testing (optional, defaults to false)
     pub fn load() -> Result<Self> {
         let api_url = env::var("JOBSUCHE_API_URL").unwrap_or_else(|_| {
             "https://rest.arbeitsagentur.de/jobboerse/jobsuche-service".to_string()
@@ -66,6 +488,202 @@ impl JobsucheConfig {
             .and_then(|v| v.parse().ok())
             .unwrap_or(default_max_page_size());
 
+        let details_cache_ttl_secs = env::var("JOBSUCHE_DETAILS_CACHE_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_details_cache_ttl_secs());
+
+        let details_cache_capacity = env::var("JOBSUCHE_DETAILS_CACHE_CAPACITY")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_details_cache_capacity());
+
+        let max_response_bytes = env::var("JOBSUCHE_MAX_RESPONSE_BYTES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_max_response_bytes());
+
+        let favorites_path = env::var("JOBSUCHE_FAVORITES_PATH").ok();
+
+        let pool_max_idle_per_host = env::var("JOBSUCHE_POOL_MAX_IDLE_PER_HOST")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_pool_max_idle_per_host());
+
+        let pool_idle_timeout_secs = env::var("JOBSUCHE_POOL_IDLE_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_pool_idle_timeout_secs());
+
+        let danger_accept_invalid_certs = env::var("JOBSUCHE_DANGER_ACCEPT_INVALID_CERTS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_danger_accept_invalid_certs());
+
+        let pagination_style = match env::var("JOBSUCHE_PAGINATION_STYLE") {
+            Ok(v) => v.parse()?,
+            Err(_) => default_pagination_style(),
+        };
+
+        let shutdown_grace_secs = env::var("JOBSUCHE_SHUTDOWN_GRACE_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_shutdown_grace_secs());
+
+        let synonyms_path = env::var("JOBSUCHE_SYNONYMS_PATH").ok();
+
+        let default_search_page_size = env::var("JOBSUCHE_DEFAULT_SEARCH_PAGE_SIZE")
+            .ok()
+            .and_then(|v| v.parse().ok());
+
+        let status_probe_page_size = env::var("JOBSUCHE_STATUS_PROBE_PAGE_SIZE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_status_probe_page_size());
+
+        let recently_viewed_capacity = env::var("JOBSUCHE_RECENTLY_VIEWED_CAPACITY")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_recently_viewed_capacity());
+
+        let retry_on_parse_error = env::var("JOBSUCHE_RETRY_ON_PARSE_ERROR")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_retry_on_parse_error());
+
+        let search_cache_ttl_secs = env::var("JOBSUCHE_SEARCH_CACHE_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_search_cache_ttl_secs());
+
+        let search_cache_capacity = env::var("JOBSUCHE_SEARCH_CACHE_CAPACITY")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_search_cache_capacity());
+
+        let detect_description_language = env::var("JOBSUCHE_DETECT_DESCRIPTION_LANGUAGE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_detect_description_language());
+
+        let max_concurrent_requests = env::var("JOBSUCHE_MAX_CONCURRENT_REQUESTS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_max_concurrent_requests());
+
+        let log_body_snippet_len = env::var("JOBSUCHE_LOG_BODY_SNIPPET_LEN")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_log_body_snippet_len());
+
+        let tool_timeout_secs = env::var("JOBSUCHE_TOOL_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_tool_timeout_secs());
+
+        let clean_titles = env::var("JOBSUCHE_CLEAN_TITLES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_clean_titles());
+
+        let fallback_title =
+            env::var("JOBSUCHE_FALLBACK_TITLE").unwrap_or_else(|_| default_fallback_title());
+
+        let signing_secret = env::var("JOBSUCHE_SIGNING_SECRET").ok();
+
+        let strict_params = env::var("JOBSUCHE_STRICT_PARAMS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_strict_params());
+
+        let detect_invalid_location = env::var("JOBSUCHE_DETECT_INVALID_LOCATION")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_detect_invalid_location());
+
+        let max_retry_after_secs = env::var("JOBSUCHE_MAX_RETRY_AFTER_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_max_retry_after_secs());
+
+        let strict_schema = env::var("JOBSUCHE_STRICT_SCHEMA")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_strict_schema());
+
+        let default_radius_km = env::var("JOBSUCHE_DEFAULT_RADIUS_KM")
+            .ok()
+            .and_then(|v| v.parse().ok());
+
+        let output_case = match env::var("JOBSUCHE_OUTPUT_CASE") {
+            Ok(v) => v.parse()?,
+            Err(_) => default_output_case(),
+        };
+
+        let max_detail_fetches_per_search = env::var("JOBSUCHE_MAX_DETAIL_FETCHES_PER_SEARCH")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_max_detail_fetches_per_search());
+
+        let default_employment_type = env::var("JOBSUCHE_DEFAULT_EMPLOYMENT_TYPE").ok().map(|v| {
+            v.split(',')
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect()
+        });
+
+        let clean_description = env::var("JOBSUCHE_CLEAN_DESCRIPTION")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_clean_description());
+
+        let application_url_priority =
+            env::var("JOBSUCHE_APPLICATION_URL_PRIORITY").ok().map(|v| {
+                v.split(',')
+                    .map(|t| t.trim().to_lowercase())
+                    .filter(|t| !t.is_empty())
+                    .collect()
+            });
+
+        let run_startup_check = env::var("JOBSUCHE_RUN_STARTUP_CHECK")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_run_startup_check());
+
+        let api_page_base = env::var("JOBSUCHE_API_PAGE_BASE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_api_page_base());
+
+        let arbeitszeit_allowlist = env::var("JOBSUCHE_ARBEITSZEIT_ALLOWLIST").ok().map(|v| {
+            v.split(',')
+                .map(|code| code.trim().to_string())
+                .filter(|code| !code.is_empty())
+                .collect()
+        });
+
+        let strict_batch_limits = env::var("JOBSUCHE_STRICT_BATCH_LIMITS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_strict_batch_limits());
+
+        let retry_on_connection_errors = env::var("JOBSUCHE_RETRY_ON_CONNECTION_ERRORS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_retry_on_connection_errors());
+
+        let fallback_api_url = env::var("JOBSUCHE_FALLBACK_API_URL").ok();
+
+        let random_seed = env::var("JOBSUCHE_RANDOM_SEED")
+            .ok()
+            .and_then(|v| v.parse().ok());
+
+        let allow_loopback_application_pages =
+            env::var("JOBSUCHE_ALLOW_LOOPBACK_APPLICATION_PAGES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default_allow_loopback_application_pages());
+
         // Validate configuration
         if default_page_size == 0 {
             anyhow::bail!("Default page size must be greater than 0");
@@ -93,6 +711,47 @@ impl JobsucheConfig {
             api_key,
             default_page_size,
             max_page_size,
+            details_cache_ttl_secs,
+            details_cache_capacity,
+            max_response_bytes,
+            favorites_path,
+            pool_max_idle_per_host,
+            pool_idle_timeout_secs,
+            danger_accept_invalid_certs,
+            pagination_style,
+            shutdown_grace_secs,
+            synonyms_path,
+            default_search_page_size,
+            status_probe_page_size,
+            recently_viewed_capacity,
+            retry_on_parse_error,
+            search_cache_ttl_secs,
+            search_cache_capacity,
+            detect_description_language,
+            max_concurrent_requests,
+            log_body_snippet_len,
+            tool_timeout_secs,
+            clean_titles,
+            fallback_title,
+            signing_secret,
+            strict_params,
+            detect_invalid_location,
+            max_retry_after_secs,
+            strict_schema,
+            default_radius_km,
+            output_case,
+            max_detail_fetches_per_search,
+            default_employment_type,
+            clean_description,
+            application_url_priority,
+            run_startup_check,
+            api_page_base,
+            arbeitszeit_allowlist,
+            strict_batch_limits,
+            retry_on_connection_errors,
+            fallback_api_url,
+            random_seed,
+            allow_loopback_application_pages,
         })
     }
 
@@ -106,6 +765,75 @@ impl JobsucheConfig {
             anyhow::bail!("API URL must start with http:// or https://");
         }
 
+        if let Some(fallback) = &self.fallback_api_url {
+            if !fallback.starts_with("http://") && !fallback.starts_with("https://") {
+                anyhow::bail!("Fallback API URL must start with http:// or https://");
+            }
+        }
+
+        if self.max_response_bytes == 0 {
+            anyhow::bail!("Max response bytes must be greater than 0");
+        }
+
+        if self.pool_max_idle_per_host == 0 {
+            anyhow::bail!("Pool max idle per host must be greater than 0");
+        }
+
+        if self.pool_idle_timeout_secs == 0 {
+            anyhow::bail!("Pool idle timeout must be greater than 0");
+        }
+
+        if self.max_concurrent_requests == 0 {
+            anyhow::bail!("Max concurrent requests must be greater than 0");
+        }
+
+        if self.tool_timeout_secs == 0 {
+            anyhow::bail!("Tool timeout must be greater than 0");
+        }
+
+        for employment_type in self.default_employment_type.iter().flatten() {
+            if crate::JobsucheMcpServer::parse_employment_type(employment_type).is_none() {
+                anyhow::bail!("Unknown default employment type: {}", employment_type);
+            }
+        }
+
+        if let Some(priority) = &self.application_url_priority {
+            for (idx, source) in priority.iter().enumerate() {
+                if !crate::APPLICATION_URL_SOURCES.contains(&source.as_str()) {
+                    anyhow::bail!(
+                        "Unknown application URL source: {} (expected one of {:?})",
+                        source,
+                        crate::APPLICATION_URL_SOURCES
+                    );
+                }
+                if priority[..idx].contains(source) {
+                    anyhow::bail!("Application URL source listed twice: {}", source);
+                }
+            }
+        }
+
+        if self.api_page_base > 1 {
+            anyhow::bail!("API page base must be 0 or 1, got {}", self.api_page_base);
+        }
+
+        if let Some(size) = self.default_search_page_size {
+            if !(1..=self.max_page_size).contains(&size) {
+                anyhow::bail!(
+                    "Default search page size ({}) must be between 1 and max page size ({})",
+                    size,
+                    self.max_page_size
+                );
+            }
+        }
+
+        if !(1..=self.max_page_size).contains(&self.status_probe_page_size) {
+            anyhow::bail!(
+                "Status probe page size ({}) must be between 1 and max page size ({})",
+                self.status_probe_page_size,
+                self.max_page_size
+            );
+        }
+
         Ok(())
     }
 }
@@ -126,6 +854,11 @@ mod tests {
         assert_eq!(config.api_key, None);
         assert_eq!(config.default_page_size, 25);
         assert_eq!(config.max_page_size, 100);
+        assert_eq!(config.details_cache_ttl_secs, 300);
+        assert_eq!(config.details_cache_capacity, 500);
+        assert_eq!(config.search_cache_ttl_secs, 60);
+        assert_eq!(config.search_cache_capacity, 200);
+        assert!(config.retry_on_parse_error);
     }
 
     #[test]
@@ -254,6 +987,55 @@ mod tests {
         env::remove_var("JOBSUCHE_MAX_PAGE_SIZE");
     }
 
+    #[test]
+    fn test_unknown_default_employment_type_is_rejected() {
+        let config = JobsucheConfig {
+            default_employment_type: Some(vec!["freelance".to_string()]),
+            ..JobsucheConfig::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_application_url_priority_is_validated() {
+        let config = |priority: &[&str]| JobsucheConfig {
+            application_url_priority: Some(priority.iter().map(|s| s.to_string()).collect()),
+            ..JobsucheConfig::default()
+        };
+        assert!(config(&["internal", "external"]).validate().is_ok());
+        assert!(config(&["internal", "website"]).validate().is_err());
+        assert!(config(&["partner", "partner"]).validate().is_err());
+    }
+
+    #[test]
+    fn test_parse_output_case() {
+        assert_eq!("camel".parse::<OutputCase>().unwrap(), OutputCase::Camel);
+        assert_eq!(" Snake ".parse::<OutputCase>().unwrap(), OutputCase::Snake);
+        assert!("kebab".parse::<OutputCase>().is_err());
+    }
+
+    #[test]
+    fn test_parse_pagination_style() {
+        assert_eq!(
+            "page".parse::<PaginationStyle>().unwrap(),
+            PaginationStyle::Page
+        );
+        assert_eq!(
+            "Offset".parse::<PaginationStyle>().unwrap(),
+            PaginationStyle::Offset
+        );
+        assert!("cursor".parse::<PaginationStyle>().is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_with_invalid_pagination_style() {
+        env::set_var("JOBSUCHE_PAGINATION_STYLE", "cursor");
+        let result = JobsucheConfig::load();
+        assert!(result.is_err());
+        env::remove_var("JOBSUCHE_PAGINATION_STYLE");
+    }
+
     #[test]
     fn test_validate_valid_config() {
         let config = JobsucheConfig::default();
@@ -288,6 +1070,115 @@ mod tests {
             .contains("API URL must start with http:// or https://"));
     }
 
+    #[test]
+    fn test_validate_zero_max_response_bytes() {
+        let config = JobsucheConfig {
+            max_response_bytes: 0,
+            ..Default::default()
+        };
+        let result = config.validate();
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Max response bytes must be greater than 0"));
+    }
+
+    #[test]
+    fn test_validate_zero_pool_settings() {
+        let config = JobsucheConfig {
+            pool_max_idle_per_host: 0,
+            ..Default::default()
+        };
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("Pool max idle per host"));
+
+        let config = JobsucheConfig {
+            pool_idle_timeout_secs: 0,
+            ..Default::default()
+        };
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("Pool idle timeout"));
+    }
+
+    #[test]
+    fn test_validate_api_page_base() {
+        let config = JobsucheConfig {
+            api_page_base: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        let config = JobsucheConfig {
+            api_page_base: 2,
+            ..Default::default()
+        };
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("API page base"));
+    }
+
+    #[test]
+    fn test_validate_zero_max_concurrent_requests() {
+        let config = JobsucheConfig {
+            max_concurrent_requests: 0,
+            ..Default::default()
+        };
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("Max concurrent requests"));
+    }
+
+    #[test]
+    fn test_validate_per_tool_page_sizes() {
+        let config = JobsucheConfig {
+            default_search_page_size: Some(101),
+            ..Default::default()
+        };
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("Default search page size"));
+
+        let config = JobsucheConfig {
+            status_probe_page_size: 0,
+            ..Default::default()
+        };
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("Status probe page size"));
+
+        let config = JobsucheConfig {
+            default_search_page_size: Some(50),
+            status_probe_page_size: 5,
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_fallback_url_scheme() {
+        let config = JobsucheConfig {
+            fallback_api_url: Some("backup.example.com".to_string()),
+            ..Default::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("Fallback API URL"));
+    }
+
     #[test]
     fn test_validate_http_url() {
         let config = JobsucheConfig {
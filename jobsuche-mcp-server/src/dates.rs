@@ -0,0 +1,136 @@
+//! Date helpers for dates returned by the Jobsuche API
+//!
+//! The API reports plain calendar dates (e.g. `2024-01-15`), sometimes with a
+//! time suffix. Calendar arithmetic is done in German local time.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+
+/// Parse an API date such as `2024-01-15` or `2024-01-15T08:00:00` into a calendar date
+pub fn parse_api_date(value: &str) -> Option<NaiveDate> {
+    value
+        .trim()
+        .get(..10)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+}
+
+/// Normalize an API date to ISO `YYYY-MM-DD`
+///
+/// Accepts ISO dates with an optional time suffix as well as the German
+/// `DD.MM.YYYY` form.
+pub fn iso_date(value: &str) -> Option<String> {
+    parse_api_date(value)
+        .or_else(|| NaiveDate::parse_from_str(value.trim(), "%d.%m.%Y").ok())
+        .map(|date| date.format("%Y-%m-%d").to_string())
+}
+
+/// ISO 8601 week of an API date, e.g. `2024-W03`
+pub fn iso_week(value: &str) -> Option<String> {
+    parse_api_date(value).map(|date| {
+        let week = date.iso_week();
+        format!("{}-W{:02}", week.year(), week.week())
+    })
+}
+
+/// Calendar date in Europe/Berlin for the given instant
+///
+/// Applies CET (UTC+1) and the EU daylight saving rule for CEST (UTC+2), which
+/// runs from the last Sunday of March to the last Sunday of October at 01:00 UTC.
+pub fn berlin_date(now: DateTime<Utc>) -> NaiveDate {
+    let year = now.year();
+    let dst_start = last_sunday(year, 3).and_hms_opt(1, 0, 0).unwrap().and_utc();
+    let dst_end = last_sunday(year, 10)
+        .and_hms_opt(1, 0, 0)
+        .unwrap()
+        .and_utc();
+
+    let offset_hours = if now >= dst_start && now < dst_end {
+        2
+    } else {
+        1
+    };
+    (now + Duration::hours(offset_hours)).date_naive()
+}
+
+fn last_sunday(year: i32, month: u32) -> NaiveDate {
+    let first_of_next = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)
+    }
+    .unwrap();
+    let last_day = first_of_next.pred_opt().unwrap();
+    last_day - Duration::days(last_day.weekday().num_days_from_sunday() as i64)
+}
+
+/// Whole days between an API date and `now` in Berlin time
+///
+/// Returns `None` for unparseable or future dates.
+pub fn days_since(date: &str, now: DateTime<Utc>) -> Option<u64> {
+    let published = parse_api_date(date)?;
+    let days = (berlin_date(now) - published).num_days();
+    u64::try_from(days).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_api_date() {
+        assert_eq!(
+            parse_api_date("2024-01-15"),
+            NaiveDate::from_ymd_opt(2024, 1, 15)
+        );
+        assert_eq!(
+            parse_api_date("2024-01-15T08:30:00"),
+            NaiveDate::from_ymd_opt(2024, 1, 15)
+        );
+        assert_eq!(parse_api_date("15.01.2024"), None);
+        assert_eq!(parse_api_date(""), None);
+    }
+
+    #[test]
+    fn test_iso_date() {
+        assert_eq!(iso_date("2024-01-15").as_deref(), Some("2024-01-15"));
+        assert_eq!(
+            iso_date("2024-01-15T08:30:00").as_deref(),
+            Some("2024-01-15")
+        );
+        assert_eq!(iso_date("01.02.2024").as_deref(), Some("2024-02-01"));
+        assert_eq!(iso_date("sofort"), None);
+    }
+
+    #[test]
+    fn test_iso_week() {
+        assert_eq!(iso_week("2024-01-15").as_deref(), Some("2024-W03"));
+        // Belongs to the last week of the previous ISO year
+        assert_eq!(iso_week("2021-01-03T08:00:00").as_deref(), Some("2020-W53"));
+        assert_eq!(iso_week("bald"), None);
+    }
+
+    #[test]
+    fn test_days_since_known_date() {
+        let now = Utc.with_ymd_and_hms(2024, 3, 10, 12, 0, 0).unwrap();
+        assert_eq!(days_since("2024-03-06", now), Some(4));
+        assert_eq!(days_since("2024-03-10", now), Some(0));
+    }
+
+    #[test]
+    fn test_days_since_uses_berlin_time() {
+        // 22:30 UTC in summer is already the next day in Berlin (CEST)
+        let summer = Utc.with_ymd_and_hms(2024, 7, 1, 22, 30, 0).unwrap();
+        assert_eq!(days_since("2024-07-01", summer), Some(1));
+
+        // 22:30 UTC in winter is still the same day in Berlin (CET)
+        let winter = Utc.with_ymd_and_hms(2024, 1, 1, 22, 30, 0).unwrap();
+        assert_eq!(days_since("2024-01-01", winter), Some(0));
+    }
+
+    #[test]
+    fn test_days_since_future_or_invalid() {
+        let now = Utc.with_ymd_and_hms(2024, 3, 10, 12, 0, 0).unwrap();
+        assert_eq!(days_since("2024-03-11", now), None);
+        assert_eq!(days_since("not a date", now), None);
+    }
+}
@@ -0,0 +1,179 @@
+//! Favorites store for saved job postings
+//!
+//! Favorites are kept in memory and, when a path is configured, persisted as a
+//! JSON file. Every change rewrites the file atomically (write to a temporary
+//! file next to it, then rename) so a crash never leaves a half-written store.
+
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Snapshot of a saved job posting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct FavoriteJob {
+    pub reference_number: String,
+    pub title: Option<String>,
+    pub employer: Option<String>,
+    pub location: Option<String>,
+    pub application_url: String,
+    /// Unix timestamp (seconds) of the last save
+    pub saved_at: u64,
+}
+
+/// Thread-safe favorites store
+#[derive(Debug)]
+pub struct FavoritesStore {
+    path: Option<PathBuf>,
+    entries: Mutex<Vec<FavoriteJob>>,
+}
+
+impl FavoritesStore {
+    /// Open a store, loading existing favorites from `path` if the file exists
+    ///
+    /// Without a path, favorites only live for the lifetime of the process.
+    pub fn open(path: Option<PathBuf>) -> Result<Self> {
+        let entries = match &path {
+            Some(path) if path.exists() => {
+                let data = fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read favorites file {}", path.display()))?;
+                serde_json::from_str(&data)
+                    .with_context(|| format!("Failed to parse favorites file {}", path.display()))?
+            }
+            _ => Vec::new(),
+        };
+
+        Ok(Self {
+            path,
+            entries: Mutex::new(entries),
+        })
+    }
+
+    /// Save a favorite, replacing the snapshot if the reference number is already saved
+    ///
+    /// Returns `true` if an existing favorite was updated rather than added.
+    pub fn save(&self, favorite: FavoriteJob) -> Result<bool> {
+        let mut entries = self.entries.lock().unwrap();
+        let mut updated = entries.clone();
+
+        let existing = updated
+            .iter_mut()
+            .find(|f| f.reference_number == favorite.reference_number);
+        let was_present = existing.is_some();
+        match existing {
+            Some(entry) => *entry = favorite,
+            None => updated.push(favorite),
+        }
+
+        self.persist(&updated)?;
+        *entries = updated;
+        Ok(was_present)
+    }
+
+    /// Remove a favorite by reference number, returning whether anything was removed
+    pub fn remove(&self, reference_number: &str) -> Result<bool> {
+        let mut entries = self.entries.lock().unwrap();
+        let mut updated = entries.clone();
+        updated.retain(|f| f.reference_number != reference_number);
+
+        if updated.len() == entries.len() {
+            return Ok(false);
+        }
+
+        self.persist(&updated)?;
+        *entries = updated;
+        Ok(true)
+    }
+
+    /// All saved favorites in insertion order
+    pub fn list(&self) -> Vec<FavoriteJob> {
+        self.entries.lock().unwrap().clone()
+    }
+
+    fn persist(&self, entries: &[FavoriteJob]) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        let mut tmp_path = path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_path);
+
+        fs::write(&tmp_path, serde_json::to_string_pretty(entries)?)
+            .with_context(|| format!("Failed to write favorites file {}", tmp_path.display()))?;
+        fs::rename(&tmp_path, path)
+            .with_context(|| format!("Failed to replace favorites file {}", path.display()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn favorite(refnr: &str, title: &str) -> FavoriteJob {
+        FavoriteJob {
+            reference_number: refnr.to_string(),
+            title: Some(title.to_string()),
+            employer: None,
+            location: None,
+            application_url: format!("https://www.arbeitsagentur.de/jobsuche/jobdetail/{}", refnr),
+            saved_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_save_twice_keeps_one_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FavoritesStore::open(Some(dir.path().join("favorites.json"))).unwrap();
+
+        assert!(!store.save(favorite("123", "Old title")).unwrap());
+        assert!(store.save(favorite("123", "New title")).unwrap());
+
+        let favorites = store.list();
+        assert_eq!(favorites.len(), 1);
+        assert_eq!(favorites[0].title.as_deref(), Some("New title"));
+    }
+
+    #[test]
+    fn test_remove_then_list_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FavoritesStore::open(Some(dir.path().join("favorites.json"))).unwrap();
+
+        store.save(favorite("123", "Job")).unwrap();
+        assert!(store.remove("123").unwrap());
+        assert!(!store.remove("123").unwrap());
+        assert!(store.list().is_empty());
+    }
+
+    #[test]
+    fn test_changes_are_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("favorites.json");
+
+        let store = FavoritesStore::open(Some(path.clone())).unwrap();
+        store.save(favorite("123", "Job")).unwrap();
+        store.save(favorite("456", "Other job")).unwrap();
+        store.remove("123").unwrap();
+
+        let reopened = FavoritesStore::open(Some(path.clone())).unwrap();
+        assert_eq!(reopened.list(), vec![favorite("456", "Other job")]);
+        assert!(!dir.path().join("favorites.json.tmp").exists());
+    }
+
+    #[test]
+    fn test_in_memory_store() {
+        let store = FavoritesStore::open(None).unwrap();
+        store.save(favorite("123", "Job")).unwrap();
+        assert_eq!(store.list().len(), 1);
+    }
+}
@@ -0,0 +1,75 @@
+//! Lightweight language detection for job descriptions
+//!
+//! Counts frequent function words per language. This is far less general
+//! than a statistical detector but reliable for the German and English texts
+//! found in job postings, and needs no extra dependency.
+
+const GERMAN: &[&str] = &[
+    "der", "die", "das", "und", "ist", "mit", "für", "wir", "sie", "ein", "eine", "einen", "auf",
+    "von", "zu", "im", "den", "dem", "des", "bei", "oder", "nicht", "sich", "auch", "ihre", "ihr",
+    "uns", "unser", "unsere", "werden", "sowie", "als", "nach", "über",
+];
+
+const ENGLISH: &[&str] = &[
+    "the", "and", "is", "with", "for", "we", "you", "a", "an", "on", "of", "to", "in", "at", "or",
+    "not", "are", "your", "our", "will", "be", "as", "this", "that", "have", "from", "by",
+];
+
+const FRENCH: &[&str] = &[
+    "le", "la", "les", "et", "est", "avec", "pour", "nous", "vous", "un", "une", "des", "du",
+    "sur", "dans", "ou", "pas", "votre", "notre", "sont",
+];
+
+/// Minimum number of recognized words before a language is reported
+const MIN_HITS: usize = 3;
+
+/// Detect the language of `text`, returning an ISO 639-1 code like "de" or "en"
+///
+/// Returns `None` for empty text or when too few known words are found.
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let mut counts = [("de", 0usize), ("en", 0), ("fr", 0)];
+
+    for word in text
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+    {
+        let word = word.as_str();
+        for (idx, list) in [GERMAN, ENGLISH, FRENCH].iter().enumerate() {
+            if list.contains(&word) {
+                counts[idx].1 += 1;
+            }
+        }
+    }
+
+    counts
+        .iter()
+        .filter(|(_, hits)| *hits >= MIN_HITS)
+        .max_by_key(|(_, hits)| *hits)
+        .map(|(lang, _)| *lang)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_english() {
+        let text = "We are looking for a software engineer to join our team. \
+                    You will work with the latest technologies in an agile environment.";
+        assert_eq!(detect_language(text), Some("en"));
+    }
+
+    #[test]
+    fn test_detects_german() {
+        let text = "Wir suchen für unser Team in Berlin einen Softwareentwickler (m/w/d). \
+                    Sie arbeiten mit modernen Technologien und sind Teil eines agilen Teams.";
+        assert_eq!(detect_language(text), Some("de"));
+    }
+
+    #[test]
+    fn test_empty_or_unknown_text() {
+        assert_eq!(detect_language(""), None);
+        assert_eq!(detect_language("Java, Rust, Kubernetes"), None);
+    }
+}
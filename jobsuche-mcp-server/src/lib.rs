@@ -8,12 +8,87 @@ use pulseengine_mcp_macros::{mcp_server, mcp_tools};
 use reqwest::Client;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
-use std::time::Instant;
-use tracing::{info, instrument, warn};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, instrument, warn, Instrument};
 
+pub mod cache;
 pub mod config;
-use config::JobsucheConfig;
+mod dates;
+pub mod favorites;
+pub mod language;
+pub mod metrics;
+pub mod observer;
+pub mod output;
+pub mod query;
+pub mod regions;
+pub mod shutdown;
+pub mod signing;
+pub mod skills;
+pub mod synonyms;
+mod text;
+mod titles;
+use cache::TtlCache;
+use config::{JobsucheConfig, PaginationStyle};
+use favorites::{FavoriteJob, FavoritesStore};
+use metrics::{LatencyRecorder, LatencyTimer, ToolLatency};
+use observer::JobsucheObserver;
+use output::Cased;
+use shutdown::{InFlightGuard, RequestTracker};
+use synonyms::SynonymMap;
+
+#[cfg(test)]
+mod test_support;
+
+/// Application URL sources in their default order of preference
+const APPLICATION_URL_SOURCES: [&str; 3] = ["external", "partner", "internal"];
+
+/// `arbeitszeit` codes the API accepts, allowed unless `arbeitszeit_allowlist` is configured
+const KNOWN_ARBEITSZEIT_CODES: [&str; 5] = ["vz", "tz", "minijob", "ho", "snw"];
+
+/// Shared public API key, used when none is configured; heavily throttled
+const DEFAULT_API_KEY: &str = "jobboerse-jobsuche";
+
+/// How often a rate-limited (429/503) request is retried
+const MAX_RATE_LIMIT_RETRIES: u32 = 2;
+/// How often a request that failed without an HTTP response is retried
+const MAX_CONNECTION_RETRIES: u32 = 2;
+/// Most redirects followed when fetching an external page
+const MAX_EXTERNAL_REDIRECTS: usize = 5;
+/// First backoff delay when a rate-limited response has no usable `Retry-After`
+const RATE_LIMIT_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Most searches a batch runs, to respect rate limits
+const MAX_BATCH_SEARCHES: usize = 5;
+/// Most job details a batch fetches per search
+const MAX_BATCH_DETAILS: u64 = 5;
+
+/// Largest search radius the API accepts, in kilometers
+const MAX_RADIUS_KM: u64 = 200;
+/// Largest `veroeffentlichtseit` the API accepts, in days
+const MAX_PUBLISHED_SINCE_DAYS: u64 = 100;
+
+/// Header carrying the caller's correlation ID on every API request
+const CORRELATION_ID_HEADER: &str = "X-Correlation-Id";
+
+tokio::task_local! {
+    /// Correlation ID of the tool call the current task is serving
+    static CORRELATION_ID: String;
+}
+
+/// Run `fut` with `id` as the correlation ID of its API requests; `None` keeps the current one
+async fn with_correlation_id<F: std::future::Future>(id: Option<String>, fut: F) -> F::Output {
+    match id {
+        Some(id) => CORRELATION_ID.scope(id, fut).await,
+        None => fut.await,
+    }
+}
+
+/// Correlation ID of the current task, to carry it into spawned tasks
+fn current_correlation_id() -> Option<String> {
+    CORRELATION_ID.try_with(String::clone).ok()
+}
 
 // ============================================================================
 // API Response Types (matching actual API response format)
@@ -33,6 +108,12 @@ struct ApiSearchResponse {
     // Ignore any additional fields
     #[serde(flatten)]
     _extra: std::collections::HashMap<String, serde_json::Value>,
+    /// The response body as received, for `include_raw`
+    #[serde(skip)]
+    raw: String,
+    /// Which API base answered, see [`JobsucheClient::send`]
+    #[serde(skip)]
+    served_by: &'static str,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -61,11 +142,18 @@ struct ApiArbeitsort {
     plz: Option<String>,
     region: Option<String>,
     land: Option<String>,
-    // Ignore any additional fields like koordinaten, strasse, entfernung
+    koordinaten: Option<ApiKoordinaten>,
+    // Ignore any additional fields like strasse, entfernung
     #[serde(flatten)]
     _extra: std::collections::HashMap<String, serde_json::Value>,
 }
 
+#[derive(Debug, Clone, Copy, Deserialize)]
+struct ApiKoordinaten {
+    lat: Option<f64>,
+    lon: Option<f64>,
+}
+
 #[derive(Debug, Clone, Deserialize, Default)]
 struct ApiJobDetails {
     titel: Option<String>,
@@ -74,6 +162,8 @@ struct ApiJobDetails {
     arbeitsorte: Option<Vec<ApiJobLocation>>,
     #[serde(rename = "arbeitszeitVollzeit")]
     arbeitszeit_vollzeit: Option<bool>,
+    #[serde(rename = "arbeitszeitHeimarbeitTelearbeit")]
+    arbeitszeit_heimarbeit_telearbeit: Option<bool>,
     verguetung: Option<String>,
     vertragsdauer: Option<String>,
     #[serde(rename = "stellenangebotsArt")]
@@ -83,12 +173,15 @@ struct ApiJobDetails {
     #[serde(rename = "nurFuerSchwerbehinderte")]
     nur_fuer_schwerbehinderte: Option<bool>,
     eintrittszeitraum: Option<ApiDateRange>,
+    // Parsed for completeness, not surfaced yet
+    #[allow(dead_code)]
     veroeffentlichungszeitraum: Option<ApiDateRange>,
     #[serde(rename = "istGeringfuegigeBeschaeftigung")]
     ist_geringfuegige_beschaeftigung: Option<bool>,
     #[serde(rename = "istArbeitnehmerUeberlassung")]
     ist_arbeitnehmer_ueberlassung: Option<bool>,
     #[serde(rename = "istPrivateArbeitsvermittlung")]
+    #[allow(dead_code)]
     ist_private_arbeitsvermittlung: Option<bool>,
     #[serde(rename = "quereinstiegGeeignet")]
     quereinstieg_geeignet: Option<bool>,
@@ -97,11 +190,62 @@ struct ApiJobDetails {
     externe_url: Option<String>,
     #[serde(rename = "allianzpartnerUrl")]
     allianzpartner_url: Option<String>,
+    kontakt: Option<ApiContact>,
+    angebotskontakt: Option<ApiContact>,
+    /// Number like `38.5` or text like `"20-30"` / `"39,5"`
+    #[serde(rename = "arbeitszeitWochenstunden")]
+    arbeitszeit_wochenstunden: Option<serde_json::Value>,
+    // Ignore any additional fields
+    #[serde(flatten)]
+    _extra: std::collections::HashMap<String, serde_json::Value>,
+    /// The response body as received, for `include_raw`
+    #[serde(skip)]
+    raw: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+struct ApiContact {
+    #[serde(alias = "ansprechpartner")]
+    name: Option<String>,
+    vorname: Option<String>,
+    nachname: Option<String>,
+    #[serde(alias = "emailAdresse", alias = "mail")]
+    email: Option<String>,
+    #[serde(alias = "telefonnummer", alias = "telefonnummerMobil")]
+    telefon: Option<String>,
     // Ignore any additional fields
     #[serde(flatten)]
     _extra: std::collections::HashMap<String, serde_json::Value>,
 }
 
+impl ApiContact {
+    /// The contact as exposed to callers, `None` if it holds no usable data
+    fn to_contact_info(&self) -> Option<ContactInfo> {
+        fn non_empty(value: &Option<String>) -> Option<String> {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        }
+
+        let full_name = [non_empty(&self.vorname), non_empty(&self.nachname)]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let contact = ContactInfo {
+            name: non_empty(&self.name).or_else(|| Some(full_name).filter(|n| !n.is_empty())),
+            email: non_empty(&self.email),
+            phone: non_empty(&self.telefon),
+        };
+        if contact.name.is_none() && contact.email.is_none() && contact.phone.is_none() {
+            return None;
+        }
+        Some(contact)
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Default)]
 struct ApiJobLocation {
     adresse: Option<ApiAddress>,
@@ -114,6 +258,8 @@ struct ApiJobLocation {
 struct ApiAddress {
     ort: Option<String>,
     plz: Option<String>,
+    region: Option<String>,
+    land: Option<String>,
     // Ignore any additional fields
     #[serde(flatten)]
     _extra: std::collections::HashMap<String, serde_json::Value>,
@@ -133,7 +279,7 @@ struct ApiDateRange {
 // ============================================================================
 
 /// Server status information
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct JobsucheServerStatus {
     pub server_name: String,
     pub version: String,
@@ -141,16 +287,28 @@ pub struct JobsucheServerStatus {
     pub api_url: String,
     pub api_connection_status: String,
     pub tools_count: usize,
+    /// Per-tool latency percentiles, cumulative since server start
+    pub tool_latencies: Vec<ToolLatency>,
+    /// True if requests use the shared public API key, which is throttled aggressively;
+    /// set `JOBSUCHE_API_KEY` to a dedicated key
+    pub using_default_api_key: bool,
+    /// Requests that succeeded after retrying a rate limit (429/503) or connection
+    /// error, since server start
+    pub retries_succeeded: u64,
+    /// Requests that still failed after retrying a rate limit (429/503) or connection
+    /// error, since server start
+    pub retries_exhausted: u64,
 }
 
 /// Parameters for searching jobs
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct SearchJobsParams {
     /// Job title or keywords (e.g., "Software Engineer", "Data Scientist")
     pub job_title: Option<String>,
     /// Location name (e.g., "Berlin", "München", "Deutschland")
     pub location: Option<String>,
-    /// Search radius in kilometers from the location (default: 25)
+    /// Search radius in kilometers from the location (default: server setting, if any);
+    /// ignored without a location
     pub radius_km: Option<u64>,
     /// Employment type filter: "fulltime", "parttime", "mini_job", "home_office"
     pub employment_type: Option<Vec<String>>,
@@ -166,46 +324,279 @@ pub struct SearchJobsParams {
     pub employer: Option<String>,
     /// Branch/industry to search in
     pub branch: Option<String>,
+    /// Only return jobs that can be applied to directly on the employer's site
+    pub external_application_only: Option<bool>,
+    /// Only keep jobs in these postal codes; shorter codes match as prefixes ("10" matches "10115").
+    /// Applied after the search, so a larger `radius_km` may be needed to have candidates.
+    pub postal_codes: Option<Vec<String>>,
+    /// Match the last word of `job_title` as a prefix, e.g. "Entwickl" also finds
+    /// "Entwickler" and "Entwicklerin" (default: false)
+    pub title_wildcard: Option<bool>,
+    /// Collapse postings with the same normalized title and city but different employers
+    /// (e.g. the employer and a staffing agency advertising the same role) into one
+    pub dedupe_similar: Option<bool>,
+    /// Also search for up to 4 known synonyms of `job_title` (e.g. "Softwareentwickler" and
+    /// "Software Engineer") and merge the results; each job reports the `matched_term`
+    pub expand_synonyms: Option<bool>,
+    /// Put jobs that state a salary first, keeping the order within both groups. Fetches
+    /// details for each listing, up to the server's detail lookup limit (default: false)
+    pub prioritize_salary: Option<bool>,
+    /// How to read `job_title`: "plain" (default) or "boolean", which accepts quoted phrases,
+    /// AND, OR and NOT, e.g. `"Data Scientist" OR "ML Engineer" NOT Praktikum`
+    pub query_syntax: Option<String>,
+    /// Sent as `X-Correlation-Id` on every API request and logged with this call
+    pub correlation_id: Option<String>,
+    /// API key to use for this request instead of the configured one
+    pub api_key_override: Option<ApiKey>,
+    /// Only keep jobs published on or after this date (YYYY-MM-DD). Applied after the
+    /// search and combinable with `published_since_days`
+    pub published_after: Option<String>,
+    /// Attach the unprocessed API response as `raw`, for debugging (default: false)
+    pub include_raw: Option<bool>,
+    /// Only keep jobs that were re-published after their first publication, often a sign of
+    /// an actively promoted role. Fetches details for every listing on the page
+    pub recently_updated_only: Option<bool>,
+    /// Also tally the returned jobs by ISO week of publication as `weekly_buckets`
+    /// (default: false)
+    pub group_by_week: Option<bool>,
+    /// Only keep jobs whose description has at least this many characters, dropping thin
+    /// listings. Fetches details for every listing on the page
+    pub min_description_chars: Option<usize>,
+    /// Normalize titles: collapse whitespace and turn ALL-CAPS titles into title case while
+    /// keeping acronyms like "IT" or "SAP" (default: server setting, usually false)
+    pub clean_titles: Option<bool>,
+    /// Drop listings from these employers, e.g. the current employer or temp agencies.
+    /// Ignores case, umlaut spelling and legal forms like "GmbH"
+    pub exclude_employers: Option<Vec<String>>,
+    /// Return `compact_jobs` (reference number, title, employer, city) instead of full
+    /// job summaries, roughly halving the output size (default: false)
+    pub compact: Option<bool>,
+    /// Re-rank the returned jobs by how well title, employer and branch match, weighting each
+    /// field (default weight 1.0). The API query itself is unchanged; jobs get a `relevance_score`
+    pub field_weights: Option<FieldWeights>,
+    /// Latitude of the caller's origin (e.g. home); with `origin_lon`, each job gets a
+    /// straight-line `distance_from_origin_km`
+    pub origin_lat: Option<f64>,
+    /// Longitude of the caller's origin, see `origin_lat`
+    pub origin_lon: Option<f64>,
+    /// Sort jobs by `distance_from_origin_km`, nearest first; jobs without coordinates go
+    /// last (default: false)
+    pub sort_by_distance: Option<bool>,
+    /// If `total_results` stays below this, double the radius (from 25 km if none was set,
+    /// up to 200 km) and search again. Only the radius changes; needs a location
+    pub min_results: Option<u64>,
+}
+
+/// Weights for re-ranking search results by field, see `field_weights`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct FieldWeights {
+    /// Weight of `job_title` matches in the job title (default: 1.0)
+    pub title: Option<f64>,
+    /// Weight of `employer` matches in the employer name (default: 1.0)
+    pub employer: Option<f64>,
+    /// Weight of `branch` matches in the job's occupation (default: 1.0)
+    pub branch: Option<f64>,
+}
+
+/// An API key supplied by the caller; never shown in logs or debug output
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(transparent)]
+pub struct ApiKey(pub String);
+
+impl std::fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ApiKey(<redacted>)")
+    }
 }
 
 /// Result from job search
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SearchJobsResult {
     pub total_results: Option<u64>,
     pub current_page: Option<u64>,
     pub page_size: Option<u64>,
     pub jobs_count: usize,
     pub jobs: Vec<JobSummary>,
+    /// Number of listings removed by client-side filters
+    pub filtered_out_count: usize,
+    /// Number of returned jobs that absorbed near-duplicates (only with `dedupe_similar`)
+    pub clusters_collapsed: usize,
+    /// Hash over the reference numbers of this page's results, independent of their
+    /// order. It covers only the returned page, so compare it across polls of the same
+    /// page to detect whether that page's results changed.
+    pub result_hash: String,
     pub search_duration_ms: u64,
+    /// Unprocessed API response, only with `include_raw`
+    pub raw: Option<serde_json::Value>,
+    /// The location found nothing while the same search without it has results,
+    /// so the place may be mistyped (only with `detect_invalid_location`)
+    #[serde(default)]
+    pub possible_invalid_location: bool,
+    /// Trimmed job list replacing `jobs`, only with `compact`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compact_jobs: Option<Vec<CompactJobSummary>>,
+    /// Filters the server sent to the API after applying defaults
+    #[serde(default)]
+    pub applied_filters: AppliedFilters,
+    /// A detail-based filter or ordering hit `max_detail_fetches_per_search`, so some
+    /// listings went unchecked (dropped by `recently_updated_only` and `min_description_chars`,
+    /// ranked last by `prioritize_salary`)
+    #[serde(default)]
+    pub detail_fetch_limit_reached: bool,
+    /// How often the radius was doubled to reach `min_results`
+    #[serde(default)]
+    pub radius_escalations: u32,
+    /// Request URL sent to the API, with credentials redacted. The API key travels in a
+    /// header and is never part of it. Merged searches report the first term's query
+    #[serde(default)]
+    pub executed_query: String,
+    /// Which API base answered the search, "primary" or "fallback" (only with `fallback_api_url`)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub served_by: String,
+    /// Returned jobs per ISO week of publication, oldest week first (only with `group_by_week`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub weekly_buckets: Vec<WeekBucket>,
+}
+
+/// Number of jobs published in one ISO week
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct WeekBucket {
+    /// ISO week like "2024-W03", or "unknown" for jobs without a usable date
+    pub iso_week: String,
+    pub count: usize,
+}
+
+/// Filters actually used for a search, including server defaults
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct AppliedFilters {
+    /// Radius sent as `umkreis`, if any
+    pub radius_km: Option<u64>,
+    /// True if `radius_km` is the server's default rather than the caller's
+    pub default_radius_applied: bool,
+    /// Working time codes sent as `arbeitszeit`, if any
+    #[serde(default)]
+    pub employment_type: Option<Vec<String>>,
+    /// True if `employment_type` is the server's default rather than the caller's
+    #[serde(default)]
+    pub default_employment_type_applied: bool,
+}
+
+/// Minimal job listing returned by search_jobs with `compact`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CompactJobSummary {
+    pub refnr: String,
+    pub title: String,
+    pub employer: String,
+    pub city: String,
+}
+
+impl From<JobSummary> for CompactJobSummary {
+    fn from(job: JobSummary) -> Self {
+        let city = job
+            .location
+            .split(" (")
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        Self {
+            refnr: job.reference_number,
+            title: job.title,
+            employer: job.employer,
+            city,
+        }
+    }
 }
 
 /// Summary information for a job listing
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct JobSummary {
     pub reference_number: String,
     pub title: String,
     pub employer: String,
     pub location: String,
     pub published_date: Option<String>,
+    /// Days since publication (German local time), None if unknown
+    pub days_since_published: Option<u64>,
     pub external_url: Option<String>,
     pub application_url: String,
+    /// Number of near-identical postings by other employers collapsed into this one
+    #[serde(default)]
+    pub duplicate_count: usize,
+    /// Title term that found this job (only with `expand_synonyms`)
+    #[serde(default)]
+    pub matched_term: Option<String>,
+    /// Title as delivered by the API, set when `clean_titles` changed it
+    #[serde(default)]
+    pub title_raw: Option<String>,
+    /// True if the listing had no title of its own and `title` was derived from the
+    /// occupation, the employer and location, or a placeholder
+    #[serde(default)]
+    pub title_is_fallback: bool,
+    /// Region (usually the Bundesland) as reported by the API
+    #[serde(default)]
+    pub region: Option<String>,
+    /// Weighted share of the search terms found in title, employer and occupation
+    /// (0.0-1.0, only with `field_weights`)
+    #[serde(default)]
+    pub relevance_score: Option<f64>,
+    /// Straight-line distance from `origin_lat`/`origin_lon` in km, None without
+    /// an origin or if the job has no coordinates
+    #[serde(default)]
+    pub distance_from_origin_km: Option<f64>,
+}
+
+/// Weekly working hours; `min` equals `max` unless the posting gives a range
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct WeeklyHours {
+    pub min: f64,
+    pub max: f64,
+}
+
+/// Contact details attached to a job posting
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ContactInfo {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
 }
 
 /// Parameters for getting job details
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct GetJobDetailsParams {
     /// Job reference number (refnr from search results)
     pub reference_number: String,
+    /// API key to use for this request instead of the configured one
+    pub api_key_override: Option<ApiKey>,
+    /// Attach the unprocessed API response as `raw`, for debugging (default: false)
+    pub include_raw: Option<bool>,
+    /// Decode HTML entities, normalize line breaks and collapse blank lines in the
+    /// description (default: server setting, usually false)
+    pub clean_description: Option<bool>,
+    /// Sent as `X-Correlation-Id` on every API request and logged with this call
+    pub correlation_id: Option<String>,
 }
 
 /// Detailed job information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct GetJobDetailsResult {
     pub reference_number: String,
     pub title: Option<String>,
     pub description: Option<String>,
+    /// Description as delivered by the API, set when `clean_description` changed it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description_raw: Option<String>,
+    /// Detected language of the description as ISO 639-1 code ("de", "en", ...),
+    /// None if empty or undetermined
+    pub description_language: Option<String>,
+    /// Skills and qualifications named in the description (languages, frameworks,
+    /// certifications, "Führerschein", ...), found by keyword matching
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extracted_skills: Vec<String>,
     pub employer: Option<String>,
     pub location: Option<String>,
+    /// Further work sites of multi-site postings, formatted like `location`
+    pub additional_locations: Vec<String>,
     pub employment_type: Option<String>,
     pub salary: Option<String>,
     pub contract_duration: Option<String>,
@@ -213,13 +604,43 @@ pub struct GetJobDetailsResult {
     pub first_published: Option<String>,
     pub only_for_disabled: Option<bool>,
     pub fulltime: Option<bool>,
+    /// Whether the job can be done from home: the API's home office flag if it sets one,
+    /// otherwise a guess from the description; None if neither says
+    #[serde(default)]
+    pub home_office: Option<bool>,
     pub entry_period: Option<String>,
+    /// Start of `entry_period` as ISO date (YYYY-MM-DD)
+    #[serde(default)]
+    pub entry_period_start: Option<String>,
+    /// End of `entry_period` as ISO date (YYYY-MM-DD)
+    #[serde(default)]
+    pub entry_period_end: Option<String>,
+    /// Weekly working hours, if the posting states a single value
+    #[serde(default)]
+    pub weekly_hours: Option<f64>,
+    /// Weekly working hours as a range, set whenever the posting states hours
+    #[serde(default)]
+    pub weekly_hours_range: Option<WeeklyHours>,
     pub is_minor_employment: Option<bool>,
     pub is_temp_agency: Option<bool>,
     pub career_changer_suitable: Option<bool>,
     pub external_url: Option<String>,
     pub partner_url: Option<String>,
     pub application_url: String,
+    /// How to apply: "employer_direct", "partner_board", "arbeitsagentur" or "cipher"
+    pub application_method: String,
+    /// Reference of a blind ad (Chiffre) whose employer stays anonymous
+    pub cipher_number: Option<String>,
+    /// Extra guidance on how to apply, set for blind ads
+    pub application_note: Option<String>,
+    /// User-facing sentence explaining how to apply, derived from `application_method`
+    pub application_instructions: Option<String>,
+    /// Contact person for the posting, only if the API provides one. Often absent,
+    /// especially for blind ads (Chiffre) where the employer stays anonymous.
+    #[serde(default)]
+    pub contact: Option<ContactInfo>,
+    /// Unprocessed API response, only with `include_raw`
+    pub raw: Option<serde_json::Value>,
 }
 
 /// Single search configuration for batch operations
@@ -245,6 +666,25 @@ pub struct BatchSearchItem {
     pub branch: Option<String>,
 }
 
+impl BatchSearchItem {
+    /// Search parameters for this item, returning up to `page_size` jobs
+    fn search_params(&self, page_size: u64) -> SearchJobsParams {
+        SearchJobsParams {
+            job_title: self.job_title.clone(),
+            location: self.location.clone(),
+            radius_km: self.radius_km,
+            employment_type: self.employment_type.clone(),
+            contract_type: self.contract_type.clone(),
+            published_since_days: self.published_since_days,
+            page_size: Some(page_size),
+            page: None,
+            employer: self.employer.clone(),
+            branch: self.branch.clone(),
+            ..Default::default()
+        }
+    }
+}
+
 /// Parameters for batch_search_jobs
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BatchSearchJobsParams {
@@ -252,10 +692,35 @@ pub struct BatchSearchJobsParams {
     pub searches: Vec<BatchSearchItem>,
     /// Automatically fetch details for top N results per search (default: 2, max: 5)
     pub max_details_per_search: Option<u64>,
+    /// Token under which the batch can be stopped early with cancel_batch_search; it must
+    /// not be in use by another running batch
+    #[serde(default)]
+    pub cancel_token: Option<String>,
+    /// Only return these job detail fields (e.g. ["title", "salary"]) as `projected_jobs`
+    /// instead of full `jobs`; `reference_number` is always kept
+    #[serde(default)]
+    pub detail_fields: Option<Vec<String>>,
+    /// Sent as `X-Correlation-Id` on every API request of the batch and logged with it
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+}
+
+/// Parameters for cancel_batch_search
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CancelBatchParams {
+    /// `cancel_token` passed to batch_search_jobs
+    pub cancel_token: String,
+}
+
+/// Result from cancel_batch_search
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CancelBatchResult {
+    /// False if no running batch uses this token
+    pub cancelled: bool,
 }
 
 /// Result from a single batch search
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BatchSearchItemResult {
     /// Name of this search
     pub search_name: String,
@@ -267,19 +732,346 @@ pub struct BatchSearchItemResult {
     pub jobs: Vec<GetJobDetailsResult>,
     /// Error message if search failed
     pub error: Option<String>,
+    /// Time spent on this search and its detail fetches
+    #[serde(default)]
+    pub duration_ms: u64,
+    /// Job details reduced to `detail_fields`, replacing `jobs`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub projected_jobs: Option<Vec<serde_json::Value>>,
 }
 
 /// Result from batch_search_jobs
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BatchSearchJobsResult {
     /// Number of searches performed
     pub searches_count: usize,
     /// Results from each search
     pub results: Vec<BatchSearchItemResult>,
+    /// Number of job details served from the details cache instead of the API
+    pub cache_hits: usize,
+    /// True if the batch was cancelled and `results` only covers the searches run so far
+    #[serde(default)]
+    pub cancelled: bool,
     /// Total execution time
     pub total_duration_ms: u64,
 }
 
+/// Overview of a single search in batch_search_digest
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BatchDigestItem {
+    /// Name of this search
+    pub search_name: String,
+    /// Total number of results found
+    pub total_results: Option<u64>,
+    /// Most frequent job titles among the first results, most frequent first
+    pub top_titles: Vec<String>,
+    /// Most frequent employers among the first results, most frequent first
+    pub top_employers: Vec<String>,
+    /// Error message if search failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Result from batch_search_digest
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BatchDigest {
+    /// Number of searches performed
+    pub searches_count: usize,
+    /// Digest of each search, in input order
+    pub searches: Vec<BatchDigestItem>,
+}
+
+/// A single accepted value for a search filter
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FilterValue {
+    /// Value to pass in the search parameters
+    pub value: String,
+    /// What the value means
+    pub description: String,
+}
+
+/// Catalog of accepted search filter values
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FilterCatalog {
+    /// Values for `employment_type`
+    pub employment_types: Vec<FilterValue>,
+    /// Values for `contract_type`
+    pub contract_types: Vec<FilterValue>,
+    /// Available result orderings
+    pub sort_options: Vec<FilterValue>,
+}
+
+/// Parameters for get_employer_locations
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EmployerLocationsParams {
+    /// Employer name (e.g., "Siemens", "Deutsche Bahn")
+    pub employer: String,
+}
+
+/// Number of openings at one place
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct LocationCount {
+    pub city: String,
+    pub postal_code: Option<String>,
+    pub count: u64,
+}
+
+/// Result from get_employer_locations
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EmployerLocationsResult {
+    pub employer: String,
+    /// Places with openings, most openings first
+    pub locations: Vec<LocationCount>,
+}
+
+/// Parameters for get_similar_jobs
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SimilarJobsParams {
+    /// Reference number of the job to find similar postings for
+    pub reference_number: String,
+    /// Minimum share of the seed title's words a candidate title must contain (0.0-1.0, default: 0.3)
+    pub min_title_overlap: Option<f64>,
+    /// Number of candidates to consider (default: page size)
+    pub page_size: Option<u64>,
+}
+
+/// Result from get_similar_jobs
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SimilarJobsResult {
+    pub reference_number: String,
+    pub seed_title: Option<String>,
+    pub jobs_count: usize,
+    pub jobs: Vec<JobSummary>,
+    /// Candidates dropped for falling below `min_title_overlap`
+    pub filtered_out_count: usize,
+}
+
+/// Parameters for search_jobs_collect
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CollectParams {
+    /// The search to run, same parameters as search_jobs; `page` is the first page to fetch
+    pub search: SearchJobsParams,
+    /// Maximum number of pages to fetch (1-10)
+    pub max_pages: u64,
+    /// Stop once this many unique jobs were collected (1-1000)
+    pub max_results: u64,
+    /// Once the first page tells how many pages exist, fetch up to this many of the
+    /// remaining pages at a time (1-5, default: 1, one after another)
+    #[serde(default)]
+    pub parallel_pages: Option<u64>,
+}
+
+/// Result from search_jobs_collect
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CollectResult {
+    pub total_results: Option<u64>,
+    pub jobs_count: usize,
+    /// Unique jobs from all fetched pages, in page order
+    pub jobs: Vec<JobSummary>,
+    pub pages_fetched: u64,
+    /// Requests sent to the API while collecting; pages served from the search cache
+    /// cost none. Counted server-wide, so concurrent tool calls are included.
+    pub api_calls: u64,
+    /// Jobs that came back again on a later page and were dropped; a non-zero value
+    /// means the API's ordering shifted between requests and some jobs may be missing
+    pub cross_page_duplicates: usize,
+    /// Rounds of requests that had to wait for the previous one; lower than
+    /// `pages_fetched` when `parallel_pages` fetched several pages at once
+    #[serde(default)]
+    pub sequential_rounds: u64,
+    pub search_duration_ms: u64,
+}
+
+/// Parameters for get_new_jobs_since
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NewJobsSinceParams {
+    /// The search to run, same parameters as search_jobs
+    pub search: SearchJobsParams,
+    /// Reference numbers returned by the previous poll (`reference_numbers` of the last result)
+    pub known_reference_numbers: Vec<String>,
+}
+
+/// Result from get_new_jobs_since
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NewJobsSinceResult {
+    pub new_jobs_count: usize,
+    /// Jobs whose reference number was not among the known ones
+    pub new_jobs: Vec<JobSummary>,
+    /// All reference numbers of the current result; pass them as
+    /// `known_reference_numbers` on the next poll
+    pub reference_numbers: Vec<String>,
+    /// Hash over `reference_numbers`, see search_jobs
+    pub result_hash: String,
+}
+
+/// Result from get_market_insight
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MarketInsight {
+    pub total_results: Option<u64>,
+    /// Median age of the sampled postings in days
+    pub median_days_since_published: Option<f64>,
+    /// Sampled postings published within the last 7 days
+    pub newest_count_last_7d: u64,
+    /// Number of postings with a known publication date in the sample
+    pub sample_size: usize,
+    /// Short human-readable summary, e.g. "many fresh postings"
+    pub assessment: String,
+    /// Sampled postings per Bundesland, most frequent first; unknown regions count as "Sonstige"
+    pub by_bundesland: Vec<(String, u64)>,
+}
+
+/// Result from save_favorite
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SaveFavoriteResult {
+    pub reference_number: String,
+    /// True if the job was already saved and its snapshot was refreshed
+    pub updated: bool,
+    pub favorites_count: usize,
+}
+
+/// Result from remove_favorite
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RemoveFavoriteResult {
+    pub reference_number: String,
+    /// False if the job was not saved
+    pub removed: bool,
+    pub favorites_count: usize,
+}
+
+/// Result from list_favorites
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ListFavoritesResult {
+    pub favorites_count: usize,
+    pub favorites: Vec<FavoriteJob>,
+}
+
+/// A job recently looked up with get_job_details
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RecentlyViewedJob {
+    pub reference_number: String,
+    /// Title from the details cache, None once the cached details expired
+    pub title: Option<String>,
+}
+
+/// Parameters for refine_search
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RefineParams {
+    /// Reference numbers from an earlier search to narrow down (max: 25)
+    pub previous_reference_numbers: Vec<String>,
+    /// Keep jobs whose title or description contains this text (ignoring case and
+    /// umlaut spelling, so "muenchen" matches "München")
+    pub keyword: String,
+}
+
+/// Result from refine_search
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RefineResult {
+    pub keyword: String,
+    /// Number of jobs whose details were checked
+    pub checked_count: usize,
+    pub jobs_count: usize,
+    /// Matching jobs, in input order
+    pub jobs: Vec<GetJobDetailsResult>,
+    /// Reference numbers that were invalid or whose details could not be fetched
+    pub failed_reference_numbers: Vec<String>,
+}
+
+/// Result from fetch_application_page
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ApplicationPageResult {
+    /// The job's external application URL
+    pub url: String,
+    /// Plain text of the page, cut to a few thousand characters
+    pub text_excerpt: String,
+    /// False if the URL is not http(s) or the page could not be fetched
+    pub fetched: bool,
+}
+
+/// Parameters for get_job_details_bulk
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BulkDetailsParams {
+    /// Reference numbers to fetch (max 25)
+    pub reference_numbers: Vec<String>,
+    /// API key to use for these requests instead of the configured one
+    pub api_key_override: Option<ApiKey>,
+}
+
+/// A job whose details could not be fetched
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BulkDetailsFailure {
+    pub reference_number: String,
+    pub error: String,
+    /// True for rate limiting, server errors and timeouts, which may succeed later
+    pub retryable: bool,
+}
+
+/// Result from get_job_details_bulk
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BulkDetailsResult {
+    pub jobs_count: usize,
+    /// Fetched jobs, in input order
+    pub jobs: Vec<GetJobDetailsResult>,
+    pub failed: Vec<BulkDetailsFailure>,
+    /// Failed reference numbers worth retrying; pass them as `reference_numbers` again.
+    /// Permanent failures such as unknown jobs (404) are left out
+    pub retry_reference_numbers: Vec<String>,
+}
+
+/// Parameters for prefetch_details
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PrefetchParams {
+    /// Reference numbers whose details to cache (max 25)
+    pub reference_numbers: Vec<String>,
+}
+
+/// Result from prefetch_details
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PrefetchResult {
+    /// Jobs whose details are now cached, including those that already were
+    pub prefetched: usize,
+    /// Jobs whose details could not be fetched or whose reference number is invalid
+    pub failed: usize,
+}
+
+/// Parameters for compare_locations
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CompareLocationsParams {
+    /// Job title or keywords to count
+    pub job_title: String,
+    /// Places to compare (max 10)
+    pub locations: Vec<String>,
+    /// Search radius around each location in km (default: server setting, if any)
+    pub radius_km: Option<u64>,
+}
+
+/// Result from compare_locations
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CompareLocationsResult {
+    /// Job count per location, most jobs first; None if the count could not be fetched
+    pub counts: Vec<(String, Option<u64>)>,
+}
+
+/// Result from explain_reference_number
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RefnrExplanation {
+    /// The reference number after trimming and URL decoding
+    pub reference_number: String,
+    /// Usable for get_job_details: no invalid characters and the usual `prefix-number-suffix` shape
+    pub looks_valid: bool,
+    /// The job's page on arbeitsagentur.de
+    pub detail_url: String,
+    /// Anything unusual about the input, e.g. decoded characters or an unexpected length
+    pub notes: Vec<String>,
+}
+
+/// Result from get_recently_viewed
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RecentlyViewedResult {
+    pub jobs_count: usize,
+    /// Most recently viewed first
+    pub jobs: Vec<RecentlyViewedJob>,
+}
+
 // ============================================================================
 // API Client
 // ============================================================================
@@ -287,23 +1079,118 @@ pub struct BatchSearchJobsResult {
 struct JobsucheClient {
     client: Client,
     api_url: String,
+    /// Tried when `api_url` is unreachable or answers with a server error
+    fallback_api_url: Option<String>,
     api_key: String,
+    pagination_style: PaginationStyle,
+    /// Upstream number of the first page; `SearchParams::page` always starts at 1
+    page_base: u64,
+    /// `arbeitszeit` codes sent to the API; others are dropped from queries
+    arbeitszeit_allowlist: Vec<String>,
+    max_response_bytes: usize,
+    retry_on_parse_error: bool,
+    log_body_snippet_len: usize,
+    details_cache: Mutex<TtlCache<ApiJobDetails>>,
+    search_cache: Mutex<TtlCache<ApiSearchResponse>>,
+    /// Crate-wide ceiling on concurrent API requests, shared by all tools
+    request_permits: tokio::sync::Semaphore,
+    observer: Option<Arc<dyn JobsucheObserver>>,
+    signing_secret: Option<String>,
+    /// Number of requests sent to the API so far
+    api_calls: std::sync::atomic::AtomicU64,
+    /// Retried requests that ended in a success or a failure
+    retries_succeeded: std::sync::atomic::AtomicU64,
+    retries_exhausted: std::sync::atomic::AtomicU64,
+    max_retry_after: Duration,
+    retry_on_connection_errors: bool,
+    strict_schema: bool,
+    allow_loopback_application_pages: bool,
+    /// State of the retry jitter generator, seeded from `random_seed` when set
+    rng_state: Mutex<u64>,
 }
 
 impl JobsucheClient {
-    fn new(api_url: &str, api_key: Option<&str>) -> anyhow::Result<Self> {
-        let client = Client::builder()
+    fn new(
+        config: &JobsucheConfig,
+        observer: Option<Arc<dyn JobsucheObserver>>,
+    ) -> anyhow::Result<Self> {
+        let mut builder = Client::builder()
             .use_native_tls()
-            .build()?;
-        
+            .pool_max_idle_per_host(config.pool_max_idle_per_host)
+            .pool_idle_timeout(Duration::from_secs(config.pool_idle_timeout_secs));
+
+        if config.danger_accept_invalid_certs {
+            warn!(
+                "TLS certificate verification is DISABLED (danger_accept_invalid_certs). \
+                 This is for development only and must never be used in production!"
+            );
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+
+        let client = builder.build()?;
+
         Ok(Self {
             client,
-            api_url: api_url.to_string(),
-            api_key: api_key.unwrap_or("jobboerse-jobsuche").to_string(),
+            api_url: config.api_url.clone(),
+            fallback_api_url: config.fallback_api_url.clone(),
+            api_key: config
+                .api_key
+                .as_deref()
+                .unwrap_or(DEFAULT_API_KEY)
+                .to_string(),
+            pagination_style: config.pagination_style,
+            page_base: config.api_page_base,
+            arbeitszeit_allowlist: config.arbeitszeit_allowlist.clone().unwrap_or_else(|| {
+                KNOWN_ARBEITSZEIT_CODES
+                    .iter()
+                    .map(|code| code.to_string())
+                    .collect()
+            }),
+            max_response_bytes: config.max_response_bytes,
+            retry_on_parse_error: config.retry_on_parse_error,
+            log_body_snippet_len: config.log_body_snippet_len,
+            details_cache: Mutex::new(TtlCache::new(
+                Duration::from_secs(config.details_cache_ttl_secs),
+                config.details_cache_capacity,
+            )),
+            search_cache: Mutex::new(TtlCache::new(
+                Duration::from_secs(config.search_cache_ttl_secs),
+                config.search_cache_capacity,
+            )),
+            request_permits: tokio::sync::Semaphore::new(config.max_concurrent_requests),
+            observer,
+            signing_secret: config.signing_secret.clone(),
+            api_calls: std::sync::atomic::AtomicU64::new(0),
+            retries_succeeded: std::sync::atomic::AtomicU64::new(0),
+            retries_exhausted: std::sync::atomic::AtomicU64::new(0),
+            max_retry_after: Duration::from_secs(config.max_retry_after_secs),
+            retry_on_connection_errors: config.retry_on_connection_errors,
+            strict_schema: config.strict_schema,
+            allow_loopback_application_pages: config.allow_loopback_application_pages,
+            rng_state: Mutex::new(config.random_seed.unwrap_or_else(|| {
+                use std::hash::{BuildHasher, Hasher};
+                std::collections::hash_map::RandomState::new()
+                    .build_hasher()
+                    .finish()
+            })),
         })
     }
 
-    async fn search(&self, params: &SearchParams) -> anyhow::Result<ApiSearchResponse> {
+    /// [`Self::build_search_url`] with any credentials in the configured API URL masked
+    fn redacted_search_url(&self, params: &SearchParams) -> String {
+        let url = self.build_search_url(params);
+        match reqwest::Url::parse(&url) {
+            Ok(mut parsed) if !parsed.username().is_empty() || parsed.password().is_some() => {
+                // Only fails for URLs that cannot have credentials in the first place
+                let _ = parsed.set_username("REDACTED");
+                let _ = parsed.set_password(None);
+                parsed.to_string()
+            }
+            _ => url,
+        }
+    }
+
+    fn build_search_url(&self, params: &SearchParams) -> String {
         let mut url = format!("{}/pc/v4/jobs", self.api_url);
         let mut query_parts = Vec::new();
 
@@ -316,433 +1203,7861 @@ impl JobsucheClient {
         if let Some(umkreis) = params.umkreis {
             query_parts.push(format!("umkreis={}", umkreis));
         }
-        if let Some(size) = params.size {
-            query_parts.push(format!("size={}", size));
-        }
-        if let Some(page) = params.page {
-            query_parts.push(format!("page={}", page));
+        match self.pagination_style {
+            PaginationStyle::Page => {
+                if let Some(size) = params.size {
+                    query_parts.push(format!("size={}", size));
+                }
+                if let Some(page) = params.page {
+                    query_parts.push(format!("page={}", page.saturating_sub(1) + self.page_base));
+                }
+            }
+            PaginationStyle::Offset => {
+                if let (Some(page), Some(size)) = (params.page, params.size) {
+                    query_parts.push(format!("offset={}", page.saturating_sub(1) * size));
+                }
+                if let Some(size) = params.size {
+                    query_parts.push(format!("limit={}", size));
+                }
+            }
         }
         if let Some(days) = params.veroeffentlichtseit {
             query_parts.push(format!("veroeffentlichtseit={}", days));
         }
         if let Some(ref arbeitszeit) = params.arbeitszeit {
             for az in arbeitszeit {
-                query_parts.push(format!("arbeitszeit={}", az));
+                if self.arbeitszeit_allowlist.contains(az) {
+                    query_parts.push(format!("arbeitszeit={}", az));
+                } else {
+                    warn!(
+                        "Dropping arbeitszeit code '{}' that the API does not accept",
+                        az
+                    );
+                }
             }
         }
+        if let Some(befristung) = params.befristung.as_ref().filter(|codes| !codes.is_empty()) {
+            query_parts.push(format!("befristung={}", befristung.join(";")));
+        }
 
         if !query_parts.is_empty() {
             url = format!("{}?{}", url, query_parts.join("&"));
         }
 
-        info!("Fetching URL: {}", url);
-
-        let response = self.client
-            .get(&url)
-            .header("X-API-Key", &self.api_key)
-            .send()
-            .await?;
+        url
+    }
 
-        let status = response.status();
-        if !status.is_success() {
-            anyhow::bail!("API error: {}", status);
+    /// Search, serving identical queries from the search cache within its TTL
+    ///
+    /// Requests with an API key override bypass the cache so that every tenant
+    /// request is made with its own key.
+    async fn search(
+        &self,
+        params: &SearchParams,
+        api_key: Option<&str>,
+    ) -> anyhow::Result<ApiSearchResponse> {
+        if api_key.is_some() {
+            return self.search_uncached(params, api_key).await;
         }
 
-        // Get text first for better error handling
-        let text = response.text().await?;
-        
-        match serde_json::from_str::<ApiSearchResponse>(&text) {
-            Ok(result) => Ok(result),
-            Err(e) => {
-                warn!("Failed to parse API response: {}", e);
-                warn!("Response body (first 500 chars): {}", &text[..text.len().min(500)]);
-                anyhow::bail!("Failed to parse API response: {}", e)
-            }
+        let key = normalize_search_key(params);
+        if let Some(cached) = self.search_cache.lock().unwrap().get(&key) {
+            info!("Search cache hit for: {}", key);
+            return Ok(cached);
         }
+
+        let result = self.search_uncached(params, None).await?;
+        self.search_cache
+            .lock()
+            .unwrap()
+            .insert(key, result.clone());
+        Ok(result)
     }
 
-    async fn job_details(&self, refnr: &str) -> anyhow::Result<ApiJobDetails> {
-        let url = format!("{}/pc/v4/jobdetails/{}", self.api_url, urlencoding::encode(refnr));
+    async fn search_uncached(
+        &self,
+        params: &SearchParams,
+        api_key: Option<&str>,
+    ) -> anyhow::Result<ApiSearchResponse> {
+        let url = self.build_search_url(params);
+        info!("Fetching URL: {}", self.redacted_search_url(params));
 
-        let response = self.client
-            .get(&url)
-            .header("X-API-Key", &self.api_key)
-            .send()
-            .await?;
+        match self.fetch_search(&url, api_key).await {
+            Err(SearchFetchError::Parse(e)) if self.retry_on_parse_error => {
+                // Truncated or stale bodies from intermediate caches usually parse on a fresh fetch
+                let separator = if url.contains('?') { '&' } else { '?' };
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_millis())
+                    .unwrap_or_default();
+                let retry_url = format!("{}{}_ts={}", url, separator, now);
+                warn!("Retrying search with cache buster after parse error: {}", e);
 
-        if !response.status().is_success() {
-            anyhow::bail!("API error: {}", response.status());
+                self.fetch_search(&retry_url, api_key)
+                    .await
+                    .map_err(SearchFetchError::into_anyhow)
+            }
+            result => result.map_err(SearchFetchError::into_anyhow),
+        }
+    }
+
+    async fn fetch_search(
+        &self,
+        url: &str,
+        api_key: Option<&str>,
+    ) -> Result<ApiSearchResponse, SearchFetchError> {
+        let _permit = self
+            .request_permits
+            .acquire()
+            .await
+            .map_err(|e| SearchFetchError::Request(e.into()))?;
+        let (response, served_by) = self
+            .send(url, api_key)
+            .await
+            .map_err(SearchFetchError::Request)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(SearchFetchError::Request(anyhow::anyhow!(
+                "API error: {}",
+                status
+            )));
+        }
+
+        let is_html = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|ct| ct.to_ascii_lowercase().starts_with("text/html"));
+
+        // Get text first for better error handling
+        let text = self
+            .read_text(response)
+            .await
+            .map_err(SearchFetchError::Request)?;
+
+        // Gateways and CDNs sometimes answer with an HTML error page and a 200 status
+        if is_html || text.trim_start().starts_with('<') {
+            let snippet: String = text::html_to_text(&text).chars().take(200).collect();
+            self.log_parse_failure(&"body is an HTML page, not JSON", &text);
+            return Err(SearchFetchError::HtmlErrorPage(snippet));
+        }
+
+        match serde_json::from_str::<ApiSearchResponse>(&text) {
+            Ok(result) => {
+                if self.strict_schema {
+                    check_unexpected_fields("search", &result._extra, DOCUMENTED_SEARCH_FIELDS)
+                        .map_err(SearchFetchError::Request)?;
+                }
+                // The echoed page uses `api_page_base`; our pages start at 1
+                let page = match self.pagination_style {
+                    PaginationStyle::Page => result
+                        .page
+                        .map(|page| (page + 1).saturating_sub(self.page_base)),
+                    PaginationStyle::Offset => result.page,
+                };
+                Ok(ApiSearchResponse {
+                    raw: text,
+                    served_by,
+                    page,
+                    ..result
+                })
+            }
+            Err(e) => {
+                self.log_parse_failure(&e, &text);
+                Err(SearchFetchError::Parse(e))
+            }
+        }
+    }
+
+    async fn job_details(
+        &self,
+        refnr: &str,
+        api_key: Option<&str>,
+    ) -> anyhow::Result<ApiJobDetails> {
+        let url = format!(
+            "{}/pc/v4/jobdetails/{}",
+            self.api_url,
+            urlencoding::encode(refnr)
+        );
+
+        let _permit = self.request_permits.acquire().await?;
+        let (response, _) = self.send(&url, api_key).await?;
+
+        if !response.status().is_success() {
+            return Err(ApiStatusError(response.status()).into());
+        }
+
+        let text = self.read_text(response).await?;
+        let parsed: ApiJobDetails = serde_json::from_str(&text).map_err(|e| {
+            self.log_parse_failure(&e, &text);
+            anyhow::anyhow!("Failed to parse job details response: {}", e)
+        })?;
+        if self.strict_schema {
+            check_unexpected_fields("job details", &parsed._extra, DOCUMENTED_DETAILS_FIELDS)?;
+        }
+        let result = ApiJobDetails {
+            raw: text.clone(),
+            ..parsed
+        };
+
+        // Like searches, responses for an API key override stay out of the shared cache
+        if api_key.is_none() {
+            self.details_cache
+                .lock()
+                .unwrap()
+                .insert(refnr.to_string(), result.clone());
         }
 
-        let result: ApiJobDetails = response.json().await?;
         Ok(result)
     }
+
+    /// Log a parse failure with the start of the offending body, up to `log_body_snippet_len` chars
+    fn log_parse_failure(&self, err: &dyn std::fmt::Display, text: &str) {
+        warn!("Failed to parse API response: {}", err);
+        if self.log_body_snippet_len > 0 {
+            let snippet: String = text.chars().take(self.log_body_snippet_len).collect();
+            warn!(
+                "Response body (first {} chars): {}",
+                self.log_body_snippet_len, snippet
+            );
+        }
+    }
+
+    /// [`Self::send_with_retries`], repeated against `fallback_api_url` when the
+    /// primary API is unreachable or answers with a server error
+    ///
+    /// Returns the response together with the base that served it, "primary" or "fallback".
+    async fn send(
+        &self,
+        url: &str,
+        api_key: Option<&str>,
+    ) -> anyhow::Result<(reqwest::Response, &'static str)> {
+        let primary = self.send_with_retries(url, api_key).await;
+        let Some(fallback_url) = self.fallback_url(url) else {
+            return primary.map(|response| (response, "primary"));
+        };
+        let reason = match &primary {
+            Ok(response) if response.status().is_server_error() => response.status().to_string(),
+            Err(e) if is_connection_error(e) => e.to_string(),
+            _ => return primary.map(|response| (response, "primary")),
+        };
+        drop(primary);
+
+        warn!("Primary API failed ({}), trying the fallback API", reason);
+        self.send_with_retries(&fallback_url, api_key)
+            .await
+            .map(|response| (response, "fallback"))
+    }
+
+    /// `url` with the primary API base replaced by `fallback_api_url`, if one is configured
+    fn fallback_url(&self, url: &str) -> Option<String> {
+        let fallback = self.fallback_api_url.as_deref()?;
+        let path = url.strip_prefix(self.api_url.as_str())?;
+        Some(format!("{}{}", fallback, path))
+    }
+
+    /// Send an authenticated GET request, retrying when the API is rate limiting
+    ///
+    /// 429 and 503 responses are retried up to [`MAX_RATE_LIMIT_RETRIES`] times,
+    /// waiting as long as their `Retry-After` header asks (see [`retry_delay`]).
+    /// Connection errors are retried up to [`MAX_CONNECTION_RETRIES`] times with
+    /// backoff unless `retry_on_connection_errors` is off.
+    /// `api_key` replaces the configured key for this request only.
+    async fn send_with_retries(
+        &self,
+        url: &str,
+        api_key: Option<&str>,
+    ) -> anyhow::Result<reqwest::Response> {
+        let mut attempt = 0;
+        loop {
+            let response = match self.send_once(url, api_key).await {
+                Ok(response) => response,
+                Err(e)
+                    if self.retry_on_connection_errors
+                        && attempt < MAX_CONNECTION_RETRIES
+                        && is_connection_error(&e) =>
+                {
+                    let delay = self.jitter(retry_delay(None, attempt, self.max_retry_after));
+                    warn!(
+                        "Request failed without a response ({}), retrying in {:?}",
+                        e, delay
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                    continue;
+                }
+                Err(e) => {
+                    if attempt > 0 {
+                        self.retries_exhausted
+                            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    }
+                    return Err(e);
+                }
+            };
+            let status = response.status();
+            let rate_limited = status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                || status == reqwest::StatusCode::SERVICE_UNAVAILABLE;
+            if !rate_limited || attempt >= MAX_RATE_LIMIT_RETRIES {
+                if attempt > 0 {
+                    let outcome = if status.is_success() {
+                        &self.retries_succeeded
+                    } else {
+                        &self.retries_exhausted
+                    };
+                    outcome.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                }
+                return Ok(response);
+            }
+
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok());
+            let delay = retry_delay(retry_after, attempt, self.max_retry_after);
+            // Only our own backoff is jittered; an explicit Retry-After is honored as given
+            let delay = match retry_after.map(|value| value.trim().parse::<u64>()) {
+                Some(Ok(_)) => delay,
+                _ => self.jitter(delay),
+            };
+            warn!("API responded with {}, retrying in {:?}", status, delay);
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Scale a backoff delay by a random factor between 0.9 and 1.1, so that clients
+    /// failing at the same moment do not retry in lockstep
+    fn jitter(&self, delay: Duration) -> Duration {
+        // splitmix64: any seed (including 0) gives a usable sequence, no external dependency
+        let mut state = self.rng_state.lock().unwrap();
+        *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = *state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        let unit = (z >> 11) as f64 / (1u64 << 53) as f64;
+        delay.mul_f64(0.9 + 0.2 * unit)
+    }
+
+    /// Send a single request, reporting it to the observer if one is set
+    async fn send_once(
+        &self,
+        url: &str,
+        api_key: Option<&str>,
+    ) -> anyhow::Result<reqwest::Response> {
+        if let Some(observer) = &self.observer {
+            observer.on_request(url);
+        }
+        let started = Instant::now();
+        self.api_calls
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+
+        let mut request = self
+            .client
+            .get(url)
+            .header("X-API-Key", api_key.unwrap_or(&self.api_key));
+        if let Some(id) = current_correlation_id() {
+            request = request.header(CORRELATION_ID_HEADER, id);
+        }
+
+        if let Some(secret) = &self.signing_secret {
+            let parsed = reqwest::Url::parse(url)?;
+            let path = match parsed.query() {
+                Some(query) => format!("{}?{}", parsed.path(), query),
+                None => parsed.path().to_string(),
+            };
+            let timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)?
+                .as_secs();
+            request = request.header(signing::TIMESTAMP_HEADER, timestamp).header(
+                signing::SIGNATURE_HEADER,
+                signing::sign(secret, &path, timestamp),
+            );
+        }
+
+        let result = request.send().await;
+
+        if let Some(observer) = &self.observer {
+            match &result {
+                Ok(response) => observer.on_response(response.status().as_u16(), started.elapsed()),
+                Err(e) => observer.on_error(&e.to_string()),
+            }
+        }
+
+        Ok(result?)
+    }
+
+    /// Read a response body chunk by chunk, aborting once it exceeds `max_response_bytes`
+    async fn read_body(&self, mut response: reqwest::Response) -> anyhow::Result<Vec<u8>> {
+        let limit = self.max_response_bytes;
+
+        if let Some(length) = response.content_length() {
+            if length > limit as u64 {
+                anyhow::bail!(
+                    "API response too large: {} bytes exceeds limit of {} bytes",
+                    length,
+                    limit
+                );
+            }
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if body.len() + chunk.len() > limit {
+                anyhow::bail!("API response too large: exceeds limit of {} bytes", limit);
+            }
+            body.extend_from_slice(&chunk);
+        }
+
+        Ok(body)
+    }
+
+    /// Read a response body and decode it to text, see [`decode_body`]
+    async fn read_text(&self, response: reqwest::Response) -> anyhow::Result<String> {
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let body = self.read_body(response).await?;
+        Ok(decode_body(&body, content_type.as_deref()))
+    }
+
+    /// Fetch a page outside the API, such as an employer's application page
+    ///
+    /// Sent without API key or signature; the body is capped at `max_response_bytes`.
+    /// The URL comes from the API payload, so every hop (up to [`MAX_EXTERNAL_REDIRECTS`]
+    /// redirects) must be http(s) and resolve to public addresses only, and the request
+    /// connects to exactly the addresses that were checked.
+    async fn fetch_external_text(&self, mut url: reqwest::Url) -> anyhow::Result<String> {
+        let _permit = self.request_permits.acquire().await?;
+
+        for _ in 0..=MAX_EXTERNAL_REDIRECTS {
+            let client = self.pinned_external_client(&url).await?;
+            let response = client.get(url.clone()).send().await?;
+            if response.status().is_redirection() {
+                let location = response
+                    .headers()
+                    .get(reqwest::header::LOCATION)
+                    .and_then(|value| value.to_str().ok())
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("Page returned {} without a location", response.status())
+                    })?;
+                url = url.join(location)?;
+                continue;
+            }
+            if !response.status().is_success() {
+                anyhow::bail!("Page returned {}", response.status());
+            }
+            return self.read_text(response).await;
+        }
+        anyhow::bail!("Page redirected more than {} times", MAX_EXTERNAL_REDIRECTS)
+    }
+
+    /// A client that does not follow redirects and connects `url`'s host only to
+    /// its checked public addresses, so DNS cannot be switched to an internal one
+    async fn pinned_external_client(&self, url: &reqwest::Url) -> anyhow::Result<Client> {
+        if !matches!(url.scheme(), "http" | "https") {
+            anyhow::bail!("not an http(s) URL");
+        }
+        let host = url
+            .host_str()
+            .ok_or_else(|| anyhow::anyhow!("URL has no host"))?;
+        let port = url.port_or_known_default().unwrap_or(80);
+        // IPv6 literals come in brackets
+        let literal_ip = host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<std::net::IpAddr>()
+            .ok();
+        let addrs: Vec<std::net::SocketAddr> = match literal_ip {
+            Some(ip) => vec![(ip, port).into()],
+            None => tokio::net::lookup_host((host, port)).await?.collect(),
+        };
+        if addrs.is_empty() {
+            anyhow::bail!("{} does not resolve", host);
+        }
+        for addr in &addrs {
+            let allowed = is_public_ip(addr.ip())
+                || (self.allow_loopback_application_pages && addr.ip().is_loopback());
+            if !allowed {
+                anyhow::bail!("{} resolves to non-public address {}", host, addr.ip());
+            }
+        }
+
+        let mut builder = Client::builder()
+            .use_native_tls()
+            .redirect(reqwest::redirect::Policy::none());
+        if literal_ip.is_none() {
+            builder = builder.resolve_to_addrs(host, &addrs);
+        }
+        Ok(builder.build()?)
+    }
+
+    /// Look up job details in the cache without touching the network
+    fn cached_job_details(&self, refnr: &str) -> Option<ApiJobDetails> {
+        self.details_cache.lock().unwrap().get(refnr)
+    }
 }
 
-struct SearchParams {
-    was: Option<String>,
-    wo: Option<String>,
-    umkreis: Option<u64>,
-    size: Option<u64>,
-    page: Option<u64>,
-    veroeffentlichtseit: Option<u64>,
-    arbeitszeit: Option<Vec<String>>,
+/// Whether an address is publicly routable, i.e. not loopback, private (RFC 1918,
+/// unique local), link-local (including cloud metadata at 169.254.169.254),
+/// shared, documentation, multicast or unspecified
+fn is_public_ip(ip: std::net::IpAddr) -> bool {
+    match ip {
+        std::net::IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                || a == 0
+                // Shared address space (RFC 6598)
+                || (a == 100 && (64..128).contains(&b)))
+        }
+        std::net::IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(v4) => is_public_ip(v4.into()),
+            None => {
+                let first = ip.segments()[0];
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    // Unique local (fc00::/7) and link-local (fe80::/10)
+                    || (first & 0xfe00) == 0xfc00
+                    || (first & 0xffc0) == 0xfe80)
+            }
+        },
+    }
 }
 
-// ============================================================================
-// MCP Server
-// ============================================================================
+/// Parse `arbeitszeitWochenstunden`: a number, or text such as "39,5", "20-30" or "20 bis 30 Std."
+fn parse_weekly_hours(value: &serde_json::Value) -> Option<WeeklyHours> {
+    fn hours(text: &str) -> Option<f64> {
+        let number: String = text
+            .trim()
+            .chars()
+            .take_while(|c| c.is_ascii_digit() || *c == ',' || *c == '.')
+            .collect();
+        number
+            .replace(',', ".")
+            .parse()
+            .ok()
+            .filter(|h: &f64| *h > 0.0 && *h <= 168.0)
+    }
 
-/// Jobsuche MCP Server
-#[mcp_server(
-    name = "Jobsuche MCP Server",
-    version = "0.3.1",
-    description = "AI-friendly job search integration using the German Federal Employment Agency API",
-    auth = "disabled"
-)]
-#[derive(Clone)]
-pub struct JobsucheMcpServer {
-    start_time: Instant,
-    client: Arc<JobsucheClient>,
-    config: Arc<JobsucheConfig>,
+    let (min, max) = match value {
+        serde_json::Value::Number(n) => {
+            let h = n.as_f64().filter(|h| *h > 0.0 && *h <= 168.0)?;
+            (h, h)
+        }
+        serde_json::Value::String(text) => {
+            let text = text.to_lowercase();
+            match text.split_once('-').or_else(|| text.split_once("bis")) {
+                Some((from, to)) => (hours(from)?, hours(to)?),
+                None => {
+                    let h = hours(&text)?;
+                    (h, h)
+                }
+            }
+        }
+        _ => return None,
+    };
+    (min <= max).then_some(WeeklyHours { min, max })
+}
+
+/// Keep only `fields` (plus `reference_number`) of a serialized result object
+fn project_fields(value: serde_json::Value, fields: &[String]) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.into_iter()
+                .filter(|(key, _)| key == "reference_number" || fields.iter().any(|f| f == key))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+/// Top-level search response fields the API documents but we do not deserialize
+const DOCUMENTED_SEARCH_FIELDS: &[&str] = &["facetten"];
+
+/// Top-level job details fields the API documents but we do not deserialize
+const DOCUMENTED_DETAILS_FIELDS: &[&str] = &[
+    "aktuelleVeroeffentlichungsdatum",
+    "allianzpartner",
+    "allianzpartnerName",
+    "alternativBerufe",
+    "angebotsart",
+    "anzahlOffeneStellen",
+    "arbeitgeberAdresse",
+    "arbeitgeberdarstellung",
+    "arbeitgeberdarstellungUrl",
+    "arbeitgeberHashId",
+    "arbeitszeitmodelle",
+    "befristung",
+    "beruf",
+    "betriebsgroesse",
+    "branche",
+    "branchengruppe",
+    "eintrittsdatum",
+    "fertigkeiten",
+    "fuehrerscheine",
+    "fuehrungskompetenzen",
+    "hashId",
+    "hauptDkz",
+    "istBetreut",
+    "istGoogleJobsRelevant",
+    "mobilitaet",
+    "modifikationsTimestamp",
+    "refnr",
+    "sprachkenntnisse",
+    "tarifvertrag",
+    "uebernahme",
+];
+
+/// Fail if a response carried top-level fields that we neither deserialize nor
+/// find in `documented` (`strict_schema`)
+fn check_unexpected_fields(
+    response: &str,
+    extra: &std::collections::HashMap<String, serde_json::Value>,
+    documented: &[&str],
+) -> anyhow::Result<()> {
+    let mut keys: Vec<&str> = extra
+        .keys()
+        .map(String::as_str)
+        .filter(|key| !documented.contains(key))
+        .collect();
+    if keys.is_empty() {
+        return Ok(());
+    }
+    keys.sort_unstable();
+    anyhow::bail!(
+        "Unexpected fields in {} response (strict_schema is enabled): {}",
+        response,
+        keys.join(", ")
+    )
+}
+
+/// Delay before retrying a rate-limited request
+///
+/// Honors a numeric `Retry-After` (in seconds) up to `cap`; longer values are
+/// clamped. A missing or non-numeric header falls back to exponential backoff
+/// starting at [`RATE_LIMIT_BACKOFF`].
+fn retry_delay(retry_after: Option<&str>, attempt: u32, cap: Duration) -> Duration {
+    match retry_after.and_then(|value| value.trim().parse::<u64>().ok()) {
+        Some(secs) => {
+            let requested = Duration::from_secs(secs);
+            if requested > cap {
+                warn!("Clamping Retry-After of {}s to {}s", secs, cap.as_secs());
+                cap
+            } else {
+                requested
+            }
+        }
+        None => {
+            if let Some(value) = retry_after {
+                warn!("Ignoring malformed Retry-After header: {:?}", value);
+            }
+            RATE_LIMIT_BACKOFF * 2u32.pow(attempt)
+        }
+    }
+}
+
+/// A non-success HTTP status from the API
+#[derive(Debug)]
+struct ApiStatusError(reqwest::StatusCode);
+
+impl std::fmt::Display for ApiStatusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "API error: {}", self.0)
+    }
+}
+
+impl std::error::Error for ApiStatusError {}
+
+/// Whether a failed request may succeed when repeated later
+///
+/// Rate limiting (429), server errors (5xx), timeouts and connection failures
+/// are transient; anything else, like 404 or an unparseable body, is not.
+fn is_retryable(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        if let Some(ApiStatusError(status)) = cause.downcast_ref() {
+            *status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+        } else if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+            e.is_timeout() || e.is_connect()
+        } else {
+            false
+        }
+    })
+}
+
+/// Whether a request failed to connect (refused, DNS failure) or timed out
+fn is_connection_error(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        cause
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(|e| e.is_connect() || e.is_timeout())
+    })
+}
+
+/// Why a single search request failed; only parse failures are worth retrying
+enum SearchFetchError {
+    Request(anyhow::Error),
+    Parse(serde_json::Error),
+    /// An HTML page where JSON was expected, with the start of its text
+    HtmlErrorPage(String),
+}
+
+impl SearchFetchError {
+    fn into_anyhow(self) -> anyhow::Error {
+        match self {
+            SearchFetchError::Request(e) => e,
+            SearchFetchError::Parse(e) => anyhow::anyhow!("Failed to parse API response: {}", e),
+            SearchFetchError::HtmlErrorPage(snippet) => {
+                anyhow::anyhow!("upstream returned an HTML error page: {}", snippet)
+            }
+        }
+    }
+}
+
+/// Decode a response body, tolerating mislabeled encodings
+///
+/// A charset other than UTF-8 declared in the content type is honored. Bodies
+/// that claim (or default to) UTF-8 but are not valid UTF-8 are decoded as
+/// Windows-1252, the Latin-1 superset some gateways emit for German text.
+fn decode_body(body: &[u8], content_type: Option<&str>) -> String {
+    let declared = content_type
+        .and_then(|ct| {
+            ct.split(';')
+                .filter_map(|part| part.trim().strip_prefix("charset="))
+                .next()
+        })
+        .and_then(|label| encoding_rs::Encoding::for_label(label.trim_matches('"').as_bytes()));
+
+    if let Some(encoding) = declared.filter(|e| *e != encoding_rs::UTF_8) {
+        return encoding.decode(body).0.into_owned();
+    }
+
+    match std::str::from_utf8(body) {
+        Ok(text) => text.to_string(),
+        Err(_) => {
+            warn!("Response body is not valid UTF-8, decoding as Windows-1252");
+            encoding_rs::WINDOWS_1252.decode(body).0.into_owned()
+        }
+    }
+}
+
+/// Query parameters sent to the Jobsuche search endpoint
+#[derive(Debug, Clone, Default)]
+pub struct SearchParams {
+    /// Search term (`was`)
+    pub was: Option<String>,
+    /// Location (`wo`)
+    pub wo: Option<String>,
+    /// Radius around the location in km (`umkreis`)
+    pub umkreis: Option<u64>,
+    /// Page size (`size`)
+    pub size: Option<u64>,
+    /// Page number, starting at 1 (`page`, translated to the API's `api_page_base`)
+    pub page: Option<u64>,
+    /// Days since publication (`veroeffentlichtseit`)
+    pub veroeffentlichtseit: Option<u64>,
+    /// Working time codes (`arbeitszeit`)
+    pub arbeitszeit: Option<Vec<String>>,
+    /// Contract duration codes (`befristung`): "1" fixed-term, "2" permanent
+    pub befristung: Option<Vec<String>>,
+}
+
+/// Build a stable, canonical key for a search
+///
+/// Two searches that would return the same results produce the same key:
+/// free-text values are trimmed, whitespace-collapsed and lowercased (the API
+/// matches case-insensitively), working time codes are sorted and deduplicated,
+/// and the parts are emitted in a fixed order. Values are percent-encoded, so a
+/// value containing `&` or `=` cannot pass for another parameter. Use this for
+/// any cache or request coalescing keyed by search.
+pub fn normalize_search_key(params: &SearchParams) -> String {
+    fn text(value: &str) -> String {
+        let value = value
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+        urlencoding::encode(&value).into_owned()
+    }
+
+    let mut parts = Vec::new();
+
+    if let Some(was) = params.was.as_deref().map(text).filter(|v| !v.is_empty()) {
+        parts.push(format!("was={}", was));
+    }
+    if let Some(wo) = params.wo.as_deref().map(text).filter(|v| !v.is_empty()) {
+        parts.push(format!("wo={}", wo));
+    }
+    if let Some(umkreis) = params.umkreis {
+        parts.push(format!("umkreis={}", umkreis));
+    }
+    if let Some(size) = params.size {
+        parts.push(format!("size={}", size));
+    }
+    if let Some(page) = params.page {
+        parts.push(format!("page={}", page));
+    }
+    if let Some(days) = params.veroeffentlichtseit {
+        parts.push(format!("veroeffentlichtseit={}", days));
+    }
+    if let Some(arbeitszeit) = &params.arbeitszeit {
+        let mut codes: Vec<String> = arbeitszeit.iter().map(|c| text(c)).collect();
+        codes.sort();
+        codes.dedup();
+        for code in codes {
+            parts.push(format!("arbeitszeit={}", code));
+        }
+    }
+    if let Some(befristung) = &params.befristung {
+        let mut codes: Vec<String> = befristung.iter().map(|c| text(c)).collect();
+        codes.sort();
+        codes.dedup();
+        parts.push(format!("befristung={}", codes.join(";")));
+    }
+
+    parts.sort();
+    parts.join("&")
 }
 
-impl Default for JobsucheMcpServer {
-    fn default() -> Self {
-        panic!("JobsucheMcpServer cannot be created with default(). Use JobsucheMcpServer::new() instead.")
+/// Format a work location as "City (postal code)"
+///
+/// Falls back to the region, then the country, when the city is missing, and
+/// to the bare postal code when no place name is known at all.
+fn format_location(
+    ort: Option<&str>,
+    plz: Option<&str>,
+    region: Option<&str>,
+    land: Option<&str>,
+) -> Option<String> {
+    fn non_empty(value: Option<&str>) -> Option<&str> {
+        value.map(str::trim).filter(|v| !v.is_empty())
+    }
+    let place = non_empty(ort).or(non_empty(region)).or(non_empty(land));
+
+    match (place, non_empty(plz)) {
+        (Some(place), Some(plz)) => Some(format!("{} ({})", place, plz)),
+        (Some(place), None) => Some(place.to_string()),
+        (None, Some(plz)) => Some(plz.to_string()),
+        (None, None) => None,
+    }
+}
+
+/// Clean up a reference number supplied by a caller
+///
+/// Trims surrounding whitespace and decodes URL-encoded input (e.g.
+/// `10000-1234567890-S%20` from a copied link). Rejects empty values and
+/// values containing whitespace or control characters.
+pub fn sanitize_refnr(input: &str) -> anyhow::Result<String> {
+    let trimmed = input.trim();
+    let decoded = if trimmed.contains('%') {
+        urlencoding::decode(trimmed)
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "Invalid reference number '{}': malformed URL encoding",
+                    trimmed
+                )
+            })?
+            .trim()
+            .to_string()
+    } else {
+        trimmed.to_string()
+    };
+
+    if decoded.is_empty() {
+        anyhow::bail!("Reference number must not be empty");
+    }
+    if decoded.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        anyhow::bail!(
+            "Invalid reference number '{}': must not contain spaces or control characters",
+            decoded.escape_debug()
+        );
+    }
+
+    Ok(decoded)
+}
+
+/// Check a reference number against the usual `10000-1234567890-S` shape
+fn explain_refnr(input: &str) -> RefnrExplanation {
+    let trimmed = input.trim();
+    let mut notes = Vec::new();
+    if trimmed.len() != input.len() {
+        notes.push("Surrounding whitespace was removed".to_string());
+    }
+
+    let reference_number = match sanitize_refnr(input) {
+        Ok(refnr) => refnr,
+        Err(e) => {
+            notes.push(e.to_string());
+            return RefnrExplanation {
+                reference_number: trimmed.to_string(),
+                looks_valid: false,
+                detail_url: format!(
+                    "https://www.arbeitsagentur.de/jobsuche/jobdetail/{}",
+                    urlencoding::encode(trimmed)
+                ),
+                notes,
+            };
+        }
+    };
+    if reference_number != trimmed {
+        notes.push(format!(
+            "URL-encoded characters were decoded from '{}'",
+            trimmed
+        ));
+    }
+
+    let parts: Vec<&str> = reference_number.split('-').collect();
+    let has_prefix =
+        parts.len() >= 2 && parts[0].chars().all(|c| c.is_ascii_digit()) && !parts[0].is_empty();
+    if !has_prefix {
+        notes.push("Does not start with a numeric prefix such as '10000-'".to_string());
+    }
+    if parts.len() != 3 {
+        notes.push(format!(
+            "Has {} part(s) separated by '-'; most reference numbers have 3, like 10000-1234567890-S",
+            parts.len()
+        ));
+    } else {
+        if parts[1].len() != 10 || !parts[1].chars().all(|c| c.is_ascii_digit()) {
+            notes.push(format!(
+                "The posting number '{}' is not the usual 10 digits, as is common for partner job boards",
+                parts[1]
+            ));
+        }
+        if parts[2] != "S" {
+            notes.push(format!("Ends in '-{}' instead of the usual '-S'", parts[2]));
+        }
+    }
+
+    RefnrExplanation {
+        looks_valid: has_prefix && parts.iter().all(|part| !part.is_empty()),
+        detail_url: format!(
+            "https://www.arbeitsagentur.de/jobsuche/jobdetail/{}",
+            urlencoding::encode(&reference_number)
+        ),
+        reference_number,
+        notes,
+    }
+}
+
+/// Append the API's `*` wildcard to the final token of a search term, unless already present
+fn with_trailing_wildcard(term: &str) -> String {
+    let term = term.trim_end();
+    if term.is_empty() || term.ends_with('*') {
+        term.to_string()
+    } else {
+        format!("{}*", term)
+    }
+}
+
+/// Lowercased word tokens of a job title, ignoring gender markers like "(m/w/d)"
+fn title_tokens(title: &str) -> std::collections::HashSet<String> {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|t| t.len() > 1 && !matches!(t.as_str(), "mwd" | "wmd" | "dmw"))
+        .collect()
+}
+
+/// Guess from a description whether the job allows working from home
+/// Explicit refusals ("keine Homeoffice-Möglichkeit", "nur vor Ort") win over mentions of
+/// home office or remote work; None if the text says neither.
+fn remote_work_hint(description: &str) -> Option<bool> {
+    const ON_SITE: [&str; 2] = ["nur vor ort", "ausschließlich vor ort"];
+    // Words that turn a following remote term into a refusal ("keine Homeoffice-Möglichkeit")
+    const NEGATIONS: [&str; 5] = ["kein", "keine", "keinen", "keinem", "ohne"];
+    const REMOTE: [&str; 7] = [
+        "homeoffice",
+        "home-office",
+        "home office",
+        "remote",
+        "mobiles arbeiten",
+        "mobile arbeit",
+        "telearbeit",
+    ];
+
+    let text = description.to_lowercase();
+    let negated = |pos: usize| {
+        text[..pos]
+            .split_whitespace()
+            .next_back()
+            .is_some_and(|word| {
+                NEGATIONS.contains(&word.trim_matches(|c: char| !c.is_alphanumeric()))
+            })
+    };
+    let refused = REMOTE
+        .iter()
+        .any(|term| text.match_indices(term).any(|(pos, _)| negated(pos)));
+    if refused || ON_SITE.iter().any(|phrase| text.contains(phrase)) {
+        Some(false)
+    } else if REMOTE.iter().any(|phrase| text.contains(phrase)) {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+/// Up to `n` distinct non-empty values, most frequent first (ties in order of appearance)
+fn most_frequent<'a>(values: impl IntoIterator<Item = &'a str>, n: usize) -> Vec<String> {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for value in values.into_iter().map(str::trim).filter(|v| !v.is_empty()) {
+        match counts.iter_mut().find(|(seen, _)| *seen == value) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((value, 1)),
+        }
+    }
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    counts
+        .into_iter()
+        .take(n)
+        .map(|(value, _)| value.to_string())
+        .collect()
+}
+
+/// Share of the seed title's tokens that also appear in the candidate title (0.0-1.0)
+fn title_overlap(seed: &str, candidate: &str) -> f64 {
+    let seed_tokens = title_tokens(seed);
+    if seed_tokens.is_empty() {
+        return 0.0;
+    }
+    let candidate_tokens = title_tokens(candidate);
+    seed_tokens.intersection(&candidate_tokens).count() as f64 / seed_tokens.len() as f64
+}
+
+/// Weighted token overlap of a listing with the title, employer and branch terms (0.0-1.0)
+///
+/// Only fields with a search term count towards the total weight.
+fn relevance_score(
+    params: &SearchJobsParams,
+    weights: &FieldWeights,
+    job: &ApiJobListing,
+    title: &str,
+) -> f64 {
+    let fields = [
+        (params.job_title.as_deref(), weights.title, title),
+        (
+            params.employer.as_deref(),
+            weights.employer,
+            job.arbeitgeber.as_str(),
+        ),
+        (params.branch.as_deref(), weights.branch, job.beruf.as_str()),
+    ];
+
+    let mut score = 0.0;
+    let mut total_weight = 0.0;
+    for (term, weight, field) in fields {
+        let Some(term) = term.filter(|t| !title_tokens(t).is_empty()) else {
+            continue;
+        };
+        let weight = weight.unwrap_or(1.0);
+        score += weight * title_overlap(term, field);
+        total_weight += weight;
+    }
+    if total_weight > 0.0 {
+        score / total_weight
+    } else {
+        0.0
+    }
+}
+
+/// Great-circle distance in km between two points given in degrees (haversine formula)
+fn haversine_km((lat1, lon1): (f64, f64), (lat2, lon2): (f64, f64)) -> f64 {
+    const EARTH_RADIUS_KM: f64 = 6371.0;
+    let d_lat = (lat2 - lat1).to_radians();
+    let d_lon = (lon2 - lon1).to_radians();
+    let a = (d_lat / 2.0).sin().powi(2)
+        + lat1.to_radians().cos() * lat2.to_radians().cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+/// Pick the application URL and `application_method` by source preference
+///
+/// `priority` orders "external", "partner" and "internal" (default: that order).
+/// Unavailable sources are skipped; the agency page always exists and is the
+/// last resort even if `priority` leaves it out.
+fn pick_application_url(
+    priority: Option<&[String]>,
+    refnr: &str,
+    external: Option<&str>,
+    partner: Option<&str>,
+) -> (String, &'static str) {
+    let internal = || {
+        (
+            format!("https://www.arbeitsagentur.de/jobsuche/jobdetail/{}", refnr),
+            "arbeitsagentur",
+        )
+    };
+    let order: Vec<&str> = match priority {
+        Some(priority) => priority.iter().map(String::as_str).collect(),
+        None => APPLICATION_URL_SOURCES.to_vec(),
+    };
+    for source in order {
+        match (source, external, partner) {
+            ("external", Some(url), _) => return (url.to_string(), "employer_direct"),
+            ("partner", _, Some(url)) => return (url.to_string(), "partner_board"),
+            ("internal", _, _) => return internal(),
+            _ => {}
+        }
+    }
+    internal()
+}
+
+/// Count jobs per ISO week of publication, oldest first, with undated jobs last as "unknown"
+fn weekly_buckets(jobs: &[JobSummary]) -> Vec<WeekBucket> {
+    let mut weeks: std::collections::BTreeMap<String, usize> = std::collections::BTreeMap::new();
+    let mut unknown = 0;
+    for job in jobs {
+        match job.published_date.as_deref().and_then(dates::iso_week) {
+            Some(week) => *weeks.entry(week).or_default() += 1,
+            None => unknown += 1,
+        }
+    }
+
+    let mut buckets: Vec<WeekBucket> = weeks
+        .into_iter()
+        .map(|(iso_week, count)| WeekBucket { iso_week, count })
+        .collect();
+    if unknown > 0 {
+        buckets.push(WeekBucket {
+            iso_week: "unknown".to_string(),
+            count: unknown,
+        });
+    }
+    buckets
+}
+
+/// Summarize posting ages (in days) and the result count into a [`MarketInsight`]
+fn market_insight(
+    total_results: Option<u64>,
+    ages: &[u64],
+    by_bundesland: Vec<(String, u64)>,
+) -> MarketInsight {
+    let mut sorted = ages.to_vec();
+    sorted.sort_unstable();
+
+    let median = match sorted.len() {
+        0 => None,
+        n if n % 2 == 1 => Some(sorted[n / 2] as f64),
+        n => Some((sorted[n / 2 - 1] + sorted[n / 2]) as f64 / 2.0),
+    };
+    let newest = sorted.iter().filter(|&&days| days <= 7).count() as u64;
+
+    let freshness = match median {
+        None => "no dated postings found",
+        Some(m) if m <= 7.0 => "many fresh postings",
+        Some(m) if m <= 30.0 => "mixed freshness",
+        Some(_) => "saturated/old postings",
+    };
+    let assessment = match total_results {
+        Some(0) => "no postings found".to_string(),
+        Some(total) if total < 20 => format!("{}, few openings overall ({})", freshness, total),
+        Some(total) if total > 1000 => format!("{}, large market ({} openings)", freshness, total),
+        _ => freshness.to_string(),
+    };
+
+    MarketInsight {
+        total_results,
+        median_days_since_published: median,
+        newest_count_last_7d: newest,
+        sample_size: sorted.len(),
+        assessment,
+        by_bundesland,
+    }
+}
+
+/// Key under which postings count as duplicates: sorted title tokens plus the city
+fn duplicate_key(job: &JobSummary) -> String {
+    let mut tokens: Vec<String> = title_tokens(&job.title).into_iter().collect();
+    tokens.sort();
+    let city = job.location.split(" (").next().unwrap_or(&job.location);
+    format!("{}|{}", tokens.join(" "), city.trim().to_lowercase())
+}
+
+/// Collapse postings sharing a [`duplicate_key`] but listed by different employers
+///
+/// The first posting of a cluster is kept as its representative and counts the
+/// others in `duplicate_count`. Several postings from the same employer are
+/// distinct openings and are all kept. Returns the remaining jobs and the
+/// number of representatives that absorbed at least one duplicate.
+fn collapse_duplicates(jobs: Vec<JobSummary>) -> (Vec<JobSummary>, usize) {
+    let mut representatives: std::collections::HashMap<String, usize> =
+        std::collections::HashMap::new();
+    let mut kept: Vec<JobSummary> = Vec::with_capacity(jobs.len());
+
+    for job in jobs {
+        let key = duplicate_key(&job);
+        match representatives.get(&key) {
+            Some(&idx) if !kept[idx].employer.eq_ignore_ascii_case(&job.employer) => {
+                kept[idx].duplicate_count += 1;
+            }
+            Some(_) => kept.push(job),
+            None => {
+                representatives.insert(key, kept.len());
+                kept.push(job);
+            }
+        }
+    }
+
+    let clusters = kept.iter().filter(|job| job.duplicate_count > 0).count();
+    (kept, clusters)
+}
+
+/// Compute an order-independent SHA-256 hash over a set of reference numbers
+pub fn result_hash<'a>(reference_numbers: impl IntoIterator<Item = &'a str>) -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut refnrs: Vec<&str> = reference_numbers.into_iter().collect();
+    refnrs.sort_unstable();
+    refnrs.dedup();
+
+    let mut hasher = Sha256::new();
+    for refnr in refnrs {
+        hasher.update(refnr.as_bytes());
+        hasher.update(b"\n");
+    }
+
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+// ============================================================================
+// MCP Server
+// ============================================================================
+
+/// Jobsuche MCP Server
+#[mcp_server(
+    name = "Jobsuche MCP Server",
+    version = "0.3.1",
+    description = "AI-friendly job search integration using the German Federal Employment Agency API",
+    auth = "disabled"
+)]
+#[derive(Clone)]
+pub struct JobsucheMcpServer {
+    start_time: Instant,
+    client: Arc<JobsucheClient>,
+    config: Arc<JobsucheConfig>,
+    favorites: Arc<FavoritesStore>,
+    metrics: Arc<LatencyRecorder>,
+    requests: Arc<RequestTracker>,
+    synonyms: Arc<SynonymMap>,
+    recently_viewed: Arc<Mutex<VecDeque<String>>>,
+    /// Cancel tokens of running batch searches and whether they were cancelled
+    batch_cancellations: Arc<Mutex<HashMap<String, bool>>>,
+}
+
+/// Releases a batch's cancel token when dropped, also if the batch is aborted
+struct CancelRegistration {
+    tokens: Arc<Mutex<HashMap<String, bool>>>,
+    token: String,
+}
+
+impl Drop for CancelRegistration {
+    fn drop(&mut self) {
+        self.tokens.lock().unwrap().remove(&self.token);
+    }
+}
+
+impl Default for JobsucheMcpServer {
+    fn default() -> Self {
+        panic!("JobsucheMcpServer cannot be created with default(). Use JobsucheMcpServer::new() instead.")
+    }
+}
+
+impl JobsucheMcpServer {
+    #[instrument]
+    pub async fn new() -> anyhow::Result<Self> {
+        info!("Initializing Jobsuche MCP Server");
+
+        let config = JobsucheConfig::load()?;
+        Self::with_config(config).await
+    }
+
+    /// Create a server from an explicit configuration instead of the environment
+    pub async fn with_config(config: JobsucheConfig) -> anyhow::Result<Self> {
+        let server = Self::build(config, None)?;
+        server.startup_check().await?;
+        Ok(server)
+    }
+
+    /// Create a server that reports every upstream API call to `observer`
+    pub async fn with_observer(
+        config: JobsucheConfig,
+        observer: Arc<dyn JobsucheObserver>,
+    ) -> anyhow::Result<Self> {
+        let server = Self::build(config, Some(observer))?;
+        server.startup_check().await?;
+        Ok(server)
+    }
+
+    /// With `run_startup_check`, run one small search to verify the API is reachable and accepts the key
+    async fn startup_check(&self) -> anyhow::Result<()> {
+        if !self.config.run_startup_check {
+            return Ok(());
+        }
+        let params = SearchParams {
+            was: Some("Entwickler".to_string()),
+            size: Some(1),
+            ..Default::default()
+        };
+        self.client
+            .search_uncached(&params, None)
+            .await
+            .map_err(|e| {
+                anyhow::anyhow!(
+                "Startup check failed: the API at {} is unreachable or rejected the API key: {}",
+                self.config.api_url,
+                e
+            )
+            })?;
+        info!("Startup check passed");
+        Ok(())
+    }
+
+    fn build(
+        config: JobsucheConfig,
+        observer: Option<Arc<dyn JobsucheObserver>>,
+    ) -> anyhow::Result<Self> {
+        config.validate()?;
+        let config = Arc::new(config);
+
+        info!("Configuration loaded: API URL = {}", config.api_url);
+
+        let client = JobsucheClient::new(&config, observer)?;
+        if client.api_key == DEFAULT_API_KEY {
+            warn!(
+                "Using the shared public API key '{}', which is throttled aggressively. \
+                 Set JOBSUCHE_API_KEY to a dedicated key for reliable access.",
+                DEFAULT_API_KEY
+            );
+        }
+        let favorites = FavoritesStore::open(config.favorites_path.as_ref().map(Into::into))?;
+        let synonyms = SynonymMap::load(config.synonyms_path.as_deref().map(std::path::Path::new))?;
+
+        info!("Jobsuche MCP Server initialized successfully");
+
+        Ok(Self {
+            start_time: Instant::now(),
+            client: Arc::new(client),
+            config,
+            favorites: Arc::new(favorites),
+            metrics: Arc::new(LatencyRecorder::new()),
+            requests: Arc::new(RequestTracker::new()),
+            synonyms: Arc::new(synonyms),
+            recently_viewed: Arc::new(Mutex::new(VecDeque::new())),
+            batch_cancellations: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    fn get_uptime_seconds(&self) -> u64 {
+        self.start_time.elapsed().as_secs()
+    }
+
+    /// Page size for a search: the requested one, else the configured search default, capped at the maximum
+    fn search_page_size(&self, requested: Option<u64>) -> u64 {
+        requested
+            .or(self.config.default_search_page_size)
+            .unwrap_or(self.config.default_page_size)
+            .clamp(1, self.config.max_page_size)
+    }
+
+    /// Check a numeric tool parameter against its allowed range
+    ///
+    /// Out-of-range values are an error with `strict_params`, otherwise they
+    /// are clamped with a warning.
+    fn checked_param(&self, name: &str, value: u64, min: u64, max: u64) -> anyhow::Result<u64> {
+        if (min..=max).contains(&value) {
+            return Ok(value);
+        }
+        if self.config.strict_params {
+            anyhow::bail!(
+                "{} must be between {} and {}, got {}",
+                name,
+                min,
+                max,
+                value
+            );
+        }
+        let clamped = value.clamp(min, max);
+        warn!(
+            "Clamping {}={} to {} (allowed: {}-{})",
+            name, value, clamped, min, max
+        );
+        Ok(clamped)
+    }
+
+    /// Cap a batch size at its limit, or reject it with `strict_batch_limits`
+    fn batch_limit<T: PartialOrd + std::fmt::Display>(
+        &self,
+        name: &str,
+        value: T,
+        max: T,
+    ) -> anyhow::Result<T> {
+        if value <= max {
+            return Ok(value);
+        }
+        if self.config.strict_batch_limits {
+            anyhow::bail!("{} exceeds the batch limit of {}, got {}", name, max, value);
+        }
+        Ok(max)
+    }
+
+    /// The radius to search with
+    ///
+    /// A radius without a location is dropped with a warning; a location without
+    /// a radius gets `default_radius`.
+    fn search_radius(params: &SearchJobsParams, default_radius: Option<u64>) -> Option<u64> {
+        let has_location = params
+            .location
+            .as_deref()
+            .is_some_and(|loc| !loc.trim().is_empty());
+        match params.radius_km {
+            Some(radius) if !has_location => {
+                warn!(
+                    "Ignoring radius_km={} because no location was given",
+                    radius
+                );
+                None
+            }
+            None if has_location => default_radius,
+            radius => radius,
+        }
+    }
+
+    fn parse_employment_type(emp_type: &str) -> Option<String> {
+        match emp_type.to_lowercase().as_str() {
+            "fulltime" | "full" | "vollzeit" | "vz" => Some("vz".to_string()),
+            "parttime" | "part" | "teilzeit" | "tz" => Some("tz".to_string()),
+            "mini" | "minijob" | "mini_job" => Some("minijob".to_string()),
+            "home" | "homeoffice" | "home_office" | "ho" => Some("ho".to_string()),
+            "shift" | "schicht" | "snw" => Some("snw".to_string()),
+            _ => None,
+        }
+    }
+
+    /// API `befristung` code for a contract type
+    fn parse_contract_type(contract_type: &str) -> Option<String> {
+        match contract_type.to_lowercase().as_str() {
+            "temporary" | "fixed_term" | "befristet" => Some("1".to_string()),
+            "permanent" | "unbefristet" => Some("2".to_string()),
+            _ => None,
+        }
+    }
+
+    /// Count listings per city and postal code, most openings first
+    fn aggregate_locations<'a>(
+        listings: impl IntoIterator<Item = &'a ApiJobListing>,
+    ) -> Vec<LocationCount> {
+        let mut counts: std::collections::HashMap<(String, Option<String>), u64> =
+            std::collections::HashMap::new();
+        for job in listings {
+            let city = job
+                .arbeitsort
+                .ort
+                .clone()
+                .filter(|ort| !ort.trim().is_empty())
+                .unwrap_or_else(|| "Unbekannt".to_string());
+            *counts
+                .entry((city, job.arbeitsort.plz.clone()))
+                .or_default() += 1;
+        }
+
+        let mut locations: Vec<LocationCount> = counts
+            .into_iter()
+            .map(|((city, postal_code), count)| LocationCount {
+                city,
+                postal_code,
+                count,
+            })
+            .collect();
+        locations.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| a.city.cmp(&b.city))
+                .then_with(|| a.postal_code.cmp(&b.postal_code))
+        });
+        locations
+    }
+
+    fn filter_catalog() -> FilterCatalog {
+        fn values(entries: &[(&str, &str)]) -> Vec<FilterValue> {
+            entries
+                .iter()
+                .map(|(value, description)| FilterValue {
+                    value: value.to_string(),
+                    description: description.to_string(),
+                })
+                .collect()
+        }
+
+        FilterCatalog {
+            employment_types: values(&[
+                ("fulltime", "Full-time positions (Vollzeit)"),
+                ("parttime", "Part-time positions (Teilzeit)"),
+                ("mini_job", "Marginal employment / Minijob"),
+                ("home_office", "Remote or home office positions"),
+                (
+                    "shift",
+                    "Shift, night and weekend work (Schicht/Nacht/Wochenende)",
+                ),
+            ]),
+            contract_types: values(&[
+                ("permanent", "Permanent contracts (unbefristet)"),
+                ("temporary", "Fixed-term contracts (befristet)"),
+            ]),
+            sort_options: values(&[(
+                "relevance",
+                "Default ordering as returned by the Federal Employment Agency",
+            )]),
+        }
+    }
+
+    /// JSON Schema of every tool's result, keyed by tool name
+    ///
+    /// Schemas describe the default snake_case keys, see `output_case`.
+    fn output_schemas() -> std::collections::BTreeMap<String, serde_json::Value> {
+        fn schema<T: JsonSchema>() -> serde_json::Value {
+            schemars::schema_for!(T).to_value()
+        }
+
+        [
+            ("search_jobs", schema::<SearchJobsResult>()),
+            ("get_job_details", schema::<GetJobDetailsResult>()),
+            ("batch_search_jobs", schema::<BatchSearchJobsResult>()),
+            ("cancel_batch_search", schema::<CancelBatchResult>()),
+            ("batch_search_digest", schema::<BatchDigest>()),
+            ("export_search_results_ndjson", schema::<String>()),
+            ("search_jobs_collect", schema::<CollectResult>()),
+            ("get_job_details_bulk", schema::<BulkDetailsResult>()),
+            ("prefetch_details", schema::<PrefetchResult>()),
+            ("refine_search", schema::<RefineResult>()),
+            ("fetch_application_page", schema::<ApplicationPageResult>()),
+            ("get_similar_jobs", schema::<SimilarJobsResult>()),
+            ("get_market_insight", schema::<MarketInsight>()),
+            ("get_new_jobs_since", schema::<NewJobsSinceResult>()),
+            (
+                "get_employer_locations",
+                schema::<EmployerLocationsResult>(),
+            ),
+            ("compare_locations", schema::<CompareLocationsResult>()),
+            ("explain_reference_number", schema::<RefnrExplanation>()),
+            ("save_favorite", schema::<SaveFavoriteResult>()),
+            ("remove_favorite", schema::<RemoveFavoriteResult>()),
+            ("list_favorites", schema::<ListFavoritesResult>()),
+            ("get_recently_viewed", schema::<RecentlyViewedResult>()),
+            ("list_filters", schema::<FilterCatalog>()),
+            (
+                "get_output_schemas",
+                schema::<std::collections::BTreeMap<String, serde_json::Value>>(),
+            ),
+            ("get_server_status", schema::<JobsucheServerStatus>()),
+        ]
+        .into_iter()
+        .map(|(tool, schema)| (tool.to_string(), schema))
+        .collect()
+    }
+
+    /// Run a search and widen the radius until `min_results` is reached
+    ///
+    /// Each step doubles the radius actually used, starting from
+    /// `ESCALATION_START_RADIUS_KM` when the first search had none, and stops
+    /// at `MAX_RADIUS_KM`. The last result is returned either way.
+    async fn run_search_with_min_results(
+        &self,
+        params: SearchJobsParams,
+    ) -> anyhow::Result<SearchJobsResult> {
+        const ESCALATION_START_RADIUS_KM: u64 = 25;
+
+        let mut result = self.run_search(params.clone()).await?;
+        let Some(min_results) = params.min_results else {
+            return Ok(result);
+        };
+        if params
+            .location
+            .as_deref()
+            .is_none_or(|loc| loc.trim().is_empty())
+        {
+            warn!(
+                "Ignoring min_results={} because no location was given",
+                min_results
+            );
+            return Ok(result);
+        }
+
+        let mut escalations = 0;
+        loop {
+            let found = result.total_results.unwrap_or(result.jobs_count as u64);
+            let radius = result.applied_filters.radius_km;
+            if found >= min_results || radius.is_some_and(|r| r >= MAX_RADIUS_KM) {
+                break;
+            }
+            let next = radius
+                .map_or(ESCALATION_START_RADIUS_KM, |r| r * 2)
+                .clamp(ESCALATION_START_RADIUS_KM, MAX_RADIUS_KM);
+            info!(
+                "Found {} of {} jobs, widening radius to {} km",
+                found, min_results, next
+            );
+            result = self
+                .run_search(SearchJobsParams {
+                    radius_km: Some(next),
+                    ..params.clone()
+                })
+                .await?;
+            escalations += 1;
+        }
+
+        result.radius_escalations = escalations;
+        Ok(result)
+    }
+
+    /// Fetch job details, serving them from the details cache when possible
+    ///
+    /// Returns the converted details and whether they came from the cache.
+    async fn fetch_job_details(
+        &self,
+        refnr: &str,
+        api_key: Option<&ApiKey>,
+    ) -> anyhow::Result<(GetJobDetailsResult, bool)> {
+        let (details, from_cache) = self.fetch_api_job_details(refnr, api_key).await?;
+        Ok((self.convert_details(refnr, details), from_cache))
+    }
+
+    /// Convert job details and, if enabled, detect the description's language
+    fn convert_details(&self, refnr: &str, details: ApiJobDetails) -> GetJobDetailsResult {
+        let mut result = Self::convert_job_details(
+            refnr,
+            details,
+            self.config.application_url_priority.as_deref(),
+        );
+        if self.config.detect_description_language {
+            result.description_language = result
+                .description
+                .as_deref()
+                .and_then(language::detect_language)
+                .map(str::to_string);
+        }
+        result
+    }
+
+    async fn fetch_api_job_details(
+        &self,
+        refnr: &str,
+        api_key: Option<&ApiKey>,
+    ) -> anyhow::Result<(ApiJobDetails, bool)> {
+        // An override key bypasses the cache so that it is checked by the API every time
+        if let Some(details) = self
+            .client
+            .cached_job_details(refnr)
+            .filter(|_| api_key.is_none())
+        {
+            info!("Job details cache hit for: {}", refnr);
+            return Ok((details, true));
+        }
+
+        let details = self
+            .client
+            .job_details(refnr, api_key.map(|key| key.0.as_str()))
+            .await?;
+        Ok((details, false))
+    }
+
+    /// Convert job details, preferring application URLs in `url_priority` order
+    fn convert_job_details(
+        refnr: &str,
+        details: ApiJobDetails,
+        url_priority: Option<&[String]>,
+    ) -> GetJobDetailsResult {
+        let mut locations = details
+            .arbeitsorte
+            .iter()
+            .flatten()
+            .filter_map(|loc| loc.adresse.as_ref())
+            .filter_map(|addr| {
+                format_location(
+                    addr.ort.as_deref(),
+                    addr.plz.as_deref(),
+                    addr.region.as_deref(),
+                    addr.land.as_deref(),
+                )
+            });
+        let location_str = locations.next();
+        let additional_locations: Vec<String> = locations.collect();
+
+        let entry_period = details
+            .eintrittszeitraum
+            .as_ref()
+            .map(|dr| match (&dr.von, &dr.bis) {
+                (Some(von), Some(bis)) => format!("{} - {}", von, bis),
+                (Some(von), None) => format!("ab {}", von),
+                (None, Some(bis)) => format!("bis {}", bis),
+                (None, None) => String::new(),
+            });
+        let weekly_hours_range = details
+            .arbeitszeit_wochenstunden
+            .as_ref()
+            .and_then(parse_weekly_hours);
+        let entry_period_start = details
+            .eintrittszeitraum
+            .as_ref()
+            .and_then(|dr| dr.von.as_deref())
+            .and_then(dates::iso_date);
+        let entry_period_end = details
+            .eintrittszeitraum
+            .as_ref()
+            .and_then(|dr| dr.bis.as_deref())
+            .and_then(dates::iso_date);
+        let extracted_skills = details
+            .stellenbeschreibung
+            .as_deref()
+            .map(|description| skills::extract_skills(&text::decode_entities(description)))
+            .unwrap_or_default();
+        // The explicit flag is authoritative, the description only a fallback
+        let home_office = details.arbeitszeit_heimarbeit_telearbeit.or_else(|| {
+            details
+                .stellenbeschreibung
+                .as_deref()
+                .and_then(remote_work_hint)
+        });
+
+        // Determine the best application URL, by default with the fallback hierarchy:
+        // 1. external_url (employer's application page)
+        // 2. partner_url (partner job board)
+        // 3. Internal Bundesagentur für Arbeit URL (always available)
+        // Blind ads (Chiffre) are always applied to through the agency.
+        let (application_url, application_method) = if details.chiffrenummer.is_some() {
+            (
+                format!("https://www.arbeitsagentur.de/jobsuche/jobdetail/{}", refnr),
+                "cipher",
+            )
+        } else {
+            pick_application_url(
+                url_priority,
+                refnr,
+                details.externe_url.as_deref(),
+                details.allianzpartner_url.as_deref(),
+            )
+        };
+
+        let application_instructions = match application_method {
+            "employer_direct" => format!(
+                "Apply directly on the website of {}: {}",
+                details.arbeitgeber.as_deref().unwrap_or("the employer"),
+                application_url
+            ),
+            "partner_board" => format!("Apply via the partner job board: {}", application_url),
+            "cipher" => format!(
+                "The employer is anonymous (blind ad). Apply through the Bundesagentur für Arbeit \
+                 at {} and quote the cipher number {}.",
+                application_url,
+                details.chiffrenummer.as_deref().unwrap_or_default()
+            ),
+            _ => format!("View and apply on arbeitsagentur.de: {}", application_url),
+        };
+
+        GetJobDetailsResult {
+            reference_number: refnr.to_string(),
+            title: details.titel,
+            description: details.stellenbeschreibung,
+            description_raw: None,
+            description_language: None,
+            extracted_skills,
+            employer: details.arbeitgeber,
+            location: location_str,
+            additional_locations,
+            employment_type: details
+                .arbeitszeit_vollzeit
+                .map(|vz| if vz { "Vollzeit" } else { "Teilzeit" }.to_string()),
+            salary: details.verguetung,
+            contract_duration: details.vertragsdauer,
+            job_type: details.stellenangebots_art,
+            first_published: details.erste_veroeffentlichungsdatum,
+            only_for_disabled: details.nur_fuer_schwerbehinderte,
+            fulltime: details.arbeitszeit_vollzeit,
+            home_office,
+            entry_period,
+            entry_period_start,
+            entry_period_end,
+            weekly_hours: weekly_hours_range.filter(|h| h.min == h.max).map(|h| h.min),
+            weekly_hours_range,
+            is_minor_employment: details.ist_geringfuegige_beschaeftigung,
+            is_temp_agency: details.ist_arbeitnehmer_ueberlassung,
+            career_changer_suitable: details.quereinstieg_geeignet,
+            external_url: details.externe_url,
+            partner_url: details.allianzpartner_url,
+            application_url,
+            application_method: application_method.to_string(),
+            application_note: details.chiffrenummer.as_ref().map(|chiffre| {
+                format!(
+                    "This is a blind ad: the employer is anonymous. Apply through the \
+                     Bundesagentur für Arbeit and quote the cipher number {}.",
+                    chiffre
+                )
+            }),
+            contact: details
+                .angebotskontakt
+                .as_ref()
+                .and_then(ApiContact::to_contact_info)
+                .or_else(|| {
+                    details
+                        .kontakt
+                        .as_ref()
+                        .and_then(ApiContact::to_contact_info)
+                }),
+            cipher_number: details.chiffrenummer,
+            application_instructions: Some(application_instructions),
+            raw: None,
+        }
+    }
+
+    /// Run `search` for each of `pages` concurrently, returning the outcomes in page order
+    ///
+    /// Failed pages are reported in place rather than failing the whole round,
+    /// so the caller can ignore errors of pages past the point where it stops.
+    async fn fetch_pages(
+        &self,
+        search: &SearchJobsParams,
+        pages: std::ops::RangeInclusive<u64>,
+    ) -> anyhow::Result<Vec<anyhow::Result<SearchJobsResult>>> {
+        if pages.start() == pages.end() {
+            let result = self
+                .run_search(SearchJobsParams {
+                    page: Some(*pages.start()),
+                    ..search.clone()
+                })
+                .await;
+            return Ok(vec![result]);
+        }
+
+        let mut tasks = tokio::task::JoinSet::new();
+        for (idx, page) in pages.enumerate() {
+            let server = self.clone();
+            let page_params = SearchJobsParams {
+                page: Some(page),
+                ..search.clone()
+            };
+            let correlation_id = current_correlation_id();
+            tasks.spawn(
+                with_correlation_id(correlation_id, async move {
+                    (idx, server.run_search(page_params).await)
+                })
+                .in_current_span(),
+            );
+        }
+
+        let mut results: Vec<Option<anyhow::Result<SearchJobsResult>>> =
+            (0..tasks.len()).map(|_| None).collect();
+        while let Some(joined) = tasks.join_next().await {
+            let (idx, result) = joined?;
+            results[idx] = Some(result);
+        }
+        Ok(results
+            .into_iter()
+            .map(|r| r.expect("every page task reports back"))
+            .collect())
+    }
+
+    /// Run a job search; shared by `search_jobs` and the tools built on top of it
+    async fn run_search(&self, params: SearchJobsParams) -> anyhow::Result<SearchJobsResult> {
+        with_correlation_id(params.correlation_id.clone(), self.run_search_inner(params)).await
+    }
+
+    async fn run_search_inner(&self, params: SearchJobsParams) -> anyhow::Result<SearchJobsResult> {
+        match params.query_syntax.as_deref().map(str::trim) {
+            None | Some("plain") => {}
+            Some("boolean") => return self.run_boolean_search(params).await,
+            Some(other) => anyhow::bail!(
+                "Unknown query_syntax '{}', expected \"plain\" or \"boolean\"",
+                other
+            ),
+        }
+        if params.expand_synonyms == Some(true) {
+            let synonyms = params
+                .job_title
+                .as_deref()
+                .map(|title| self.synonyms.lookup(title))
+                .unwrap_or_default();
+            if !synonyms.is_empty() {
+                let primary = params.job_title.clone().unwrap_or_default();
+                let terms: Vec<String> = std::iter::once(primary).chain(synonyms).collect();
+                info!("Expanding search to synonyms: {:?}", terms);
+                return self.run_merged_search(params, terms).await;
+            }
+        }
+        self.run_single_search(params).await
+    }
+
+    /// Run a `query_syntax: "boolean"` search
+    ///
+    /// The API has no boolean operators, so each `OR` alternative is searched
+    /// separately and merged like synonyms, and `NOT` terms are dropped from the
+    /// merged jobs by title. Synonym expansion does not apply.
+    async fn run_boolean_search(
+        &self,
+        params: SearchJobsParams,
+    ) -> anyhow::Result<SearchJobsResult> {
+        let Some(title) = params.job_title.as_deref() else {
+            return self.run_single_search(params).await;
+        };
+        let query = query::parse_boolean_query(title)?;
+        info!(
+            "Boolean query: any of {:?}, none of {:?}",
+            query.any_of, query.none_of
+        );
+
+        let mut result = if query.any_of.len() == 1 {
+            self.run_single_search(SearchJobsParams {
+                job_title: Some(query.any_of[0].clone()),
+                ..params
+            })
+            .await?
+        } else {
+            self.run_merged_search(params, query.any_of).await?
+        };
+
+        if !query.none_of.is_empty() {
+            let before = result.jobs.len();
+            result.jobs.retain(|job| {
+                !query
+                    .none_of
+                    .iter()
+                    .any(|term| text::contains_folded(&job.title, term))
+            });
+            result.filtered_out_count += before - result.jobs.len();
+            result.jobs_count = result.jobs.len();
+            result.result_hash =
+                result_hash(result.jobs.iter().map(|job| job.reference_number.as_str()));
+        }
+        Ok(result)
+    }
+
+    /// Search several title terms in parallel and merge the results
+    ///
+    /// Used for synonyms and boolean `OR` queries. Paging metadata comes from
+    /// the search for the first term, which must succeed; jobs found by several
+    /// terms are reported once, attributed to the first term.
+    async fn run_merged_search(
+        &self,
+        params: SearchJobsParams,
+        terms: Vec<String>,
+    ) -> anyhow::Result<SearchJobsResult> {
+        let start = Instant::now();
+
+        let mut tasks = tokio::task::JoinSet::new();
+        for (idx, term) in terms.iter().enumerate() {
+            let server = self.clone();
+            let term_params = SearchJobsParams {
+                job_title: Some(term.clone()),
+                expand_synonyms: None,
+                ..params.clone()
+            };
+            let correlation_id = current_correlation_id();
+            tasks.spawn(
+                with_correlation_id(correlation_id, async move {
+                    (idx, server.run_single_search(term_params).await)
+                })
+                .in_current_span(),
+            );
+        }
+
+        let mut results: Vec<Option<SearchJobsResult>> = vec![None; terms.len()];
+        while let Some(joined) = tasks.join_next().await {
+            let (idx, result) = joined?;
+            match result {
+                Ok(result) => results[idx] = Some(result),
+                // The first term must succeed; failed others only narrow the results
+                Err(e) if idx == 0 => return Err(e),
+                Err(e) => warn!("Search for '{}' failed: {}", terms[idx], e),
+            }
+        }
+
+        let mut results = results.into_iter().zip(&terms);
+        let (merged, primary_term) = results.next().expect("primary search is always run");
+        let mut merged = merged.expect("primary search failure returns early");
+        let mut seen: std::collections::HashSet<String> = merged
+            .jobs
+            .iter()
+            .map(|job| job.reference_number.clone())
+            .collect();
+        for job in &mut merged.jobs {
+            job.matched_term = Some(primary_term.clone());
+        }
+
+        for (result, term) in results {
+            let Some(result) = result else { continue };
+            merged.filtered_out_count += result.filtered_out_count;
+            merged.clusters_collapsed += result.clusters_collapsed;
+            for mut job in result.jobs {
+                if seen.insert(job.reference_number.clone()) {
+                    job.matched_term = Some(term.clone());
+                    merged.jobs.push(job);
+                }
+            }
+        }
+
+        merged.jobs_count = merged.jobs.len();
+        merged.result_hash =
+            result_hash(merged.jobs.iter().map(|job| job.reference_number.as_str()));
+        merged.search_duration_ms = start.elapsed().as_millis() as u64;
+        Ok(merged)
+    }
+
+    async fn run_single_search(
+        &self,
+        params: SearchJobsParams,
+    ) -> anyhow::Result<SearchJobsResult> {
+        info!("Searching jobs with params: {:?}", params);
+        let start = Instant::now();
+
+        let published_after = match params.published_after.as_deref() {
+            Some(date) => Some(dates::parse_api_date(date).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Invalid published_after date '{}', expected YYYY-MM-DD",
+                    date
+                )
+            })?),
+            None => None,
+        };
+        let origin = match (params.origin_lat, params.origin_lon) {
+            (Some(lat), Some(lon))
+                if (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lon) =>
+            {
+                Some((lat, lon))
+            }
+            (None, None) => None,
+            (Some(_), Some(_)) => {
+                anyhow::bail!("origin_lat must be within -90..90 and origin_lon within -180..180")
+            }
+            _ => anyhow::bail!("origin_lat and origin_lon must be given together"),
+        };
+        if let Some(weights) = &params.field_weights {
+            let all = [weights.title, weights.employer, weights.branch];
+            if all.iter().flatten().any(|w| !w.is_finite() || *w < 0.0) {
+                anyhow::bail!("field_weights must be non-negative numbers");
+            }
+        }
+
+        // Build search query
+        let mut search_terms = Vec::new();
+        if let Some(ref title) = params.job_title {
+            if params.title_wildcard == Some(true) {
+                search_terms.push(with_trailing_wildcard(title));
+            } else {
+                search_terms.push(title.clone());
+            }
+        }
+        if let Some(ref employer) = params.employer {
+            search_terms.push(employer.clone());
+        }
+        if let Some(ref branch) = params.branch {
+            search_terms.push(branch.clone());
+        }
+        // The same term in several fields (e.g. job_title and employer) is sent once
+        let mut seen_terms = std::collections::HashSet::new();
+        search_terms.retain(|term| seen_terms.insert(term.trim().to_lowercase()));
+
+        let default_employment_type_applied =
+            params.employment_type.is_none() && self.config.default_employment_type.is_some();
+        let arbeitszeit: Option<Vec<String>> = params
+            .employment_type
+            .as_ref()
+            .or(self.config.default_employment_type.as_ref())
+            .map(|types| {
+                let mut codes: Vec<String> = Vec::new();
+                for code in types.iter().filter_map(|t| Self::parse_employment_type(t)) {
+                    if !codes.contains(&code) {
+                        codes.push(code);
+                    }
+                }
+                codes
+            });
+        let befristung: Option<Vec<String>> = params.contract_type.as_ref().map(|types| {
+            let mut codes: Vec<String> = Vec::new();
+            for code in types.iter().filter_map(|t| Self::parse_contract_type(t)) {
+                if !codes.contains(&code) {
+                    codes.push(code);
+                }
+            }
+            codes
+        });
+
+        let page_size = match params.page_size {
+            Some(size) => self.checked_param("page_size", size, 1, self.config.max_page_size)?,
+            None => self.search_page_size(None),
+        };
+
+        let radius = Self::search_radius(&params, self.config.default_radius_km)
+            .map(|radius| self.checked_param("radius_km", radius, 0, MAX_RADIUS_KM))
+            .transpose()?;
+        let applied_filters = AppliedFilters {
+            radius_km: radius,
+            default_radius_applied: params.radius_km.is_none() && radius.is_some(),
+            employment_type: arbeitszeit.clone(),
+            default_employment_type_applied,
+        };
+        let published_since_days = params
+            .published_since_days
+            .map(|days| {
+                self.checked_param("published_since_days", days, 0, MAX_PUBLISHED_SINCE_DAYS)
+            })
+            .transpose()?;
+
+        let search_params = SearchParams {
+            was: if search_terms.is_empty() {
+                None
+            } else {
+                Some(search_terms.join(" "))
+            },
+            wo: params.location.clone(),
+            umkreis: radius,
+            size: Some(page_size),
+            page: params.page,
+            veroeffentlichtseit: published_since_days,
+            arbeitszeit,
+            befristung,
+        };
+
+        let api_key = params.api_key_override.as_ref().map(|key| key.0.as_str());
+        let executed_query = self.client.redacted_search_url(&search_params);
+        let response = self.client.search(&search_params, api_key).await?;
+        let possible_invalid_location = self.config.detect_invalid_location
+            && response.stellenangebote.is_empty()
+            && response.max_ergebnisse.unwrap_or(0) == 0
+            && search_params
+                .wo
+                .as_deref()
+                .is_some_and(|wo| !wo.trim().is_empty())
+            && self.has_results_anywhere(&search_params, api_key).await;
+        let raw = if params.include_raw == Some(true) {
+            Some(serde_json::from_str(&response.raw)?)
+        } else {
+            None
+        };
+        let now = chrono::Utc::now();
+
+        // Client-side filters on data already present in the search results
+        let mut listings = response.stellenangebote;
+        let listings_before_filters = listings.len();
+
+        if params.external_application_only == Some(true) {
+            listings.retain(|job| job.externe_url.is_some());
+        }
+
+        if let Some(excluded) = params.exclude_employers.as_ref().filter(|e| !e.is_empty()) {
+            listings.retain(|job| {
+                !excluded
+                    .iter()
+                    .any(|name| text::employer_matches(&job.arbeitgeber, name))
+            });
+        }
+
+        if let Some(postal_codes) = params
+            .postal_codes
+            .as_ref()
+            .filter(|codes| !codes.is_empty())
+        {
+            listings.retain(|job| {
+                job.arbeitsort.plz.as_deref().is_some_and(|plz| {
+                    postal_codes
+                        .iter()
+                        .any(|code| !code.trim().is_empty() && plz.starts_with(code.trim()))
+                })
+            });
+        }
+
+        if let Some(cutoff) = published_after {
+            // Listings without a parseable date are kept, they cannot be ruled out
+            listings.retain(|job| {
+                job.aktuelle_veroeffentlichungsdatum
+                    .as_deref()
+                    .and_then(dates::parse_api_date)
+                    .is_none_or(|published| published >= cutoff)
+            });
+        }
+
+        // Detail lookups of all detail-based filters share one budget
+        let mut detail_fetches = 0;
+        let mut detail_fetch_limit_reached = false;
+        if params.recently_updated_only == Some(true) {
+            // Listings left unchecked once the detail budget is spent are dropped
+            let mut refreshed = Vec::with_capacity(listings.len());
+            for job in listings {
+                let Some(latest) = job
+                    .aktuelle_veroeffentlichungsdatum
+                    .as_deref()
+                    .and_then(dates::parse_api_date)
+                else {
+                    continue;
+                };
+                if !self.spend_detail_fetch(&mut detail_fetches) {
+                    detail_fetch_limit_reached = true;
+                    break;
+                }
+                if self
+                    .was_republished(&job, latest, params.api_key_override.as_ref())
+                    .await
+                {
+                    refreshed.push(job);
+                }
+            }
+            listings = refreshed;
+        }
+        if let Some(min_chars) = params.min_description_chars.filter(|&n| n > 0) {
+            // Listings left unchecked once the detail budget is spent are dropped
+            let mut substantive = Vec::with_capacity(listings.len());
+            for job in listings {
+                if !self.spend_detail_fetch(&mut detail_fetches) {
+                    detail_fetch_limit_reached = true;
+                    break;
+                }
+                if self
+                    .has_description_of(&job, min_chars, params.api_key_override.as_ref())
+                    .await
+                {
+                    substantive.push(job);
+                }
+            }
+            listings = substantive;
+        }
+
+        let filtered_out_count = listings_before_filters - listings.len();
+
+        // Listings left unchecked once the detail budget is spent count as unsalaried
+        let mut salaried = std::collections::HashSet::new();
+        if params.prioritize_salary == Some(true) {
+            for job in &listings {
+                if !self.spend_detail_fetch(&mut detail_fetches) {
+                    detail_fetch_limit_reached = true;
+                    break;
+                }
+                if self
+                    .discloses_salary(job, params.api_key_override.as_ref())
+                    .await
+                {
+                    salaried.insert(job.refnr.clone());
+                }
+            }
+        }
+
+        let clean_titles = params.clean_titles.unwrap_or(self.config.clean_titles);
+
+        let jobs: Vec<JobSummary> = listings
+            .iter()
+            .map(|job| {
+                let place = &job.arbeitsort;
+                let location = format_location(
+                    place.ort.as_deref(),
+                    place.plz.as_deref(),
+                    place.region.as_deref(),
+                    place.land.as_deref(),
+                )
+                .unwrap_or_default();
+
+                // Listings carry no partner URL
+                let (application_url, _) = pick_application_url(
+                    self.config.application_url_priority.as_deref(),
+                    &job.refnr,
+                    job.externe_url.as_deref(),
+                    None,
+                );
+
+                let (raw_title, title_is_fallback) = titles::listing_title(
+                    job.titel.as_deref(),
+                    &job.beruf,
+                    &job.arbeitgeber,
+                    &location,
+                    &self.config.fallback_title,
+                );
+                let (title, title_raw) = if clean_titles {
+                    let cleaned = titles::clean_title(&raw_title);
+                    if cleaned != raw_title {
+                        (cleaned, Some(raw_title))
+                    } else {
+                        (raw_title, None)
+                    }
+                } else {
+                    (raw_title, None)
+                };
+
+                JobSummary {
+                    reference_number: job.refnr.clone(),
+                    title_raw,
+                    title_is_fallback,
+                    region: place.region.clone(),
+                    employer: job.arbeitgeber.clone(),
+                    location,
+                    published_date: job.aktuelle_veroeffentlichungsdatum.clone(),
+                    days_since_published: job
+                        .aktuelle_veroeffentlichungsdatum
+                        .as_deref()
+                        .and_then(|date| dates::days_since(date, now)),
+                    external_url: job.externe_url.clone(),
+                    application_url,
+                    duplicate_count: 0,
+                    matched_term: None,
+                    relevance_score: params
+                        .field_weights
+                        .as_ref()
+                        .map(|weights| relevance_score(&params, weights, job, &title)),
+                    distance_from_origin_km: origin
+                        .zip(place.koordinaten.and_then(|k| k.lat.zip(k.lon)))
+                        .map(|(origin, coords)| haversine_km(origin, coords)),
+                    title,
+                }
+            })
+            .collect();
+
+        let (mut jobs, clusters_collapsed) = if params.dedupe_similar == Some(true) {
+            collapse_duplicates(jobs)
+        } else {
+            (jobs, 0)
+        };
+        if params.field_weights.is_some() {
+            // Stable, so equally scored jobs keep the API's order
+            jobs.sort_by(|a, b| {
+                b.relevance_score
+                    .partial_cmp(&a.relevance_score)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+        }
+        if origin.is_some() && params.sort_by_distance == Some(true) {
+            jobs.sort_by(
+                |a, b| match (a.distance_from_origin_km, b.distance_from_origin_km) {
+                    (Some(a), Some(b)) => a.total_cmp(&b),
+                    (a, b) => a.is_none().cmp(&b.is_none()),
+                },
+            );
+        }
+        if params.prioritize_salary == Some(true) {
+            // Stable, so each group keeps the order from above
+            jobs.sort_by_key(|job| !salaried.contains(&job.reference_number));
+        }
+
+        let duration = start.elapsed();
+        info!(
+            "Search completed: {} jobs found in {:?}",
+            jobs.len(),
+            duration
+        );
+
+        let result_hash = result_hash(jobs.iter().map(|job| job.reference_number.as_str()));
+
+        Ok(SearchJobsResult {
+            total_results: response.max_ergebnisse,
+            current_page: response.page,
+            page_size: response.size,
+            jobs_count: jobs.len(),
+            jobs,
+            filtered_out_count,
+            clusters_collapsed,
+            result_hash,
+            search_duration_ms: duration.as_millis() as u64,
+            raw,
+            possible_invalid_location,
+            compact_jobs: None,
+            applied_filters,
+            detail_fetch_limit_reached,
+            radius_escalations: 0,
+            executed_query,
+            served_by: match self.config.fallback_api_url {
+                Some(_) => response.served_by.to_string(),
+                None => String::new(),
+            },
+            weekly_buckets: Vec::new(),
+        })
+    }
+
+    /// Whether the search finds anything once its location filter is dropped
+    async fn has_results_anywhere(
+        &self,
+        search_params: &SearchParams,
+        api_key: Option<&str>,
+    ) -> bool {
+        let probe = SearchParams {
+            wo: None,
+            umkreis: None,
+            // Only whether anything matches is read, so one listing is enough
+            size: Some(1),
+            page: None,
+            ..search_params.clone()
+        };
+        match self.client.search(&probe, api_key).await {
+            Ok(response) => {
+                let found = !response.stellenangebote.is_empty()
+                    || response.max_ergebnisse.unwrap_or(0) > 0;
+                if found {
+                    info!(
+                        "No jobs in {:?}, but the search has results elsewhere",
+                        search_params.wo
+                    );
+                }
+                found
+            }
+            Err(e) => {
+                warn!("Location probe failed: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Remember a viewed job, moving it to the front if it was viewed before
+    fn record_viewed(&self, refnr: &str) {
+        let capacity = self.config.recently_viewed_capacity;
+        if capacity == 0 {
+            return;
+        }
+
+        let mut viewed = self.recently_viewed.lock().unwrap();
+        viewed.retain(|r| r != refnr);
+        viewed.push_front(refnr.to_string());
+        viewed.truncate(capacity);
+    }
+
+    /// Whether a listing's details, fetched with the search's `api_key`, state a salary (`verguetung`)
+    async fn discloses_salary(&self, job: &ApiJobListing, api_key: Option<&ApiKey>) -> bool {
+        match self.fetch_api_job_details(&job.refnr, api_key).await {
+            Ok((details, _)) => details
+                .verguetung
+                .is_some_and(|salary| !salary.trim().is_empty()),
+            Err(e) => {
+                warn!(
+                    "Could not fetch details for {} to check the salary: {}",
+                    job.refnr, e
+                );
+                false
+            }
+        }
+    }
+
+    /// Count a detail lookup against `max_detail_fetches_per_search`; false once it is spent
+    fn spend_detail_fetch(&self, fetches: &mut usize) -> bool {
+        if *fetches >= self.config.max_detail_fetches_per_search {
+            warn!(
+                "Stopping detail lookups after {} (max_detail_fetches_per_search)",
+                fetches
+            );
+            return false;
+        }
+        *fetches += 1;
+        true
+    }
+
+    /// Whether a listing's description has at least `min_chars` characters
+    ///
+    /// The details are fetched with the search's `api_key`. Missing
+    /// descriptions and failing detail fetches count as too short.
+    async fn has_description_of(
+        &self,
+        job: &ApiJobListing,
+        min_chars: usize,
+        api_key: Option<&ApiKey>,
+    ) -> bool {
+        match self.fetch_api_job_details(&job.refnr, api_key).await {
+            Ok((details, _)) => details
+                .stellenbeschreibung
+                .is_some_and(|description| description.trim().chars().count() >= min_chars),
+            Err(e) => {
+                warn!(
+                    "Could not fetch details for {} to check the description: {}",
+                    job.refnr, e
+                );
+                false
+            }
+        }
+    }
+
+    /// Whether a listing's latest publication date is later than its first one
+    ///
+    /// The first publication date is only available in the job details, which
+    /// are fetched (or taken from the cache) with the search's `api_key`.
+    /// Listings with missing dates or failing detail fetches count as not
+    /// re-published.
+    async fn was_republished(
+        &self,
+        job: &ApiJobListing,
+        latest: chrono::NaiveDate,
+        api_key: Option<&ApiKey>,
+    ) -> bool {
+        match self.fetch_api_job_details(&job.refnr, api_key).await {
+            Ok((details, _)) => details
+                .erste_veroeffentlichungsdatum
+                .as_deref()
+                .and_then(dates::parse_api_date)
+                .is_some_and(|first| latest > first),
+            Err(e) => {
+                warn!(
+                    "Could not fetch details for {} to check re-publication: {}",
+                    job.refnr, e
+                );
+                false
+            }
+        }
+    }
+
+    /// Register a tool call for graceful shutdown and time it for the latency metrics
+    fn begin_tool(&self, tool: &'static str) -> anyhow::Result<(InFlightGuard, LatencyTimer<'_>)> {
+        let request = self.requests.begin()?;
+        Ok((request, self.metrics.start(tool)))
+    }
+
+    /// Run a tool body, failing once it exceeds `tool_timeout_secs`
+    ///
+    /// On expiry the body is dropped, so work in progress (including partial
+    /// batch results) is discarded.
+    async fn with_tool_timeout<T>(
+        &self,
+        tool: &str,
+        body: impl std::future::Future<Output = anyhow::Result<T>>,
+    ) -> anyhow::Result<T> {
+        let limit = self.config.tool_timeout_secs;
+        match tokio::time::timeout(Duration::from_secs(limit), body).await {
+            Ok(result) => result,
+            Err(_) => {
+                warn!("{} timed out after {}s", tool, limit);
+                anyhow::bail!("{} timed out after {}s", tool, limit)
+            }
+        }
+    }
+
+    /// Cancel the running batch search started with `token`
+    ///
+    /// The batch stops before its next sub-search and returns the results
+    /// gathered so far. Returns `false` if no running batch uses the token.
+    pub fn cancel(&self, token: &str) -> bool {
+        match self.batch_cancellations.lock().unwrap().get_mut(token) {
+            Some(cancelled) => {
+                *cancelled = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Register a running batch's cancel token, rejecting one already in use
+    fn register_cancel_token(&self, token: &str) -> anyhow::Result<CancelRegistration> {
+        let mut tokens = self.batch_cancellations.lock().unwrap();
+        if tokens.contains_key(token) {
+            anyhow::bail!(
+                "cancel_token '{}' is already used by a running batch search",
+                token
+            );
+        }
+        tokens.insert(token.to_string(), false);
+        Ok(CancelRegistration {
+            tokens: self.batch_cancellations.clone(),
+            token: token.to_string(),
+        })
+    }
+
+    fn batch_cancelled(&self, token: Option<&str>) -> bool {
+        token.is_some_and(|token| {
+            self.batch_cancellations
+                .lock()
+                .unwrap()
+                .get(token)
+                .copied()
+                .unwrap_or(false)
+        })
+    }
+
+    /// Stop accepting tool calls and wait for in-flight ones to complete
+    ///
+    /// Waits at most `shutdown_grace_secs`. Returns `true` if every in-flight
+    /// call finished within the grace period.
+    pub async fn shutdown(&self) -> bool {
+        let grace = Duration::from_secs(self.config.shutdown_grace_secs);
+        info!(
+            "Shutting down, waiting up to {:?} for {} in-flight requests",
+            grace,
+            self.requests.in_flight()
+        );
+
+        let drained = self.requests.drain(grace).await;
+        if !drained {
+            warn!(
+                "Shutdown grace period expired with {} requests still in flight",
+                self.requests.in_flight()
+            );
+        }
+        drained
+    }
+}
+
+#[mcp_tools]
+impl JobsucheMcpServer {
+    /// Search for jobs in Germany using the Federal Employment Agency database
+    #[instrument(skip(self), fields(correlation_id = params.correlation_id.as_deref()))]
+    pub async fn search_jobs(
+        &self,
+        params: SearchJobsParams,
+    ) -> anyhow::Result<Cased<SearchJobsResult>> {
+        let _call = self.begin_tool("search_jobs")?;
+        let compact = params.compact == Some(true);
+        let group_by_week = params.group_by_week == Some(true);
+        let mut result = self
+            .with_tool_timeout("search_jobs", self.run_search_with_min_results(params))
+            .await?;
+        if group_by_week {
+            result.weekly_buckets = weekly_buckets(&result.jobs);
+        }
+        if compact {
+            result.compact_jobs =
+                Some(result.jobs.drain(..).map(CompactJobSummary::from).collect());
+        }
+        Ok(Cased::new(result, self.config.output_case))
+    }
+
+    /// Get detailed information about a specific job posting
+    #[instrument(skip(self), fields(correlation_id = params.correlation_id.as_deref()))]
+    pub async fn get_job_details(
+        &self,
+        params: GetJobDetailsParams,
+    ) -> anyhow::Result<Cased<GetJobDetailsResult>> {
+        let params = GetJobDetailsParams {
+            reference_number: sanitize_refnr(&params.reference_number)?,
+            ..params
+        };
+        info!("Getting job details for: {}", params.reference_number);
+        let _call = self.begin_tool("get_job_details")?;
+        let correlation_id = params.correlation_id.clone();
+
+        self.with_tool_timeout(
+            "get_job_details",
+            with_correlation_id(correlation_id, async move {
+                let (details, _) = self
+                    .fetch_api_job_details(
+                        &params.reference_number,
+                        params.api_key_override.as_ref(),
+                    )
+                    .await?;
+                let raw = if params.include_raw == Some(true) {
+                    Some(serde_json::from_str(&details.raw)?)
+                } else {
+                    None
+                };
+                let mut result = GetJobDetailsResult {
+                    raw,
+                    ..self.convert_details(&params.reference_number, details)
+                };
+                if params
+                    .clean_description
+                    .unwrap_or(self.config.clean_description)
+                {
+                    if let Some(description) = result.description.take() {
+                        let cleaned = text::clean_description(&description);
+                        if cleaned != description {
+                            result.description_raw = Some(description);
+                        }
+                        result.description = Some(cleaned);
+                    }
+                }
+                self.record_viewed(&params.reference_number);
+
+                info!("Job details retrieved successfully");
+                Ok(Cased::new(result, self.config.output_case))
+            }),
+        )
+        .await
+    }
+
+    /// Perform multiple job searches in a single operation
+    ///
+    /// This tool allows you to search for different types of jobs simultaneously,
+    /// making it perfect for comparing opportunities across employers, locations,
+    /// or job types. Each search can have different parameters and will return
+    /// results independently.
+    ///
+    /// # Examples
+    /// - Compare employers: `{"searches": [{"name": "BARMER", "employer": "BARMER", "location": "Wuppertal"}, {"name": "Siemens", "employer": "Siemens", "location": "Wuppertal"}], "max_details_per_search": 3}`
+    /// - Different job types: `{"searches": [{"name": "Sekretariat", "job_title": "Sekretärin"}, {"name": "Sport", "job_title": "Schwimm"}]}`
+    #[instrument(skip(self), fields(correlation_id = params.correlation_id.as_deref()))]
+    pub async fn batch_search_jobs(
+        &self,
+        params: BatchSearchJobsParams,
+    ) -> anyhow::Result<BatchSearchJobsResult> {
+        let _call = self.begin_tool("batch_search_jobs")?;
+        let correlation_id = params.correlation_id.clone();
+
+        self.with_tool_timeout(
+            "batch_search_jobs",
+            with_correlation_id(correlation_id, async move {
+                let start = Instant::now();
+                let searches_count =
+                    self.batch_limit("searches", params.searches.len(), MAX_BATCH_SEARCHES)?;
+
+                info!("Performing batch search with {} searches", searches_count);
+
+                let max_details = self.batch_limit(
+                    "max_details_per_search",
+                    params.max_details_per_search.unwrap_or(2),
+                    MAX_BATCH_DETAILS,
+                )?;
+                let mut results = Vec::new();
+                let mut cache_hits = 0;
+                let mut cancelled = false;
+
+                let cancel_token = params.cancel_token.as_deref();
+                let _cancel_registration = cancel_token
+                    .map(|token| self.register_cancel_token(token))
+                    .transpose()?;
+
+                // Process each search
+                for (search_idx, search_item) in
+                    params.searches.iter().take(searches_count).enumerate()
+                {
+                    if self.batch_cancelled(cancel_token) {
+                        info!("Batch search cancelled after {} searches", search_idx);
+                        cancelled = true;
+                        break;
+                    }
+                    // Small delay between searches to avoid rate limiting (except first)
+                    if search_idx > 0 {
+                        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+                    }
+                    info!("Processing search: {}", search_item.name);
+                    let item_start = Instant::now();
+
+                    // Perform search
+                    let search_result = match self
+                        .run_search(search_item.search_params(max_details))
+                        .await
+                    {
+                        Ok(result) => result,
+                        Err(e) => {
+                            // If search fails, add error result and continue
+                            results.push(BatchSearchItemResult {
+                                search_name: search_item.name.clone(),
+                                total_results: None,
+                                jobs_count: 0,
+                                jobs: Vec::new(),
+                                error: Some(format!("Search failed: {}", e)),
+                                duration_ms: item_start.elapsed().as_millis() as u64,
+                                projected_jobs: None,
+                            });
+                            continue;
+                        }
+                    };
+
+                    // Fetch details if requested (with delay to respect rate limits)
+                    let mut jobs_with_details = Vec::new();
+                    if max_details > 0 {
+                        for (detail_idx, job) in search_result
+                            .jobs
+                            .iter()
+                            .take(max_details as usize)
+                            .enumerate()
+                        {
+                            // Small delay between detail fetches (except first in this search)
+                            if detail_idx > 0 {
+                                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                            }
+
+                            match self.fetch_job_details(&job.reference_number, None).await {
+                                Ok((details, from_cache)) => {
+                                    if from_cache {
+                                        cache_hits += 1;
+                                    }
+                                    jobs_with_details.push(details);
+                                }
+                                Err(e) => {
+                                    info!(
+                                        "Failed to fetch details for {} in search '{}': {}",
+                                        job.reference_number, search_item.name, e
+                                    );
+                                    // Continue with other jobs even if one fails
+                                }
+                            }
+                        }
+                    }
+
+                    let jobs_count = jobs_with_details.len();
+                    let (jobs, projected_jobs) = match &params.detail_fields {
+                        Some(fields) => {
+                            let projected = jobs_with_details
+                                .iter()
+                                .map(|job| Ok(project_fields(serde_json::to_value(job)?, fields)))
+                                .collect::<anyhow::Result<Vec<_>>>()?;
+                            (Vec::new(), Some(projected))
+                        }
+                        None => (jobs_with_details, None),
+                    };
+
+                    results.push(BatchSearchItemResult {
+                        search_name: search_item.name.clone(),
+                        total_results: search_result.total_results,
+                        jobs_count,
+                        jobs,
+                        error: None,
+                        duration_ms: item_start.elapsed().as_millis() as u64,
+                        projected_jobs,
+                    });
+                }
+
+                let duration = start.elapsed();
+                info!(
+                    "Batch search completed: {} searches in {:?}",
+                    results.len(),
+                    duration
+                );
+
+                Ok(BatchSearchJobsResult {
+                    searches_count: results.len(),
+                    results,
+                    cache_hits,
+                    cancelled,
+                    total_duration_ms: duration.as_millis() as u64,
+                })
+            }),
+        )
+        .await
+    }
+
+    /// Cancel a running batch_search_jobs call
+    ///
+    /// Pass the `cancel_token` given to batch_search_jobs. The batch stops
+    /// before its next search and returns what it has gathered so far with
+    /// `cancelled: true`.
+    #[instrument(skip(self))]
+    pub async fn cancel_batch_search(
+        &self,
+        params: CancelBatchParams,
+    ) -> anyhow::Result<CancelBatchResult> {
+        let _timer = self.metrics.start("cancel_batch_search");
+        Ok(CancelBatchResult {
+            cancelled: self.cancel(&params.cancel_token),
+        })
+    }
+
+    /// Run a batch of searches and return a compact digest of each
+    ///
+    /// Takes the same `searches` as batch_search_jobs (max 5), but instead of
+    /// job details returns per search its total count and the most frequent
+    /// titles and employers among the first results. Fetches no details, so
+    /// it suits a quick comparison before drilling down with
+    /// batch_search_jobs. `max_details_per_search`, `cancel_token` and
+    /// `detail_fields` are ignored.
+    #[instrument(skip(self), fields(correlation_id = params.correlation_id.as_deref()))]
+    pub async fn batch_search_digest(
+        &self,
+        params: BatchSearchJobsParams,
+    ) -> anyhow::Result<BatchDigest> {
+        let _call = self.begin_tool("batch_search_digest")?;
+        const DIGEST_PAGE_SIZE: u64 = 25;
+        const TOP_N: usize = 5;
+        let correlation_id = params.correlation_id.clone();
+
+        self.with_tool_timeout(
+            "batch_search_digest",
+            with_correlation_id(correlation_id, async move {
+                let searches_count =
+                    self.batch_limit("searches", params.searches.len(), MAX_BATCH_SEARCHES)?;
+                let mut searches = Vec::new();
+                for (search_idx, search_item) in
+                    params.searches.iter().take(searches_count).enumerate()
+                {
+                    // Same spacing as batch_search_jobs to avoid rate limiting
+                    if search_idx > 0 {
+                        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+                    }
+                    let item = match self
+                        .run_search(search_item.search_params(DIGEST_PAGE_SIZE))
+                        .await
+                    {
+                        Ok(result) => BatchDigestItem {
+                            search_name: search_item.name.clone(),
+                            total_results: result.total_results,
+                            top_titles: most_frequent(
+                                result.jobs.iter().map(|job| job.title.as_str()),
+                                TOP_N,
+                            ),
+                            top_employers: most_frequent(
+                                result.jobs.iter().map(|job| job.employer.as_str()),
+                                TOP_N,
+                            ),
+                            error: None,
+                        },
+                        Err(e) => BatchDigestItem {
+                            search_name: search_item.name.clone(),
+                            total_results: None,
+                            top_titles: Vec::new(),
+                            top_employers: Vec::new(),
+                            error: Some(format!("Search failed: {}", e)),
+                        },
+                    };
+                    searches.push(item);
+                }
+
+                info!("Batch digest completed: {} searches", searches.len());
+                Ok(BatchDigest {
+                    searches_count: searches.len(),
+                    searches,
+                })
+            }),
+        )
+        .await
+    }
+
+    /// Export search results as newline-delimited JSON (NDJSON)
+    ///
+    /// Fetches up to 10 pages starting at `page` (default 1) and emits one
+    /// `JobSummary` JSON object per line, without a trailing newline. Jobs that
+    /// appear on more than one page are only emitted once.
+    #[instrument(skip(self))]
+    pub async fn export_search_results_ndjson(
+        &self,
+        params: SearchJobsParams,
+    ) -> anyhow::Result<String> {
+        let _call = self.begin_tool("export_search_results_ndjson")?;
+        const MAX_PAGES: u64 = 10;
+
+        let first_page = params.page.unwrap_or(1).max(1);
+        let mut seen = std::collections::HashSet::new();
+        let mut lines = Vec::new();
+
+        for page in first_page..first_page + MAX_PAGES {
+            let result = self
+                .run_search(SearchJobsParams {
+                    page: Some(page),
+                    ..params.clone()
+                })
+                .await?;
+
+            let requested_size = self.search_page_size(params.page_size);
+            let collapsed: usize = result.jobs.iter().map(|job| job.duplicate_count).sum();
+            let received = result.jobs_count + result.filtered_out_count + collapsed;
+
+            for job in result.jobs {
+                if seen.insert(job.reference_number.clone()) {
+                    lines.push(serde_json::to_string(&job)?);
+                }
+            }
+
+            if (received as u64) < requested_size {
+                break;
+            }
+        }
+
+        info!("Exported {} jobs as NDJSON", lines.len());
+        Ok(lines.join("\n"))
+    }
+
+    /// Collect the unique jobs from several result pages
+    ///
+    /// Fetches up to `max_pages` pages starting at `search.page` (default 1),
+    /// merges them and drops jobs already seen on an earlier page. Stops early
+    /// at the last page or once `max_results` jobs were collected. Unlike
+    /// export_search_results_ndjson, the page budget is set by the caller and
+    /// `pages_fetched`/`api_calls` report what it cost.
+    ///
+    /// The API has no sort parameter to pin, so its ordering can shift between
+    /// page requests (e.g. when postings are added). The merged list never holds
+    /// a job twice; jobs that moved onto an already fetched page can be missed,
+    /// which `cross_page_duplicates` hints at.
+    #[instrument(skip(self))]
+    pub async fn search_jobs_collect(
+        &self,
+        params: CollectParams,
+    ) -> anyhow::Result<CollectResult> {
+        let _call = self.begin_tool("search_jobs_collect")?;
+        let start = Instant::now();
+        let max_pages = self.checked_param("max_pages", params.max_pages, 1, 10)?;
+        let max_results = self.checked_param("max_results", params.max_results, 1, 1000)? as usize;
+
+        let parallel_pages = params
+            .parallel_pages
+            .map(|n| self.checked_param("parallel_pages", n, 1, 5))
+            .transpose()?
+            .unwrap_or(1);
+
+        let first_page = params.search.page.unwrap_or(1).max(1);
+        let requested_size = self.search_page_size(params.search.page_size);
+        let api_calls_before = self
+            .client
+            .api_calls
+            .load(std::sync::atomic::Ordering::Relaxed);
+        let mut seen = std::collections::HashSet::new();
+        let mut jobs = Vec::new();
+        let mut total_results = None;
+        let mut pages_fetched = 0;
+        let mut cross_page_duplicates = 0;
+        let mut sequential_rounds = 0;
+
+        let mut last_page = first_page + max_pages - 1;
+        let mut next_page = first_page;
+        'pages: while next_page <= last_page {
+            // Until the first page reports the total, it is unknown which pages exist
+            let round_end = if pages_fetched == 0 {
+                next_page
+            } else {
+                (next_page + parallel_pages - 1).min(last_page)
+            };
+            let results = self
+                .fetch_pages(&params.search, next_page..=round_end)
+                .await?;
+            sequential_rounds += 1;
+
+            // Merged in page order, so dedupe and stopping work as if fetched one by one
+            for (page, result) in (next_page..=round_end).zip(results) {
+                // Errors of speculative pages past the stop point are never reached
+                let result = result?;
+                pages_fetched += 1;
+                if total_results.is_none() && parallel_pages > 1 {
+                    if let Some(total) = result.total_results {
+                        last_page = last_page.min(total.div_ceil(requested_size).max(first_page));
+                    }
+                }
+                total_results = total_results.or(result.total_results);
+
+                let collapsed: usize = result.jobs.iter().map(|job| job.duplicate_count).sum();
+                let received = result.jobs_count + result.filtered_out_count + collapsed;
+
+                for job in result.jobs {
+                    if !seen.insert(job.reference_number.clone()) {
+                        debug!(
+                            "Job {} seen again on page {}, result order shifted",
+                            job.reference_number, page
+                        );
+                        cross_page_duplicates += 1;
+                    } else if jobs.len() < max_results {
+                        jobs.push(job);
+                    }
+                }
+
+                if jobs.len() >= max_results || (received as u64) < requested_size {
+                    break 'pages;
+                }
+            }
+            next_page = round_end + 1;
+        }
+
+        let api_calls = self
+            .client
+            .api_calls
+            .load(std::sync::atomic::Ordering::Relaxed)
+            - api_calls_before;
+        info!(
+            "Collected {} jobs from {} pages with {} API calls",
+            jobs.len(),
+            pages_fetched,
+            api_calls
+        );
+        Ok(CollectResult {
+            total_results,
+            jobs_count: jobs.len(),
+            jobs,
+            pages_fetched,
+            api_calls,
+            cross_page_duplicates,
+            sequential_rounds,
+            search_duration_ms: start.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// Get the details of several jobs at once
+    ///
+    /// Fetches up to 25 jobs in input order, from the details cache where
+    /// possible. Jobs that fail do not fail the call; they are listed in
+    /// `failed`, and those that failed for transient reasons (rate limiting,
+    /// server errors, timeouts) also in `retry_reference_numbers`.
+    #[instrument(skip(self))]
+    pub async fn get_job_details_bulk(
+        &self,
+        params: BulkDetailsParams,
+    ) -> anyhow::Result<BulkDetailsResult> {
+        let _call = self.begin_tool("get_job_details_bulk")?;
+        const MAX_BULK_JOBS: usize = 25;
+
+        if params.reference_numbers.len() > MAX_BULK_JOBS {
+            anyhow::bail!(
+                "Too many reference numbers: {} (max: {})",
+                params.reference_numbers.len(),
+                MAX_BULK_JOBS
+            );
+        }
+
+        let mut jobs = Vec::new();
+        let mut failed = Vec::new();
+        for input in &params.reference_numbers {
+            let details = match sanitize_refnr(input) {
+                Ok(refnr) => {
+                    self.fetch_job_details(&refnr, params.api_key_override.as_ref())
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+            match details {
+                Ok((details, _)) => jobs.push(details),
+                Err(e) => {
+                    warn!("Could not fetch details of {}: {}", input, e);
+                    failed.push(BulkDetailsFailure {
+                        reference_number: input.clone(),
+                        retryable: is_retryable(&e),
+                        error: e.to_string(),
+                    });
+                }
+            }
+        }
+
+        let retry_reference_numbers = failed
+            .iter()
+            .filter(|f| f.retryable)
+            .map(|f| f.reference_number.clone())
+            .collect();
+        info!(
+            "Fetched {} of {} job details",
+            jobs.len(),
+            params.reference_numbers.len()
+        );
+        Ok(BulkDetailsResult {
+            jobs_count: jobs.len(),
+            jobs,
+            failed,
+            retry_reference_numbers,
+        })
+    }
+
+    /// Warm the details cache for jobs that are likely to be looked at next
+    ///
+    /// Fetches the details of up to 25 jobs concurrently, e.g. the top results
+    /// of a search, and only reports how many succeeded. Later get_job_details
+    /// calls for these jobs are served from the cache while it is fresh.
+    #[instrument(skip(self))]
+    pub async fn prefetch_details(&self, params: PrefetchParams) -> anyhow::Result<PrefetchResult> {
+        let _call = self.begin_tool("prefetch_details")?;
+        const MAX_PREFETCH_JOBS: usize = 25;
+
+        if params.reference_numbers.len() > MAX_PREFETCH_JOBS {
+            anyhow::bail!(
+                "Too many reference numbers: {} (max: {})",
+                params.reference_numbers.len(),
+                MAX_PREFETCH_JOBS
+            );
+        }
+
+        let mut failed = 0;
+        let mut tasks = tokio::task::JoinSet::new();
+        for input in &params.reference_numbers {
+            let refnr = match sanitize_refnr(input) {
+                Ok(refnr) => refnr,
+                Err(e) => {
+                    warn!("Not prefetching {}: {}", input, e);
+                    failed += 1;
+                    continue;
+                }
+            };
+            let server = self.clone();
+            tasks.spawn(
+                async move {
+                    let result = server.fetch_api_job_details(&refnr, None).await;
+                    (refnr, result)
+                }
+                .in_current_span(),
+            );
+        }
+
+        let mut prefetched = 0;
+        while let Some(joined) = tasks.join_next().await {
+            match joined? {
+                (_, Ok(_)) => prefetched += 1,
+                (refnr, Err(e)) => {
+                    warn!("Could not prefetch details of {}: {}", refnr, e);
+                    failed += 1;
+                }
+            }
+        }
+
+        info!("Prefetched {} job details, {} failed", prefetched, failed);
+        Ok(PrefetchResult { prefetched, failed })
+    }
+
+    /// Narrow down earlier search results by a keyword
+    ///
+    /// Fetches the details of the given jobs (from the details cache where
+    /// possible) and keeps those whose title or description contains the
+    /// keyword. Costs at most one request per reference number, without
+    /// re-running the search.
+    #[instrument(skip(self))]
+    pub async fn refine_search(&self, params: RefineParams) -> anyhow::Result<RefineResult> {
+        let _call = self.begin_tool("refine_search")?;
+        const MAX_REFINE_JOBS: usize = 25;
+
+        let keyword = params.keyword.trim();
+        if keyword.is_empty() {
+            anyhow::bail!("keyword must not be empty");
+        }
+        if params.previous_reference_numbers.len() > MAX_REFINE_JOBS {
+            anyhow::bail!(
+                "Too many reference numbers: {} (max: {})",
+                params.previous_reference_numbers.len(),
+                MAX_REFINE_JOBS
+            );
+        }
+
+        let mut jobs = Vec::new();
+        let mut failed_reference_numbers = Vec::new();
+        let mut checked_count = 0;
+        for input in &params.previous_reference_numbers {
+            let details = match sanitize_refnr(input) {
+                Ok(refnr) => self.fetch_job_details(&refnr, None).await,
+                Err(e) => Err(e),
+            };
+            let details = match details {
+                Ok((details, _)) => details,
+                Err(e) => {
+                    warn!("Could not check {} for refine_search: {}", input, e);
+                    failed_reference_numbers.push(input.clone());
+                    continue;
+                }
+            };
+
+            checked_count += 1;
+            let matches = [details.title.as_deref(), details.description.as_deref()]
+                .into_iter()
+                .flatten()
+                .any(|field| text::contains_folded(field, keyword));
+            if matches {
+                jobs.push(details);
+            }
+        }
+
+        info!(
+            "Refined {} jobs to {} matching '{}'",
+            checked_count,
+            jobs.len(),
+            params.keyword
+        );
+        Ok(RefineResult {
+            keyword: params.keyword,
+            checked_count,
+            jobs_count: jobs.len(),
+            jobs,
+            failed_reference_numbers,
+        })
+    }
+
+    /// Fetch the external application page of a job as plain text
+    ///
+    /// Only for jobs with an `external_url`. Follows http(s) URLs only and
+    /// returns at most the first few thousand characters of text, so agents can
+    /// summarize the employer's own posting. A page that cannot be fetched is
+    /// reported with `fetched: false` instead of an error.
+    #[instrument(skip(self))]
+    pub async fn fetch_application_page(
+        &self,
+        params: GetJobDetailsParams,
+    ) -> anyhow::Result<ApplicationPageResult> {
+        let _call = self.begin_tool("fetch_application_page")?;
+        const MAX_EXCERPT_CHARS: usize = 4000;
+
+        let refnr = sanitize_refnr(&params.reference_number)?;
+        let (details, _) = self
+            .fetch_api_job_details(&refnr, params.api_key_override.as_ref())
+            .await?;
+        let Some(url) = details.externe_url else {
+            anyhow::bail!("Job {} has no external application page", refnr);
+        };
+
+        let page = match reqwest::Url::parse(&url) {
+            Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {
+                self.client.fetch_external_text(parsed).await
+            }
+            _ => Err(anyhow::anyhow!("not an http(s) URL")),
+        };
+        let text_excerpt = match page {
+            Ok(html) => text::html_to_text(&html)
+                .chars()
+                .take(MAX_EXCERPT_CHARS)
+                .collect(),
+            Err(e) => {
+                warn!("Could not fetch application page {}: {}", url, e);
+                return Ok(ApplicationPageResult {
+                    url,
+                    text_excerpt: String::new(),
+                    fetched: false,
+                });
+            }
+        };
+
+        info!("Fetched application page of {}", refnr);
+        Ok(ApplicationPageResult {
+            url,
+            text_excerpt,
+            fetched: true,
+        })
+    }
+
+    /// Find jobs similar to a given job posting
+    ///
+    /// Searches with the seed job's title (near its location, if known) and keeps
+    /// candidates whose titles share at least `min_title_overlap` of the seed
+    /// title's words. The seed itself is excluded.
+    #[instrument(skip(self))]
+    pub async fn get_similar_jobs(
+        &self,
+        params: SimilarJobsParams,
+    ) -> anyhow::Result<SimilarJobsResult> {
+        let _call = self.begin_tool("get_similar_jobs")?;
+        let params = SimilarJobsParams {
+            reference_number: sanitize_refnr(&params.reference_number)?,
+            ..params
+        };
+        let min_overlap = params.min_title_overlap.unwrap_or(0.3);
+        if !(0.0..=1.0).contains(&min_overlap) {
+            anyhow::bail!("min_title_overlap must be between 0.0 and 1.0");
+        }
+
+        let (seed, _) = self
+            .fetch_job_details(&params.reference_number, None)
+            .await?;
+        let Some(seed_title) = seed.title.clone() else {
+            anyhow::bail!(
+                "Job {} has no title to compare against",
+                params.reference_number
+            );
+        };
+
+        let location = seed
+            .location
+            .as_deref()
+            .map(|loc| loc.split(" (").next().unwrap_or(loc).to_string());
+
+        let search = self
+            .run_search(SearchJobsParams {
+                job_title: Some(seed_title.clone()),
+                location,
+                page_size: params.page_size,
+                ..Default::default()
+            })
+            .await?;
+
+        let candidates: Vec<JobSummary> = search
+            .jobs
+            .into_iter()
+            .filter(|job| job.reference_number != params.reference_number)
+            .collect();
+        let candidates_count = candidates.len();
+
+        let jobs: Vec<JobSummary> = candidates
+            .into_iter()
+            .filter(|job| title_overlap(&seed_title, &job.title) >= min_overlap)
+            .collect();
+
+        Ok(SimilarJobsResult {
+            reference_number: params.reference_number,
+            seed_title: Some(seed_title),
+            jobs_count: jobs.len(),
+            filtered_out_count: candidates_count - jobs.len(),
+            jobs,
+        })
+    }
+
+    /// Estimate how fresh and competitive the market for a search is
+    ///
+    /// Samples up to two pages of results and summarizes the age of the
+    /// postings together with the total number of results. A heuristic only:
+    /// the API exposes no applicant numbers.
+    #[instrument(skip(self))]
+    pub async fn get_market_insight(
+        &self,
+        params: SearchJobsParams,
+    ) -> anyhow::Result<MarketInsight> {
+        let _call = self.begin_tool("get_market_insight")?;
+        const SAMPLE_PAGES: u64 = 2;
+
+        let page_size = self.search_page_size(params.page_size);
+        let mut total_results = None;
+        let mut ages = Vec::new();
+        let mut regions = Vec::new();
+
+        for page in 1..=SAMPLE_PAGES {
+            let result = self
+                .run_search(SearchJobsParams {
+                    page: Some(page),
+                    ..params.clone()
+                })
+                .await?;
+
+            total_results = total_results.or(result.total_results);
+            ages.extend(
+                result
+                    .jobs
+                    .iter()
+                    .filter_map(|job| job.days_since_published),
+            );
+            regions.extend(result.jobs.into_iter().map(|job| job.region));
+
+            if (result.jobs_count + result.filtered_out_count) < page_size as usize {
+                break;
+            }
+        }
+
+        let by_bundesland = regions::tally_bundeslaender(regions.iter().map(|r| r.as_deref()));
+        Ok(market_insight(total_results, &ages, by_bundesland))
+    }
+
+    /// Get only the jobs that are new since a previous poll
+    ///
+    /// Runs the search and drops every job whose reference number the caller
+    /// already knows. The current reference numbers are returned so the caller
+    /// can poll again without the server keeping any state.
+    #[instrument(skip(self))]
+    pub async fn get_new_jobs_since(
+        &self,
+        params: NewJobsSinceParams,
+    ) -> anyhow::Result<NewJobsSinceResult> {
+        let _call = self.begin_tool("get_new_jobs_since")?;
+        let result = self.run_search(params.search).await?;
+
+        let known: std::collections::HashSet<&str> = params
+            .known_reference_numbers
+            .iter()
+            .map(String::as_str)
+            .collect();
+        let reference_numbers: Vec<String> = result
+            .jobs
+            .iter()
+            .map(|job| job.reference_number.clone())
+            .collect();
+        let new_jobs: Vec<JobSummary> = result
+            .jobs
+            .into_iter()
+            .filter(|job| !known.contains(job.reference_number.as_str()))
+            .collect();
+
+        Ok(NewJobsSinceResult {
+            new_jobs_count: new_jobs.len(),
+            new_jobs,
+            reference_numbers,
+            result_hash: result.result_hash,
+        })
+    }
+
+    /// Find out where an employer is hiring
+    ///
+    /// Searches for the employer's openings (up to 3 pages) and counts them per
+    /// city and postal code. Listings from other employers that merely mention
+    /// the name are ignored.
+    #[instrument(skip(self))]
+    pub async fn get_employer_locations(
+        &self,
+        params: EmployerLocationsParams,
+    ) -> anyhow::Result<EmployerLocationsResult> {
+        let _call = self.begin_tool("get_employer_locations")?;
+        const MAX_PAGES: u64 = 3;
+
+        let employer = params.employer.trim();
+        if employer.is_empty() {
+            anyhow::bail!("Employer must not be empty");
+        }
+
+        let page_size = self.config.max_page_size;
+        let mut listings = Vec::new();
+
+        for page in 1..=MAX_PAGES {
+            let search_params = SearchParams {
+                was: Some(employer.to_string()),
+                size: Some(page_size),
+                page: Some(page),
+                ..Default::default()
+            };
+            let response = self.client.search(&search_params, None).await?;
+
+            let page_len = response.stellenangebote.len() as u64;
+            listings.extend(
+                response
+                    .stellenangebote
+                    .into_iter()
+                    .filter(|job| text::contains_folded(&job.arbeitgeber, employer)),
+            );
+
+            if page_len < page_size {
+                break;
+            }
+        }
+
+        Ok(EmployerLocationsResult {
+            employer: employer.to_string(),
+            locations: Self::aggregate_locations(&listings),
+        })
+    }
+
+    /// Compare how many jobs a search finds in several places
+    ///
+    /// Runs one count-only search per location (max 10) concurrently and
+    /// returns the counts, most jobs first. Locations whose search failed are
+    /// listed last with no count. Useful for relocation questions like "where
+    /// are the most nursing jobs?".
+    #[instrument(skip(self))]
+    pub async fn compare_locations(
+        &self,
+        params: CompareLocationsParams,
+    ) -> anyhow::Result<CompareLocationsResult> {
+        let _call = self.begin_tool("compare_locations")?;
+        const MAX_LOCATIONS: usize = 10;
+
+        let job_title = params.job_title.trim();
+        if job_title.is_empty() {
+            anyhow::bail!("job_title must not be empty");
+        }
+        if params.locations.len() > MAX_LOCATIONS {
+            anyhow::bail!(
+                "Too many locations: {} (max: {})",
+                params.locations.len(),
+                MAX_LOCATIONS
+            );
+        }
+        let radius = params
+            .radius_km
+            .or(self.config.default_radius_km)
+            .map(|radius| self.checked_param("radius_km", radius, 0, MAX_RADIUS_KM))
+            .transpose()?;
+
+        let mut tasks = tokio::task::JoinSet::new();
+        for (idx, location) in params.locations.iter().enumerate() {
+            let server = self.clone();
+            let search_params = SearchParams {
+                was: Some(job_title.to_string()),
+                wo: Some(location.trim().to_string()),
+                umkreis: radius,
+                size: Some(1),
+                ..Default::default()
+            };
+            tasks.spawn(async move { (idx, server.client.search(&search_params, None).await) });
+        }
+
+        let mut counts: Vec<(String, Option<u64>)> = params
+            .locations
+            .iter()
+            .map(|loc| (loc.clone(), None))
+            .collect();
+        while let Some(joined) = tasks.join_next().await {
+            let (idx, result) = joined?;
+            match result {
+                Ok(response) => counts[idx].1 = Some(response.max_ergebnisse.unwrap_or(0)),
+                Err(e) => warn!("Count for '{}' failed: {}", counts[idx].0, e),
+            }
+        }
+
+        // Stable, so equal counts keep the input order; failed locations go last
+        counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        Ok(CompareLocationsResult { counts })
+    }
+
+    /// Explain the structure of a job reference number (refnr)
+    ///
+    /// Refnrs usually look like `10000-1234567890-S`: a numeric prefix of the
+    /// publishing agency or partner, the posting number and the suffix `S`
+    /// (Stellenangebot). Checks the input against that shape, builds the
+    /// job's page on arbeitsagentur.de and notes anything unusual. Makes no
+    /// API request, so it cannot tell whether the job still exists.
+    #[instrument(skip(self))]
+    pub async fn explain_reference_number(
+        &self,
+        params: GetJobDetailsParams,
+    ) -> anyhow::Result<RefnrExplanation> {
+        let _timer = self.metrics.start("explain_reference_number");
+        Ok(explain_refnr(&params.reference_number))
+    }
+
+    /// Save a job posting to the favorites list
+    ///
+    /// Fetches the job details and stores a snapshot (title, employer, location,
+    /// application URL). Saving an already-saved job refreshes its snapshot.
+    #[instrument(skip(self))]
+    pub async fn save_favorite(
+        &self,
+        params: GetJobDetailsParams,
+    ) -> anyhow::Result<SaveFavoriteResult> {
+        let _call = self.begin_tool("save_favorite")?;
+        let params = GetJobDetailsParams {
+            reference_number: sanitize_refnr(&params.reference_number)?,
+            ..params
+        };
+        let (details, _) = self
+            .fetch_job_details(&params.reference_number, params.api_key_override.as_ref())
+            .await?;
+
+        let saved_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        let updated = self.favorites.save(FavoriteJob {
+            reference_number: details.reference_number,
+            title: details.title,
+            employer: details.employer,
+            location: details.location,
+            application_url: details.application_url,
+            saved_at,
+        })?;
+
+        Ok(SaveFavoriteResult {
+            reference_number: params.reference_number,
+            updated,
+            favorites_count: self.favorites.list().len(),
+        })
+    }
+
+    /// Remove a job posting from the favorites list
+    #[instrument(skip(self))]
+    pub async fn remove_favorite(
+        &self,
+        params: GetJobDetailsParams,
+    ) -> anyhow::Result<RemoveFavoriteResult> {
+        let _timer = self.metrics.start("remove_favorite");
+        let params = GetJobDetailsParams {
+            reference_number: sanitize_refnr(&params.reference_number)?,
+            ..params
+        };
+        let removed = self.favorites.remove(&params.reference_number)?;
+
+        Ok(RemoveFavoriteResult {
+            reference_number: params.reference_number,
+            removed,
+            favorites_count: self.favorites.list().len(),
+        })
+    }
+
+    /// List all saved favorite job postings
+    #[instrument(skip(self))]
+    pub async fn list_favorites(&self) -> anyhow::Result<ListFavoritesResult> {
+        let _timer = self.metrics.start("list_favorites");
+        let favorites = self.favorites.list();
+
+        Ok(ListFavoritesResult {
+            favorites_count: favorites.len(),
+            favorites,
+        })
+    }
+
+    /// List the jobs most recently looked up with get_job_details
+    ///
+    /// Returns up to `recently_viewed_capacity` reference numbers, newest first,
+    /// with titles taken from the details cache. Kept in memory only.
+    #[instrument(skip(self))]
+    pub async fn get_recently_viewed(&self) -> anyhow::Result<RecentlyViewedResult> {
+        let _timer = self.metrics.start("get_recently_viewed");
+        let refnrs: Vec<String> = self
+            .recently_viewed
+            .lock()
+            .unwrap()
+            .iter()
+            .cloned()
+            .collect();
+
+        let jobs: Vec<RecentlyViewedJob> = refnrs
+            .into_iter()
+            .map(|refnr| RecentlyViewedJob {
+                title: self.client.cached_job_details(&refnr).and_then(|d| d.titel),
+                reference_number: refnr,
+            })
+            .collect();
+
+        Ok(RecentlyViewedResult {
+            jobs_count: jobs.len(),
+            jobs,
+        })
+    }
+
+    /// List all accepted values for the search filters
+    ///
+    /// Use this before constructing a search to discover valid `employment_type`
+    /// and `contract_type` values. No API call is made.
+    #[instrument(skip(self))]
+    pub async fn list_filters(&self) -> anyhow::Result<FilterCatalog> {
+        let _timer = self.metrics.start("list_filters");
+        Ok(Self::filter_catalog())
+    }
+
+    /// Get the JSON Schema of every tool's output, keyed by tool name
+    ///
+    /// Lets typed clients validate and parse responses. Keys are shown in
+    /// snake_case; with the camelCase output setting they are renamed
+    /// accordingly. No API call is made.
+    #[instrument(skip(self))]
+    pub async fn get_output_schemas(
+        &self,
+    ) -> anyhow::Result<std::collections::BTreeMap<String, serde_json::Value>> {
+        let _timer = self.metrics.start("get_output_schemas");
+        Ok(Self::output_schemas())
+    }
+
+    /// Get server status and connection information
+    #[instrument(skip(self))]
+    pub async fn get_server_status(&self) -> anyhow::Result<JobsucheServerStatus> {
+        let _timer = self.metrics.start("get_server_status");
+        info!("Getting server status");
+
+        // Test API connectivity
+        let search_params = SearchParams {
+            was: None,
+            wo: Some("Berlin".to_string()),
+            umkreis: None,
+            size: Some(self.config.status_probe_page_size),
+            page: None,
+            veroeffentlichtseit: None,
+            arbeitszeit: None,
+            befristung: None,
+        };
+
+        // Uncached, so the status reflects the API now rather than a cached probe
+        let connection_status = match self.client.search_uncached(&search_params, None).await {
+            Ok(_) => "Connected".to_string(),
+            Err(e) => format!("Connection Error: {}", e),
+        };
+
+        Ok(JobsucheServerStatus {
+            server_name: "Jobsuche MCP Server".to_string(),
+            version: "0.3.1".to_string(),
+            uptime_seconds: self.get_uptime_seconds(),
+            api_url: self.config.api_url.clone(),
+            api_connection_status: connection_status,
+            tools_count: 24,
+            tool_latencies: self.metrics.snapshot(),
+            using_default_api_key: self.client.api_key == DEFAULT_API_KEY,
+            retries_succeeded: self
+                .client
+                .retries_succeeded
+                .load(std::sync::atomic::Ordering::Relaxed),
+            retries_exhausted: self
+                .client
+                .retries_exhausted
+                .load(std::sync::atomic::Ordering::Relaxed),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_support::{
+        capture_logs, details_body, listing, search_body, test_config, MockApi, MockResponse,
+    };
+
+    fn batch_item(name: &str, job_title: &str) -> BatchSearchItem {
+        BatchSearchItem {
+            name: name.to_string(),
+            job_title: Some(job_title.to_string()),
+            location: None,
+            radius_km: None,
+            employment_type: None,
+            contract_type: None,
+            published_since_days: None,
+            employer: None,
+            branch: None,
+        }
+    }
+
+    fn search_params(job_title: &str) -> SearchJobsParams {
+        SearchJobsParams {
+            job_title: Some(job_title.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_filter_catalog_contains_documented_values() {
+        let catalog = JobsucheMcpServer::filter_catalog();
+
+        let fulltime = catalog
+            .employment_types
+            .iter()
+            .find(|f| f.value == "fulltime")
+            .expect("fulltime missing");
+        assert!(!fulltime.description.is_empty());
+
+        let permanent = catalog
+            .contract_types
+            .iter()
+            .find(|f| f.value == "permanent")
+            .expect("permanent missing");
+        assert!(!permanent.description.is_empty());
+
+        // Every advertised value must be understood by the search
+        for filter in &catalog.employment_types {
+            assert!(JobsucheMcpServer::parse_employment_type(&filter.value).is_some());
+        }
+        for filter in &catalog.contract_types {
+            assert!(JobsucheMcpServer::parse_contract_type(&filter.value).is_some());
+        }
+    }
+
+    #[test]
+    fn test_entry_period_iso_range() {
+        let entry = |range: serde_json::Value| {
+            let details: ApiJobDetails =
+                serde_json::from_value(serde_json::json!({ "eintrittszeitraum": range })).unwrap();
+            let result = JobsucheMcpServer::convert_job_details("123", details, None);
+            (
+                result.entry_period,
+                result.entry_period_start,
+                result.entry_period_end,
+            )
+        };
+
+        let (period, start, end) = entry(serde_json::json!({ "von": "2025-03-01" }));
+        assert_eq!(period.as_deref(), Some("ab 2025-03-01"));
+        assert_eq!(start.as_deref(), Some("2025-03-01"));
+        assert_eq!(end, None);
+
+        let (period, start, end) = entry(serde_json::json!({ "bis": "30.06.2025" }));
+        assert_eq!(period.as_deref(), Some("bis 30.06.2025"));
+        assert_eq!(start, None);
+        assert_eq!(end.as_deref(), Some("2025-06-30"));
+
+        let (period, start, end) =
+            entry(serde_json::json!({ "von": "2025-03-01T00:00:00", "bis": "2025-04-15" }));
+        assert_eq!(period.as_deref(), Some("2025-03-01T00:00:00 - 2025-04-15"));
+        assert_eq!(start.as_deref(), Some("2025-03-01"));
+        assert_eq!(end.as_deref(), Some("2025-04-15"));
+    }
+
+    #[test]
+    fn test_contact_details_are_exposed_when_present() {
+        let details: ApiJobDetails = serde_json::from_value(serde_json::json!({
+            "titel": "Sachbearbeiter (m/w/d)",
+            "angebotskontakt": {
+                "vorname": "Erika",
+                "nachname": "Mustermann",
+                "emailAdresse": "bewerbung@acme.example",
+                "telefonnummer": "+49 30 123456",
+                "anrede": "Frau",
+            },
+        }))
+        .unwrap();
+        let result = JobsucheMcpServer::convert_job_details("123", details, None);
+        assert_eq!(
+            result.contact,
+            Some(ContactInfo {
+                name: Some("Erika Mustermann".to_string()),
+                email: Some("bewerbung@acme.example".to_string()),
+                phone: Some("+49 30 123456".to_string()),
+            })
+        );
+
+        let details: ApiJobDetails = serde_json::from_value(serde_json::json!({
+            "titel": "Sachbearbeiter (m/w/d)",
+            "kontakt": { "anrede": "Herr" },
+        }))
+        .unwrap();
+        let result = JobsucheMcpServer::convert_job_details("123", details, None);
+        assert_eq!(result.contact, None);
+    }
+
+    #[test]
+    fn test_weekly_hours_from_details() {
+        let hours = |payload: serde_json::Value| {
+            let details: ApiJobDetails = serde_json::from_value(payload).unwrap();
+            let result = JobsucheMcpServer::convert_job_details("123", details, None);
+            (result.weekly_hours, result.weekly_hours_range)
+        };
+
+        assert_eq!(
+            hours(serde_json::json!({ "arbeitszeitWochenstunden": 38.5 })),
+            (
+                Some(38.5),
+                Some(WeeklyHours {
+                    min: 38.5,
+                    max: 38.5
+                })
+            )
+        );
+        assert_eq!(
+            hours(serde_json::json!({ "arbeitszeitWochenstunden": "39,5" })),
+            (
+                Some(39.5),
+                Some(WeeklyHours {
+                    min: 39.5,
+                    max: 39.5
+                })
+            )
+        );
+        assert_eq!(
+            hours(serde_json::json!({ "arbeitszeitWochenstunden": "20 - 30 Std." })),
+            (
+                None,
+                Some(WeeklyHours {
+                    min: 20.0,
+                    max: 30.0
+                })
+            )
+        );
+        assert_eq!(
+            hours(serde_json::json!({ "arbeitszeitWochenstunden": "nach Absprache" })),
+            (None, None)
+        );
+        assert_eq!(
+            hours(serde_json::json!({ "titel": "Entwickler" })),
+            (None, None)
+        );
+    }
+
+    #[test]
+    fn test_home_office_flag_wins_over_description() {
+        let home_office = |payload: serde_json::Value| {
+            let details: ApiJobDetails = serde_json::from_value(payload).unwrap();
+            JobsucheMcpServer::convert_job_details("123", details, None).home_office
+        };
+        let on_site = "Die Tätigkeit findet ausschließlich vor Ort in Köln statt.";
+
+        assert_eq!(
+            home_office(
+                serde_json::json!({ "stellenbeschreibung": on_site, "arbeitszeitHeimarbeitTelearbeit": true })
+            ),
+            Some(true)
+        );
+        assert_eq!(
+            home_office(serde_json::json!({ "stellenbeschreibung": on_site })),
+            Some(false)
+        );
+        assert_eq!(
+            home_office(
+                serde_json::json!({ "stellenbeschreibung": "Zwei Tage Homeoffice pro Woche möglich." })
+            ),
+            Some(true)
+        );
+        assert_eq!(
+            home_office(serde_json::json!({ "stellenbeschreibung": "Wir bieten Obstkorb." })),
+            None
+        );
+        for refusal in [
+            "Es besteht keine Homeoffice-Möglichkeit.",
+            "Leider kein Home Office.",
+            "Eine Stelle ohne Remote-Anteil.",
+        ] {
+            assert_eq!(
+                home_office(serde_json::json!({ "stellenbeschreibung": refusal })),
+                Some(false),
+                "{}",
+                refusal
+            );
+        }
+    }
+
+    #[test]
+    fn test_skills_extracted_from_description() {
+        let details = ApiJobDetails {
+            stellenbeschreibung: Some(
+                "Sie haben sehr gute SQL-Kenntnisse, programmieren in Python und besitzen den F&uuml;hrerschein Klasse B."
+                    .to_string(),
+            ),
+            ..Default::default()
+        };
+        let result = JobsucheMcpServer::convert_job_details("123", details, None);
+        assert_eq!(
+            result.extracted_skills,
+            vec!["SQL", "Python", "Führerschein"]
+        );
+
+        let result = JobsucheMcpServer::convert_job_details("123", ApiJobDetails::default(), None);
+        assert!(result.extracted_skills.is_empty());
+        assert!(serde_json::to_value(&result)
+            .unwrap()
+            .get("extracted_skills")
+            .is_none());
+    }
+
+    #[test]
+    fn test_application_method_employer_direct() {
+        let details = ApiJobDetails {
+            arbeitgeber: Some("ACME GmbH".to_string()),
+            externe_url: Some("https://acme.example/jobs/1".to_string()),
+            allianzpartner_url: Some("https://partner.example/1".to_string()),
+            ..Default::default()
+        };
+        let result = JobsucheMcpServer::convert_job_details("123", details, None);
+        assert_eq!(result.application_method, "employer_direct");
+        assert_eq!(result.application_url, "https://acme.example/jobs/1");
+        assert_eq!(
+            result.application_instructions.as_deref(),
+            Some("Apply directly on the website of ACME GmbH: https://acme.example/jobs/1")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_application_url_priority_prefers_internal() {
+        let mut job = listing("1", "Entwickler", "ACME", "Berlin");
+        job["externeUrl"] = serde_json::json!("https://acme.example/jobs/1");
+        let body = search_body(vec![job]);
+        let mock = MockApi::start(move |req| {
+            if req.path.contains("/jobdetails/") {
+                MockResponse::json(
+                    serde_json::json!({ "titel": "Entwickler", "externeUrl": "https://acme.example/jobs/1" })
+                        .to_string(),
+                )
+            } else {
+                MockResponse::json(body.clone())
+            }
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(JobsucheConfig {
+            application_url_priority: Some(vec!["internal".to_string(), "external".to_string()]),
+            ..test_config(&mock.url)
+        })
+        .await
+        .unwrap();
+        let internal = "https://www.arbeitsagentur.de/jobsuche/jobdetail/1";
+
+        let search = server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .unwrap();
+        assert_eq!(search.jobs[0].application_url, internal);
+        assert_eq!(
+            search.jobs[0].external_url.as_deref(),
+            Some("https://acme.example/jobs/1")
+        );
+
+        let details = server
+            .get_job_details(GetJobDetailsParams {
+                reference_number: "1".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(details.application_url, internal);
+        assert_eq!(details.application_method, "arbeitsagentur");
+    }
+
+    #[test]
+    fn test_application_method_partner_board() {
+        let details = ApiJobDetails {
+            allianzpartner_url: Some("https://partner.example/1".to_string()),
+            ..Default::default()
+        };
+        let result = JobsucheMcpServer::convert_job_details("123", details, None);
+        assert_eq!(result.application_method, "partner_board");
+        assert_eq!(result.application_url, "https://partner.example/1");
+        assert_eq!(
+            result.application_instructions.as_deref(),
+            Some("Apply via the partner job board: https://partner.example/1")
+        );
+    }
+
+    #[test]
+    fn test_application_method_cipher() {
+        let details: ApiJobDetails = serde_json::from_value(serde_json::json!({
+            "titel": "Sachbearbeiter (m/w/d)",
+            "chiffrenummer": "CH-4711",
+            "externeUrl": "https://acme.example/jobs/1",
+        }))
+        .unwrap();
+
+        let result = JobsucheMcpServer::convert_job_details("123", details, None);
+        assert_eq!(result.application_method, "cipher");
+        assert_eq!(result.cipher_number.as_deref(), Some("CH-4711"));
+        let instructions = result.application_instructions.clone().unwrap();
+        assert!(instructions.starts_with("The employer is anonymous"));
+        assert!(instructions.contains("quote the cipher number CH-4711"));
+        assert!(result.application_note.unwrap().contains("CH-4711"));
+        assert_eq!(
+            result.application_url,
+            "https://www.arbeitsagentur.de/jobsuche/jobdetail/123"
+        );
+    }
+
+    #[test]
+    fn test_job_details_with_multiple_locations() {
+        let details: ApiJobDetails = serde_json::from_value(serde_json::json!({
+            "titel": "Filialleiter (m/w/d)",
+            "arbeitsorte": [
+                { "adresse": { "ort": "Berlin", "plz": "10115" } },
+                { "adresse": { "region": "Bayern", "land": "Deutschland" } },
+                { "adresse": {} },
+            ],
+        }))
+        .unwrap();
+
+        let result = JobsucheMcpServer::convert_job_details("123", details, None);
+        assert_eq!(result.location.as_deref(), Some("Berlin (10115)"));
+        assert_eq!(result.additional_locations, vec!["Bayern"]);
+    }
+
+    #[test]
+    fn test_format_location_falls_back_gracefully() {
+        assert_eq!(
+            format_location(Some("Köln"), None, Some("NRW"), None).as_deref(),
+            Some("Köln")
+        );
+        assert_eq!(
+            format_location(None, Some("80331"), Some("Bayern"), None).as_deref(),
+            Some("Bayern (80331)")
+        );
+        assert_eq!(
+            format_location(Some(" "), None, None, Some("Österreich")).as_deref(),
+            Some("Österreich")
+        );
+        assert_eq!(
+            format_location(None, Some("10115"), None, None).as_deref(),
+            Some("10115")
+        );
+        assert_eq!(format_location(None, None, None, None), None);
+    }
+
+    #[test]
+    fn test_application_method_arbeitsagentur() {
+        let result = JobsucheMcpServer::convert_job_details("123", ApiJobDetails::default(), None);
+        assert_eq!(result.application_method, "arbeitsagentur");
+        assert_eq!(
+            result.application_instructions.as_deref(),
+            Some("View and apply on arbeitsagentur.de: https://www.arbeitsagentur.de/jobsuche/jobdetail/123")
+        );
+        assert_eq!(result.cipher_number, None);
+        assert_eq!(result.application_note, None);
+        assert_eq!(
+            result.application_url,
+            "https://www.arbeitsagentur.de/jobsuche/jobdetail/123"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_external_application_only_filter() {
+        let mut direct = listing("1", "Entwickler", "ACME", "Berlin");
+        direct["externeUrl"] = serde_json::json!("https://acme.example/jobs/1");
+        let internal = listing("2", "Entwickler", "Beta", "Berlin");
+        let body = search_body(vec![direct, internal]);
+        let mock = MockApi::start(move |_| MockResponse::json(body.clone())).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .search_jobs(SearchJobsParams {
+                external_application_only: Some(true),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.jobs_count, 1);
+        assert_eq!(result.jobs[0].reference_number, "1");
+        assert_eq!(result.filtered_out_count, 1);
+    }
+
+    #[test]
+    fn test_normalize_search_key_ignores_order_and_case() {
+        let a = SearchParams {
+            was: Some("Software  Entwickler".to_string()),
+            wo: Some("Berlin".to_string()),
+            arbeitszeit: Some(vec!["vz".to_string(), "tz".to_string()]),
+            size: Some(25),
+            ..Default::default()
+        };
+        let b = SearchParams {
+            was: Some(" software entwickler ".to_string()),
+            wo: Some("BERLIN".to_string()),
+            arbeitszeit: Some(vec!["tz".to_string(), "vz".to_string(), "vz".to_string()]),
+            size: Some(25),
+            ..Default::default()
+        };
+        assert_eq!(normalize_search_key(&a), normalize_search_key(&b));
+    }
+
+    #[test]
+    fn test_normalize_search_key_distinguishes_searches() {
+        let base = SearchParams {
+            was: Some("Entwickler".to_string()),
+            wo: Some("Berlin".to_string()),
+            ..Default::default()
+        };
+        let other_location = SearchParams {
+            wo: Some("Hamburg".to_string()),
+            ..base.clone()
+        };
+        let other_page = SearchParams {
+            page: Some(2),
+            ..base.clone()
+        };
+        assert_ne!(
+            normalize_search_key(&base),
+            normalize_search_key(&other_location)
+        );
+        assert_ne!(
+            normalize_search_key(&base),
+            normalize_search_key(&other_page)
+        );
+    }
+
+    #[test]
+    fn test_normalize_search_key_escapes_separators() {
+        let split = SearchParams {
+            was: Some("koch".to_string()),
+            wo: Some("berlin".to_string()),
+            ..Default::default()
+        };
+        let smuggled = SearchParams {
+            was: Some("koch&wo=berlin".to_string()),
+            ..Default::default()
+        };
+        assert_ne!(
+            normalize_search_key(&split),
+            normalize_search_key(&smuggled)
+        );
+    }
+
+    #[test]
+    fn test_result_hash_is_order_independent() {
+        let a = result_hash(["1", "2", "3"]);
+        let b = result_hash(["3", "1", "2"]);
+        assert_eq!(a, b);
+        assert_eq!(a.len(), 64);
+    }
+
+    #[test]
+    fn test_result_hash_changes_with_set() {
+        let base = result_hash(["1", "2"]);
+        assert_ne!(base, result_hash(["1", "2", "3"]));
+        assert_ne!(base, result_hash(["1"]));
+        assert_ne!(result_hash(["12"]), result_hash(["1", "2"]));
+    }
+
+    #[test]
+    fn test_decode_body_handles_encodings() {
+        assert_eq!(
+            decode_body("Köln".as_bytes(), Some("application/json")),
+            "Köln"
+        );
+        assert_eq!(
+            decode_body(b"K\xf6ln", Some("application/json; charset=iso-8859-1")),
+            "Köln"
+        );
+        assert_eq!(
+            decode_body(b"K\xf6ln", Some("application/json; charset=utf-8")),
+            "Köln"
+        );
+        assert_eq!(decode_body(b"K\xf6ln", None), "Köln");
+    }
+
+    #[tokio::test]
+    async fn test_search_decodes_mislabeled_latin1_body() {
+        let json = search_body(vec![listing(
+            "1",
+            "Bäcker für Süßwaren",
+            "Müller GmbH",
+            "München",
+        )]);
+        let body = encoding_rs::WINDOWS_1252.encode(&json).0.into_owned();
+        let mock = MockApi::start(move |_| MockResponse {
+            body: body.clone(),
+            ..MockResponse::json("").with_header("Content-Type", "application/json; charset=utf-8")
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server.search_jobs(search_params("Bäcker")).await.unwrap();
+        assert_eq!(result.jobs[0].title, "Bäcker für Süßwaren");
+        assert_eq!(result.jobs[0].employer, "Müller GmbH");
+        assert_eq!(result.jobs[0].location, "München");
+    }
+
+    #[test]
+    fn test_insecure_tls_builds_and_warns() {
+        let config = JobsucheConfig {
+            danger_accept_invalid_certs: true,
+            ..Default::default()
+        };
+        let (client, logs) = capture_logs(|| JobsucheClient::new(&config, None));
+        assert!(client.is_ok());
+        assert!(logs.contains("WARN"));
+        assert!(logs.contains("TLS certificate verification is DISABLED"));
+
+        let (_, logs) = capture_logs(|| JobsucheClient::new(&JobsucheConfig::default(), None));
+        assert!(!logs.contains("TLS certificate verification is DISABLED"));
+    }
+
+    #[tokio::test]
+    async fn test_postal_codes_filter_matches_prefixes() {
+        let mut berlin = listing("1", "Entwickler", "ACME", "Berlin");
+        berlin["arbeitsort"]["plz"] = serde_json::json!("10115");
+        let mut hamburg = listing("2", "Entwickler", "Beta", "Hamburg");
+        hamburg["arbeitsort"]["plz"] = serde_json::json!("20095");
+        let unknown = listing("3", "Entwickler", "Gamma", "Irgendwo");
+        let body = search_body(vec![berlin, hamburg, unknown]);
+        let mock = MockApi::start(move |_| MockResponse::json(body.clone())).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .search_jobs(SearchJobsParams {
+                postal_codes: Some(vec!["10".to_string(), "80331".to_string()]),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.jobs_count, 1);
+        assert_eq!(result.jobs[0].reference_number, "1");
+        assert_eq!(result.filtered_out_count, 2);
+    }
+
+    fn api_listings(listings: Vec<serde_json::Value>) -> Vec<ApiJobListing> {
+        serde_json::from_str::<ApiSearchResponse>(&search_body(listings))
+            .unwrap()
+            .stellenangebote
+    }
+
+    #[test]
+    fn test_aggregate_locations_counts_per_city() {
+        let mut listings = Vec::new();
+        for (refnr, city, plz) in [
+            ("1", "Berlin", "10115"),
+            ("2", "Berlin", "10115"),
+            ("3", "München", "80331"),
+            ("4", "Berlin", "10115"),
+            ("5", "Hamburg", "20095"),
+            ("6", "München", "80331"),
+        ] {
+            let mut job = listing(refnr, "Ingenieur", "Siemens AG", city);
+            job["arbeitsort"]["plz"] = serde_json::json!(plz);
+            listings.push(job);
+        }
+
+        let locations = JobsucheMcpServer::aggregate_locations(&api_listings(listings));
+        let summary: Vec<(&str, u64)> = locations
+            .iter()
+            .map(|l| (l.city.as_str(), l.count))
+            .collect();
+        assert_eq!(summary, vec![("Berlin", 3), ("München", 2), ("Hamburg", 1)]);
+        assert_eq!(locations[0].postal_code.as_deref(), Some("10115"));
+    }
+
+    #[tokio::test]
+    async fn test_get_employer_locations_ignores_other_employers() {
+        let body = search_body(vec![
+            listing("1", "Ingenieur", "Siemens AG", "Berlin"),
+            listing("2", "Ingenieur", "Siemens Energy", "Erlangen"),
+            listing("3", "Siemens-Techniker", "Other GmbH", "Köln"),
+        ]);
+        let mock = MockApi::start(move |_| MockResponse::json(body.clone())).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .get_employer_locations(EmployerLocationsParams {
+                employer: "siemens".to_string(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.locations.len(), 2);
+        assert!(result.locations.iter().all(|l| l.city != "Köln"));
+        // A short page means there is nothing more to fetch
+        assert_eq!(mock.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_export_ndjson_paginates_one_object_per_line() {
+        let mock = MockApi::start(|req| {
+            if req.path.contains("page=1") {
+                MockResponse::json(search_body(vec![
+                    listing("1", "Entwickler", "ACME", "Berlin"),
+                    listing("2", "Entwickler", "Beta", "Hamburg"),
+                ]))
+            } else if req.path.contains("page=2") {
+                MockResponse::json(search_body(vec![
+                    listing("2", "Entwickler", "Beta", "Hamburg"),
+                    listing("3", "Entwickler", "Gamma", "Köln"),
+                ]))
+            } else {
+                MockResponse::json(search_body(vec![]))
+            }
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let output = server
+            .export_search_results_ndjson(SearchJobsParams {
+                page_size: Some(2),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
+
+        assert!(!output.ends_with('\n'));
+        let refnrs: Vec<String> = output
+            .lines()
+            .map(|line| {
+                let job: serde_json::Value = serde_json::from_str(line).unwrap();
+                job["reference_number"].as_str().unwrap().to_string()
+            })
+            .collect();
+        assert_eq!(refnrs, vec!["1", "2", "3"]);
+        assert_eq!(mock.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_export_ndjson_stops_on_short_page() {
+        let body = search_body(vec![listing("1", "Entwickler", "ACME", "Berlin")]);
+        let mock = MockApi::start(move |_| MockResponse::json(body.clone())).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let output = server
+            .export_search_results_ndjson(SearchJobsParams {
+                page_size: Some(5),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(output.lines().count(), 1);
+        assert_eq!(mock.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_title_overlap() {
+        assert_eq!(
+            title_overlap("Java Entwickler (m/w/d)", "Senior Java Entwickler"),
+            1.0
+        );
+        assert_eq!(title_overlap("Java Entwickler", "Java Berater"), 0.5);
+        assert_eq!(title_overlap("Java Entwickler", "Lagerhelfer"), 0.0);
+        assert_eq!(title_overlap("", "Lagerhelfer"), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_similar_jobs_threshold_drops_weak_matches() {
+        let mock = MockApi::start(|req| {
+            if req.path.contains("/jobdetails/") {
+                MockResponse::json(details_body("Java Entwickler"))
+            } else {
+                MockResponse::json(search_body(vec![
+                    listing("seed", "Java Entwickler", "ACME", "Berlin"),
+                    listing("1", "Java Entwickler Backend", "Beta", "Berlin"),
+                    listing("2", "Java Berater", "Gamma", "Berlin"),
+                ]))
+            }
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let strict = server
+            .get_similar_jobs(SimilarJobsParams {
+                reference_number: "seed".to_string(),
+                min_title_overlap: Some(0.9),
+                page_size: None,
+            })
+            .await
+            .unwrap();
+        let refnrs: Vec<&str> = strict
+            .jobs
+            .iter()
+            .map(|j| j.reference_number.as_str())
+            .collect();
+        assert_eq!(refnrs, vec!["1"]);
+        assert_eq!(strict.filtered_out_count, 1);
+
+        let lenient = server
+            .get_similar_jobs(SimilarJobsParams {
+                reference_number: "seed".to_string(),
+                min_title_overlap: Some(0.2),
+                page_size: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(lenient.jobs_count, 2);
+        assert_eq!(lenient.filtered_out_count, 0);
+    }
+
+    #[test]
+    fn test_pagination_styles() {
+        let params = SearchParams {
+            page: Some(3),
+            size: Some(20),
+            ..Default::default()
+        };
+
+        let client = JobsucheClient::new(&JobsucheConfig::default(), None).unwrap();
+        let url = client.build_search_url(&params);
+        assert!(url.ends_with("?size=20&page=3"));
+
+        let client = JobsucheClient::new(
+            &JobsucheConfig {
+                pagination_style: PaginationStyle::Offset,
+                ..Default::default()
+            },
+            None,
+        )
+        .unwrap();
+        let url = client.build_search_url(&params);
+        assert!(url.ends_with("?offset=40&limit=20"));
+    }
+
+    #[test]
+    fn test_unknown_arbeitszeit_codes_are_dropped() {
+        let params = SearchParams {
+            arbeitszeit: Some(vec![
+                "vz".to_string(),
+                "freelance".to_string(),
+                "ho".to_string(),
+            ]),
+            ..Default::default()
+        };
+
+        let client = JobsucheClient::new(&JobsucheConfig::default(), None).unwrap();
+        let (url, logs) = capture_logs(|| client.build_search_url(&params));
+        assert!(url.ends_with("?arbeitszeit=vz&arbeitszeit=ho"), "{}", url);
+        assert!(logs.contains("WARN"));
+        assert!(logs.contains("Dropping arbeitszeit code 'freelance'"));
+
+        let client = JobsucheClient::new(
+            &JobsucheConfig {
+                arbeitszeit_allowlist: Some(vec!["vz".to_string()]),
+                ..Default::default()
+            },
+            None,
+        )
+        .unwrap();
+        assert!(client
+            .build_search_url(&params)
+            .ends_with("?arbeitszeit=vz"));
+    }
+
+    #[tokio::test]
+    async fn test_first_page_translates_to_api_page_base() {
+        let params = SearchParams {
+            page: Some(1),
+            size: Some(20),
+            ..Default::default()
+        };
+
+        let client = JobsucheClient::new(&JobsucheConfig::default(), None).unwrap();
+        assert!(client
+            .build_search_url(&params)
+            .ends_with("?size=20&page=1"));
+
+        let client = JobsucheClient::new(
+            &JobsucheConfig {
+                api_page_base: 0,
+                ..Default::default()
+            },
+            None,
+        )
+        .unwrap();
+        assert!(client
+            .build_search_url(&params)
+            .ends_with("?size=20&page=0"));
+        let url = client.build_search_url(&SearchParams {
+            page: Some(3),
+            ..params
+        });
+        assert!(url.ends_with("?size=20&page=2"));
+
+        // The page the API echoes back is reported 1-based again
+        let mock = MockApi::start(|req| {
+            let page: u64 = req
+                .path
+                .split("page=")
+                .nth(1)
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(0);
+            let mut body: serde_json::Value = serde_json::from_str(&search_body(vec![])).unwrap();
+            body["page"] = serde_json::json!(page);
+            MockResponse::json(body.to_string())
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(JobsucheConfig {
+            api_page_base: 0,
+            ..test_config(&mock.url)
+        })
+        .await
+        .unwrap();
+        let result = server
+            .search_jobs(SearchJobsParams {
+                page: Some(1),
+                ..search_params("Koch")
+            })
+            .await
+            .unwrap();
+        assert!(
+            mock.requests()[0].path.ends_with("page=0"),
+            "{}",
+            mock.requests()[0].path
+        );
+        assert_eq!(result.current_page, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_contract_type_is_sent_as_befristung() {
+        let body = search_body(vec![listing("1", "Koch", "Gasthaus", "Berlin")]);
+        let mock = MockApi::start(move |_| MockResponse::json(body.clone())).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        server
+            .search_jobs(SearchJobsParams {
+                contract_type: Some(vec!["permanent".to_string(), "Temporary".to_string()]),
+                ..search_params("Koch")
+            })
+            .await
+            .unwrap();
+
+        assert!(
+            mock.requests()[0].path.contains("befristung=2;1"),
+            "{}",
+            mock.requests()[0].path
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_waits_for_in_flight_search() {
+        let body = search_body(vec![listing("1", "Entwickler", "ACME", "Berlin")]);
+        let mock = MockApi::start(move |_| {
+            MockResponse::json(body.clone()).with_delay(Duration::from_millis(300))
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(JobsucheConfig {
+            shutdown_grace_secs: 5,
+            ..test_config(&mock.url)
+        })
+        .await
+        .unwrap();
+
+        let in_flight = {
+            let server = server.clone();
+            tokio::spawn(async move { server.search_jobs(search_params("Entwickler")).await })
+        };
+        while server.requests.in_flight() == 0 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+
+        let started = Instant::now();
+        assert!(server.shutdown().await);
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(in_flight.is_finished());
+        assert_eq!(in_flight.await.unwrap().unwrap().jobs_count, 1);
+
+        let err = server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("shutting down"));
+    }
+
+    #[test]
+    fn test_with_trailing_wildcard() {
+        assert_eq!(with_trailing_wildcard("Entwickl"), "Entwickl*");
+        assert_eq!(
+            with_trailing_wildcard("Software Entwickl "),
+            "Software Entwickl*"
+        );
+        assert_eq!(with_trailing_wildcard("Entwickl*"), "Entwickl*");
+        assert_eq!(with_trailing_wildcard(""), "");
+    }
+
+    #[tokio::test]
+    async fn test_title_wildcard_appends_asterisk() {
+        let body = search_body(vec![]);
+        let mock = MockApi::start(move |_| MockResponse::json(body.clone())).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        server
+            .search_jobs(SearchJobsParams {
+                title_wildcard: Some(true),
+                ..search_params("Entwickl")
+            })
+            .await
+            .unwrap();
+        server.search_jobs(search_params("Entwickl")).await.unwrap();
+
+        let requests = mock.requests();
+        assert!(
+            requests[0].path.contains("was=Entwickl%2A"),
+            "{}",
+            requests[0].path
+        );
+        assert!(
+            requests[1].path.contains("was=Entwickl&"),
+            "{}",
+            requests[1].path
+        );
+    }
+
+    #[derive(Default)]
+    struct RecordingObserver {
+        events: Mutex<Vec<String>>,
+    }
+
+    impl JobsucheObserver for RecordingObserver {
+        fn on_request(&self, url: &str) {
+            self.events.lock().unwrap().push(format!("request {}", url));
+        }
+
+        fn on_response(&self, status: u16, _duration: Duration) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("response {}", status));
+        }
+
+        fn on_error(&self, err: &str) {
+            self.events.lock().unwrap().push(format!("error {}", err));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_observer_sees_request_and_response() {
+        let body = search_body(vec![listing("1", "Entwickler", "ACME", "Berlin")]);
+        let mock = MockApi::start(move |_| MockResponse::json(body.clone())).await;
+        let observer = Arc::new(RecordingObserver::default());
+        let server = JobsucheMcpServer::with_observer(test_config(&mock.url), observer.clone())
+            .await
+            .unwrap();
+
+        server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .unwrap();
+
+        let events = observer.events.lock().unwrap().clone();
+        assert_eq!(events.len(), 2, "{:?}", events);
+        assert!(events[0].starts_with(&format!("request {}/pc/v4/jobs?", mock.url)));
+        assert_eq!(events[1], "response 200");
+    }
+
+    #[tokio::test]
+    async fn test_observer_sees_connection_errors() {
+        let observer = Arc::new(RecordingObserver::default());
+        let config = JobsucheConfig {
+            retry_on_connection_errors: false,
+            ..test_config("http://127.0.0.1:1")
+        };
+        let server = JobsucheMcpServer::with_observer(config, observer.clone())
+            .await
+            .unwrap();
+
+        assert!(server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .is_err());
+
+        let events = observer.events.lock().unwrap().clone();
+        assert_eq!(events.len(), 2, "{:?}", events);
+        assert!(events[1].starts_with("error "));
+    }
+
+    #[tokio::test]
+    async fn test_dedupe_similar_collapses_agency_duplicates() {
+        let body = search_body(vec![
+            listing("1", "Softwareentwickler (m/w/d)", "ACME GmbH", "Berlin"),
+            listing(
+                "2",
+                "Softwareentwickler (w/m/d)",
+                "Personal Agentur",
+                "Berlin",
+            ),
+            listing("3", "Softwareentwickler (m/w/d)", "ACME GmbH", "Hamburg"),
+        ]);
+        let mock = MockApi::start(move |_| MockResponse::json(body.clone())).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .search_jobs(SearchJobsParams {
+                dedupe_similar: Some(true),
+                ..search_params("Softwareentwickler")
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.jobs_count, 2);
+        assert_eq!(result.clusters_collapsed, 1);
+        assert_eq!(result.jobs[0].reference_number, "1");
+        assert_eq!(result.jobs[0].duplicate_count, 1);
+        assert_eq!(result.jobs[1].duplicate_count, 0);
+
+        let all = server
+            .search_jobs(search_params("Softwareentwickler"))
+            .await
+            .unwrap();
+        assert_eq!(all.jobs_count, 3);
+        assert_eq!(all.clusters_collapsed, 0);
+    }
+
+    #[test]
+    fn test_same_employer_postings_are_not_collapsed() {
+        let job = |refnr: &str| JobSummary {
+            reference_number: refnr.to_string(),
+            title: "Pflegefachkraft".to_string(),
+            employer: "Klinikum".to_string(),
+            location: "Köln (50667)".to_string(),
+            published_date: None,
+            days_since_published: None,
+            external_url: None,
+            application_url: String::new(),
+            duplicate_count: 0,
+            matched_term: None,
+            title_raw: None,
+            title_is_fallback: false,
+            region: None,
+            relevance_score: None,
+            distance_from_origin_km: None,
+        };
+
+        let (jobs, clusters) = collapse_duplicates(vec![job("1"), job("2")]);
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(clusters, 0);
+    }
+
+    #[tokio::test]
+    async fn test_expand_synonyms_merges_results() {
+        let mock = MockApi::start(|request| {
+            let listings = if request.path.contains("was=Softwareentwickler&") {
+                vec![
+                    listing("1", "Softwareentwickler", "ACME", "Berlin"),
+                    listing("2", "Softwareentwickler", "Beta", "Berlin"),
+                ]
+            } else if request.path.contains("was=Software%20Engineer&") {
+                vec![
+                    listing("2", "Software Engineer", "Beta", "Berlin"),
+                    listing("3", "Software Engineer", "Gamma", "Berlin"),
+                ]
+            } else {
+                vec![]
+            };
+            MockResponse::json(search_body(listings))
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .search_jobs(SearchJobsParams {
+                expand_synonyms: Some(true),
+                ..search_params("Softwareentwickler")
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(mock.count_matching("was=Softwareentwickler&"), 1);
+        assert_eq!(mock.count_matching("was=Software%20Engineer&"), 1);
+        assert!(mock.requests().len() <= 1 + synonyms::MAX_SYNONYMS);
+
+        let refnrs: Vec<&str> = result
+            .jobs
+            .iter()
+            .map(|j| j.reference_number.as_str())
+            .collect();
+        assert_eq!(refnrs, vec!["1", "2", "3"]);
+        assert_eq!(result.jobs_count, 3);
+        assert_eq!(
+            result.jobs[1].matched_term.as_deref(),
+            Some("Softwareentwickler")
+        );
+        assert_eq!(
+            result.jobs[2].matched_term.as_deref(),
+            Some("Software Engineer")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_new_jobs_since_returns_unknown_jobs() {
+        let body = search_body(vec![
+            listing("1", "Entwickler", "ACME", "Berlin"),
+            listing("2", "Entwickler", "Beta", "Berlin"),
+            listing("3", "Entwickler", "Gamma", "Berlin"),
+        ]);
+        let mock = MockApi::start(move |_| MockResponse::json(body.clone())).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .get_new_jobs_since(NewJobsSinceParams {
+                search: search_params("Entwickler"),
+                known_reference_numbers: vec!["1".to_string(), "3".to_string(), "99".to_string()],
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.new_jobs_count, 1);
+        assert_eq!(result.new_jobs[0].reference_number, "2");
+        assert_eq!(result.reference_numbers, vec!["1", "2", "3"]);
+        assert_eq!(result.result_hash, result_hash(["1", "2", "3"]));
+    }
+
+    #[tokio::test]
+    async fn test_api_key_override_is_sent_per_request() {
+        let mock = MockApi::start(|req| {
+            if req.path.contains("/jobdetails/") {
+                MockResponse::json(details_body("Entwickler"))
+            } else {
+                MockResponse::json(search_body(vec![]))
+            }
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+        let tenant_key = || Some(ApiKey("tenant-key".to_string()));
+
+        server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .unwrap();
+        server
+            .search_jobs(SearchJobsParams {
+                api_key_override: tenant_key(),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
+        server
+            .get_job_details(GetJobDetailsParams {
+                reference_number: "123".to_string(),
+                api_key_override: tenant_key(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        // Neither served from nor written to the shared details cache
+        let details = |api_key_override| GetJobDetailsParams {
+            reference_number: "123".to_string(),
+            api_key_override,
+            ..Default::default()
+        };
+        server.get_job_details(details(None)).await.unwrap();
+        server.get_job_details(details(tenant_key())).await.unwrap();
+        server.get_job_details(details(None)).await.unwrap();
+
+        let keys: Vec<String> = mock
+            .requests()
+            .iter()
+            .map(|r| r.headers["x-api-key"].clone())
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                "jobboerse-jobsuche",
+                "tenant-key",
+                "tenant-key",
+                "jobboerse-jobsuche",
+                "tenant-key"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_detail_filters_use_the_api_key_override() {
+        let mock = MockApi::start(|req| {
+            if req.path.contains("/jobdetails/") {
+                let mut details: serde_json::Value =
+                    serde_json::from_str(&details_body("Entwickler")).unwrap();
+                details["ersteVeroeffentlichungsdatum"] = serde_json::json!("2024-03-01");
+                MockResponse::json(details.to_string())
+            } else {
+                let mut job = listing("123", "Entwickler", "ACME", "Berlin");
+                job["aktuelleVeroeffentlichungsdatum"] = serde_json::json!("2024-03-10");
+                MockResponse::json(search_body(vec![job]))
+            }
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .search_jobs(SearchJobsParams {
+                recently_updated_only: Some(true),
+                api_key_override: Some(ApiKey("tenant-key".to_string())),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.jobs_count, 1);
+        server
+            .search_jobs(SearchJobsParams {
+                prioritize_salary: Some(true),
+                api_key_override: Some(ApiKey("tenant-key".to_string())),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
+        server
+            .search_jobs(SearchJobsParams {
+                min_description_chars: Some(10),
+                api_key_override: Some(ApiKey("tenant-key".to_string())),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
+        // The tenant's details did not end up in the shared cache
+        server
+            .get_job_details(GetJobDetailsParams {
+                reference_number: "123".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let detail_keys: Vec<String> = mock
+            .requests()
+            .iter()
+            .filter(|r| r.path.contains("/jobdetails/"))
+            .map(|r| r.headers["x-api-key"].clone())
+            .collect();
+        assert_eq!(
+            detail_keys,
+            vec![
+                "tenant-key",
+                "tenant-key",
+                "tenant-key",
+                "jobboerse-jobsuche"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_api_key_is_redacted_in_debug_output() {
+        let params = SearchJobsParams {
+            api_key_override: Some(ApiKey("secret-key".to_string())),
+            ..search_params("Entwickler")
+        };
+        assert!(!format!("{:?}", params).contains("secret-key"));
+    }
+
+    #[tokio::test]
+    async fn test_configured_page_sizes_for_search_and_status_probe() {
+        let body = search_body(vec![]);
+        let mock = MockApi::start(move |_| MockResponse::json(body.clone())).await;
+        let server = JobsucheMcpServer::with_config(JobsucheConfig {
+            default_search_page_size: Some(40),
+            status_probe_page_size: 3,
+            ..test_config(&mock.url)
+        })
+        .await
+        .unwrap();
+
+        server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .unwrap();
+        server.get_server_status().await.unwrap();
+
+        let requests = mock.requests();
+        assert!(requests[0].path.contains("size=40"), "{}", requests[0].path);
+        assert!(requests[1].path.contains("size=3"), "{}", requests[1].path);
+    }
+
+    #[tokio::test]
+    async fn test_recently_viewed_is_newest_first() {
+        let mock = MockApi::start(|req| {
+            let refnr = req.path.rsplit('/').next().unwrap_or_default().to_string();
+            MockResponse::json(details_body(&format!("Job {}", refnr)))
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(JobsucheConfig {
+            recently_viewed_capacity: 3,
+            ..test_config(&mock.url)
+        })
+        .await
+        .unwrap();
+
+        for refnr in ["A", "B", "C", "A", "D"] {
+            server
+                .get_job_details(GetJobDetailsParams {
+                    reference_number: refnr.to_string(),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+        }
+
+        let viewed = server.get_recently_viewed().await.unwrap();
+        let refnrs: Vec<&str> = viewed
+            .jobs
+            .iter()
+            .map(|j| j.reference_number.as_str())
+            .collect();
+        assert_eq!(refnrs, vec!["D", "A", "C"]);
+        assert_eq!(viewed.jobs[0].title.as_deref(), Some("Job D"));
+    }
+
+    #[test]
+    fn test_radius_without_location_is_dropped() {
+        let params = SearchJobsParams {
+            radius_km: Some(50),
+            ..search_params("Entwickler")
+        };
+        let (radius, logs) = capture_logs(|| JobsucheMcpServer::search_radius(&params, None));
+        assert_eq!(radius, None);
+        assert!(logs.contains("Ignoring radius_km=50"));
+
+        let client = JobsucheClient::new(&JobsucheConfig::default(), None).unwrap();
+        let url = client.build_search_url(&SearchParams {
+            was: Some("Entwickler".to_string()),
+            umkreis: radius,
+            ..Default::default()
+        });
+        assert!(!url.contains("umkreis="));
+
+        let params = SearchJobsParams {
+            location: Some("Berlin".to_string()),
+            ..params
+        };
+        let (radius, logs) = capture_logs(|| JobsucheMcpServer::search_radius(&params, None));
+        assert_eq!(radius, Some(50));
+        assert!(!logs.contains("Ignoring"));
+    }
+
+    #[tokio::test]
+    async fn test_default_radius_applies_to_located_searches() {
+        let mock = MockApi::start(|_| MockResponse::json(search_body(vec![]))).await;
+        let server = JobsucheMcpServer::with_config(JobsucheConfig {
+            default_radius_km: Some(25),
+            ..test_config(&mock.url)
+        })
+        .await
+        .unwrap();
+
+        let result = server
+            .search_jobs(SearchJobsParams {
+                location: Some("Berlin".to_string()),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
+        assert!(mock.requests()[0].path.contains("umkreis=25"));
+        assert_eq!(
+            result.applied_filters,
+            AppliedFilters {
+                radius_km: Some(25),
+                default_radius_applied: true,
+                ..Default::default()
+            }
+        );
+
+        let result = server
+            .search_jobs(SearchJobsParams {
+                location: Some("Berlin".to_string()),
+                radius_km: Some(10),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
+        assert!(mock.requests()[1].path.contains("umkreis=10"));
+        assert!(!result.applied_filters.default_radius_applied);
+
+        server
+            .search_jobs(search_params("Developer"))
+            .await
+            .unwrap();
+        assert!(!mock.requests()[2].path.contains("umkreis="));
+    }
+
+    #[tokio::test]
+    async fn test_default_employment_type_applies_when_unset() {
+        let mock = MockApi::start(|_| MockResponse::json(search_body(vec![]))).await;
+        let server = JobsucheMcpServer::with_config(JobsucheConfig {
+            default_employment_type: Some(vec!["Minijob".to_string()]),
+            ..test_config(&mock.url)
+        })
+        .await
+        .unwrap();
+
+        let result = server.search_jobs(search_params("Aushilfe")).await.unwrap();
+        assert!(mock.requests()[0].path.contains("arbeitszeit=minijob"));
+        assert_eq!(
+            result.applied_filters.employment_type,
+            Some(vec!["minijob".to_string()])
+        );
+        assert!(result.applied_filters.default_employment_type_applied);
+
+        let result = server
+            .search_jobs(SearchJobsParams {
+                employment_type: Some(vec!["parttime".to_string()]),
+                ..search_params("Aushilfe")
+            })
+            .await
+            .unwrap();
+        assert!(mock.requests()[1].path.contains("arbeitszeit=tz"));
+        assert!(!mock.requests()[1].path.contains("minijob"));
+        assert!(!result.applied_filters.default_employment_type_applied);
+    }
+
+    #[tokio::test]
+    async fn test_published_after_filters_older_listings() {
+        let mut old = listing("1", "Entwickler", "ACME", "Berlin");
+        old["aktuelleVeroeffentlichungsdatum"] = serde_json::json!("2024-02-28");
+        let mut cutoff_day = listing("2", "Entwickler", "Beta", "Berlin");
+        cutoff_day["aktuelleVeroeffentlichungsdatum"] = serde_json::json!("2024-03-01");
+        let undated = listing("3", "Entwickler", "Gamma", "Berlin");
+        let body = search_body(vec![old, cutoff_day, undated]);
+        let mock = MockApi::start(move |_| MockResponse::json(body.clone())).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .search_jobs(SearchJobsParams {
+                published_after: Some("2024-03-01".to_string()),
+                published_since_days: Some(100),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
+
+        let refnrs: Vec<&str> = result
+            .jobs
+            .iter()
+            .map(|j| j.reference_number.as_str())
+            .collect();
+        assert_eq!(refnrs, vec!["2", "3"]);
+        assert_eq!(result.filtered_out_count, 1);
+        assert!(mock.requests()[0].path.contains("veroeffentlichtseit=100"));
+
+        let err = server
+            .search_jobs(SearchJobsParams {
+                published_after: Some("01.03.2024".to_string()),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid published_after"));
+    }
+
+    #[tokio::test]
+    async fn test_search_retries_once_after_parse_error() {
+        let attempts = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = attempts.clone();
+        let body = search_body(vec![listing("1", "Entwickler", "ACME", "Berlin")]);
+        let mock = MockApi::start(move |_| {
+            if counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 0 {
+                MockResponse::json("{\"stellenangebote\": [")
+            } else {
+                MockResponse::json(body.clone())
+            }
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .unwrap();
+        assert_eq!(result.jobs_count, 1);
+
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(!requests[0].path.contains("_ts="));
+        assert!(requests[1].path.contains("&_ts="));
+    }
+
+    #[tokio::test]
+    async fn test_search_parse_error_without_retry() {
+        let mock = MockApi::start(|_| MockResponse::json("not json")).await;
+        let server = JobsucheMcpServer::with_config(JobsucheConfig {
+            retry_on_parse_error: false,
+            ..test_config(&mock.url)
+        })
+        .await
+        .unwrap();
+
+        let err = server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Failed to parse API response"));
+        assert_eq!(mock.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_include_raw_only_when_requested() {
+        let mock = MockApi::start(|req| {
+            if req.path.contains("/jobdetails/") {
+                MockResponse::json(r#"{"titel": "Entwickler", "neuesFeld": 42}"#)
+            } else {
+                MockResponse::json(r#"{"stellenangebote": [], "facetten": {"ort": []}}"#)
+            }
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let plain = server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .unwrap();
+        assert!(plain.raw.is_none());
+        let with_raw = server
+            .search_jobs(SearchJobsParams {
+                include_raw: Some(true),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
+        assert!(with_raw.raw.as_ref().unwrap()["facetten"].is_object());
+
+        let details_params = GetJobDetailsParams {
+            reference_number: "123".to_string(),
+            ..Default::default()
+        };
+        let plain = server
+            .get_job_details(details_params.clone())
+            .await
+            .unwrap();
+        assert!(plain.raw.is_none());
+        // Served from the details cache, which keeps the raw body
+        let with_raw = server
+            .get_job_details(GetJobDetailsParams {
+                include_raw: Some(true),
+                ..details_params
+            })
+            .await
+            .unwrap();
+        assert_eq!(with_raw.raw.as_ref().unwrap()["neuesFeld"], 42);
+        assert_eq!(mock.count_matching("/jobdetails/"), 1);
+    }
+
+    #[test]
+    fn test_sanitize_refnr() {
+        assert_eq!(
+            sanitize_refnr("  10000-1234567890-S\n").unwrap(),
+            "10000-1234567890-S"
+        );
+        assert_eq!(
+            sanitize_refnr("10000-1234567890-S%20").unwrap(),
+            "10000-1234567890-S"
+        );
+        assert_eq!(sanitize_refnr("12265-a%2Fb").unwrap(), "12265-a/b");
+
+        assert!(sanitize_refnr("   ")
+            .unwrap_err()
+            .to_string()
+            .contains("must not be empty"));
+        assert!(sanitize_refnr("10000 1234567890")
+            .unwrap_err()
+            .to_string()
+            .contains("spaces"));
+        assert!(sanitize_refnr("10000%001234").is_err());
+    }
+
+    #[test]
+    fn test_explain_normal_refnr() {
+        let explanation = explain_refnr("10000-1234567890-S");
+        assert!(explanation.looks_valid);
+        assert_eq!(explanation.reference_number, "10000-1234567890-S");
+        assert_eq!(
+            explanation.detail_url,
+            "https://www.arbeitsagentur.de/jobsuche/jobdetail/10000-1234567890-S"
+        );
+        assert!(explanation.notes.is_empty(), "{:?}", explanation.notes);
+
+        let explanation = explain_refnr(" 10000-1234567890-S%20");
+        assert!(explanation.looks_valid);
+        assert_eq!(explanation.reference_number, "10000-1234567890-S");
+        assert_eq!(explanation.notes.len(), 2, "{:?}", explanation.notes);
+        assert!(explanation.notes[1].contains("URL-encoded"));
+    }
+
+    #[test]
+    fn test_explain_malformed_refnr() {
+        let explanation = explain_refnr("10000 1234567890");
+        assert!(!explanation.looks_valid);
+        assert!(explanation.notes[0].contains("spaces"));
+
+        let explanation = explain_refnr("ABC-123");
+        assert!(!explanation.looks_valid);
+        assert!(explanation
+            .notes
+            .iter()
+            .any(|note| note.contains("numeric prefix")));
+        assert!(explanation
+            .notes
+            .iter()
+            .any(|note| note.contains("2 part(s)")));
+
+        let explanation = explain_refnr("12265-a/b-S");
+        assert!(explanation.looks_valid);
+        assert_eq!(
+            explanation.detail_url,
+            "https://www.arbeitsagentur.de/jobsuche/jobdetail/12265-a%2Fb-S"
+        );
+        assert!(explanation.notes[0].contains("not the usual 10 digits"));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_refnr_is_rejected_before_api_call() {
+        let mock = MockApi::start(|_| MockResponse::json(details_body("Entwickler"))).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let err = server
+            .get_job_details(GetJobDetailsParams {
+                reference_number: "not a refnr".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid reference number"));
+        assert!(mock.requests().is_empty());
+
+        let result = server
+            .get_job_details(GetJobDetailsParams {
+                reference_number: " 10000-1234567890-S ".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.reference_number, "10000-1234567890-S");
+        assert!(mock.requests()[0]
+            .path
+            .ends_with("/jobdetails/10000-1234567890-S"));
+    }
+
+    #[tokio::test]
+    async fn test_identical_searches_hit_network_once() {
+        let body = search_body(vec![listing("1", "Entwickler", "ACME", "Berlin")]);
+        let mock = MockApi::start(move |_| MockResponse::json(body.clone())).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let first = server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .unwrap();
+        let second = server
+            .search_jobs(search_params("  entwickler "))
+            .await
+            .unwrap();
+        assert_eq!(first.result_hash, second.result_hash);
+        assert_eq!(mock.requests().len(), 1);
+
+        server.search_jobs(search_params("Tester")).await.unwrap();
+        assert_eq!(mock.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_search_cache_can_be_disabled() {
+        let body = search_body(vec![]);
+        let mock = MockApi::start(move |_| MockResponse::json(body.clone())).await;
+        let server = JobsucheMcpServer::with_config(JobsucheConfig {
+            search_cache_capacity: 0,
+            ..test_config(&mock.url)
+        })
+        .await
+        .unwrap();
+
+        server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .unwrap();
+        server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .unwrap();
+        assert_eq!(mock.requests().len(), 2);
+    }
+
+    #[test]
+    fn test_market_insight_median_and_fresh_count() {
+        let insight = market_insight(Some(250), &[40, 2, 10, 7, 1], Vec::new());
+        assert_eq!(insight.median_days_since_published, Some(7.0));
+        assert_eq!(insight.newest_count_last_7d, 3);
+        assert_eq!(insight.sample_size, 5);
+        assert_eq!(insight.assessment, "many fresh postings");
+
+        let insight = market_insight(Some(5000), &[60, 45, 20, 90], Vec::new());
+        assert_eq!(insight.median_days_since_published, Some(52.5));
+        assert_eq!(insight.newest_count_last_7d, 0);
+        assert_eq!(
+            insight.assessment,
+            "saturated/old postings, large market (5000 openings)"
+        );
+
+        let insight = market_insight(Some(0), &[], Vec::new());
+        assert_eq!(insight.median_days_since_published, None);
+        assert_eq!(insight.assessment, "no postings found");
+    }
+
+    #[tokio::test]
+    async fn test_clean_description_keeps_raw() {
+        let raw = "Wir suchen dich &amp; dein Team!\\n\\n\\nGr&uuml;&szlig;e";
+        let body =
+            serde_json::json!({ "titel": "Entwickler", "stellenbeschreibung": raw }).to_string();
+        let mock = MockApi::start(move |_| MockResponse::json(body.clone())).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let cleaned = server
+            .get_job_details(GetJobDetailsParams {
+                reference_number: "1".to_string(),
+                clean_description: Some(true),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            cleaned.description.as_deref(),
+            Some("Wir suchen dich & dein Team!\n\nGrüße")
+        );
+        assert_eq!(cleaned.description_raw.as_deref(), Some(raw));
+
+        let plain = server
+            .get_job_details(GetJobDetailsParams {
+                reference_number: "1".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(plain.description.as_deref(), Some(raw));
+        assert_eq!(plain.description_raw, None);
+    }
+
+    #[tokio::test]
+    async fn test_description_language_is_detected() {
+        let mock = MockApi::start(|req| {
+            let description = if req.path.ends_with("/en") {
+                "We are looking for a nurse to join our team. You will care for the patients on our ward."
+            } else {
+                ""
+            };
+            MockResponse::json(
+                serde_json::json!({ "titel": "Nurse", "stellenbeschreibung": description }).to_string(),
+            )
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+        let details = |refnr: &str| GetJobDetailsParams {
+            reference_number: refnr.to_string(),
+            ..Default::default()
+        };
+
+        let english = server.get_job_details(details("en")).await.unwrap();
+        assert_eq!(english.description_language.as_deref(), Some("en"));
+        let empty = server.get_job_details(details("empty")).await.unwrap();
+        assert_eq!(empty.description_language, None);
+
+        let disabled = JobsucheMcpServer::with_config(JobsucheConfig {
+            detect_description_language: false,
+            ..test_config(&mock.url)
+        })
+        .await
+        .unwrap();
+        let english = disabled.get_job_details(details("en")).await.unwrap();
+        assert_eq!(english.description_language, None);
+    }
+
+    #[derive(Default)]
+    struct ConcurrencyObserver {
+        in_flight: std::sync::atomic::AtomicUsize,
+        peak: std::sync::atomic::AtomicUsize,
+    }
+
+    impl JobsucheObserver for ConcurrencyObserver {
+        fn on_request(&self, _url: &str) {
+            use std::sync::atomic::Ordering;
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+        }
+
+        fn on_response(&self, _status: u16, _duration: Duration) {
+            self.in_flight
+                .fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_requests_are_bounded() {
+        let mock = MockApi::start(|req| {
+            let response = if req.path.contains("/jobdetails/") {
+                MockResponse::json(details_body("Entwickler"))
+            } else {
+                MockResponse::json(search_body(vec![]))
+            };
+            response.with_delay(Duration::from_millis(50))
+        })
+        .await;
+        let observer = Arc::new(ConcurrencyObserver::default());
+        let server = JobsucheMcpServer::with_observer(
+            JobsucheConfig {
+                max_concurrent_requests: 3,
+                ..test_config(&mock.url)
+            },
+            observer.clone(),
+        )
+        .await
+        .unwrap();
+
+        let mut tasks = tokio::task::JoinSet::new();
+        for i in 0..12 {
+            let server = server.clone();
+            tasks.spawn(async move {
+                if i % 2 == 0 {
+                    server
+                        .search_jobs(search_params(&format!("Job{}", i)))
+                        .await
+                        .map(|_| ())
+                } else {
+                    server
+                        .get_job_details(GetJobDetailsParams {
+                            reference_number: format!("ref-{}", i),
+                            ..Default::default()
+                        })
+                        .await
+                        .map(|_| ())
+                }
+            });
+        }
+        while let Some(result) = tasks.join_next().await {
+            result.unwrap().unwrap();
+        }
+
+        assert_eq!(mock.requests().len(), 12);
+        assert_eq!(observer.peak.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    fn logged_body_snippet(logs: &str) -> Option<String> {
+        logs.lines()
+            .find_map(|line| line.split_once("chars): "))
+            .map(|(_, snippet)| snippet.to_string())
+    }
+
+    #[test]
+    fn test_log_body_snippet_len_limits_logged_body() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let body = format!("<html>{}</html>", "ü".repeat(300));
+        let run = |snippet_len: usize| {
+            let body = body.clone();
+            capture_logs(|| {
+                runtime.block_on(async {
+                    let mock = MockApi::start(move |_| MockResponse::json(body.clone())).await;
+                    let server = JobsucheMcpServer::with_config(JobsucheConfig {
+                        log_body_snippet_len: snippet_len,
+                        retry_on_parse_error: false,
+                        ..test_config(&mock.url)
+                    })
+                    .await
+                    .unwrap();
+                    assert!(server
+                        .search_jobs(search_params("Entwickler"))
+                        .await
+                        .is_err());
+                })
+            })
+            .1
+        };
+
+        let logs = run(100);
+        let snippet = logged_body_snippet(&logs).expect("body snippet not logged");
+        assert_eq!(snippet.chars().count(), 100);
+        assert!(snippet.starts_with("<html>"));
+
+        let logs = run(0);
+        assert!(logs.contains("Failed to parse API response"));
+        assert_eq!(logged_body_snippet(&logs), None);
+    }
+
+    #[tokio::test]
+    async fn test_html_error_page_is_classified() {
+        let mock = MockApi::start(|_| {
+            MockResponse::json(
+                "<!DOCTYPE html><html><head><title>502 Bad Gateway</title></head><body><h1>Bad Gateway</h1></body></html>",
+            )
+            .with_header("content-type", "text/html")
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let err = server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "upstream returned an HTML error page: 502 Bad Gateway\nBad Gateway"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recently_updated_only_keeps_republished_jobs() {
+        let mut refreshed = listing("1", "Entwickler", "ACME", "Berlin");
+        refreshed["aktuelleVeroeffentlichungsdatum"] = serde_json::json!("2024-03-10");
+        let mut unchanged = listing("2", "Entwickler", "Beta", "Berlin");
+        unchanged["aktuelleVeroeffentlichungsdatum"] = serde_json::json!("2024-03-01");
+        let body = search_body(vec![refreshed, unchanged]);
+        let mock = MockApi::start(move |req| {
+            if req.path.contains("/jobdetails/") {
+                MockResponse::json(
+                    serde_json::json!({ "titel": "Entwickler", "ersteVeroeffentlichungsdatum": "2024-03-01" })
+                        .to_string(),
+                )
+            } else {
+                MockResponse::json(body.clone())
+            }
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .search_jobs(SearchJobsParams {
+                recently_updated_only: Some(true),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.jobs_count, 1);
+        assert_eq!(result.jobs[0].reference_number, "1");
+        assert_eq!(result.filtered_out_count, 1);
+        assert_eq!(mock.count_matching("/jobdetails/"), 2);
+    }
+
+    #[tokio::test]
+    async fn test_detail_fetch_cap_truncates_recently_updated_filter() {
+        let listings = (1..=3)
+            .map(|i| {
+                let mut job = listing(&i.to_string(), "Entwickler", "ACME", "Berlin");
+                job["aktuelleVeroeffentlichungsdatum"] = serde_json::json!("2024-03-10");
+                job
+            })
+            .collect();
+        let body = search_body(listings);
+        let mock = MockApi::start(move |req| {
+            if req.path.contains("/jobdetails/") {
+                MockResponse::json(
+                    serde_json::json!({ "titel": "Entwickler", "ersteVeroeffentlichungsdatum": "2024-03-01" })
+                        .to_string(),
+                )
+            } else {
+                MockResponse::json(body.clone())
+            }
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(JobsucheConfig {
+            max_detail_fetches_per_search: 1,
+            ..test_config(&mock.url)
+        })
+        .await
+        .unwrap();
+
+        let result = server
+            .search_jobs(SearchJobsParams {
+                recently_updated_only: Some(true),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
+
+        assert!(result.detail_fetch_limit_reached);
+        assert_eq!(result.jobs_count, 1);
+        assert_eq!(result.jobs[0].reference_number, "1");
+        assert_eq!(mock.count_matching("/jobdetails/"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_tool_timeout_fires() {
+        let mock = MockApi::start(|req| {
+            let response = if req.path.contains("/jobdetails/") {
+                MockResponse::json(details_body("Entwickler"))
+            } else {
+                MockResponse::json(search_body(vec![]))
+            };
+            response.with_delay(Duration::from_secs(3))
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(JobsucheConfig {
+            tool_timeout_secs: 1,
+            ..test_config(&mock.url)
+        })
+        .await
+        .unwrap();
+
+        let started = Instant::now();
+        let err = server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "search_jobs timed out after 1s");
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        let err = server
+            .get_job_details(GetJobDetailsParams {
+                reference_number: "123".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "get_job_details timed out after 1s");
+    }
+
+    #[tokio::test]
+    async fn test_clean_titles_keeps_raw_title() {
+        let body = search_body(vec![
+            listing("1", "LAGERMITARBEITER   (M/W/D)", "ACME", "Berlin"),
+            listing("2", "SAP Berater (m/w/d)", "Beta", "Berlin"),
+        ]);
+        let mock = MockApi::start(move |_| MockResponse::json(body.clone())).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .search_jobs(SearchJobsParams {
+                clean_titles: Some(true),
+                ..search_params("Lager")
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.jobs[0].title, "Lagermitarbeiter (m/w/d)");
+        assert_eq!(
+            result.jobs[0].title_raw.as_deref(),
+            Some("LAGERMITARBEITER   (M/W/D)")
+        );
+        assert_eq!(result.jobs[1].title, "SAP Berater (m/w/d)");
+        assert_eq!(result.jobs[1].title_raw, None);
+
+        let raw = server.search_jobs(search_params("Lager")).await.unwrap();
+        assert_eq!(raw.jobs[0].title, "LAGERMITARBEITER   (M/W/D)");
+        assert_eq!(raw.jobs[0].title_raw, None);
+    }
+
+    #[tokio::test]
+    async fn test_title_fallbacks_are_flagged() {
+        let mut with_title = listing("1", "Entwickler", "ACME", "Berlin");
+        with_title["beruf"] = serde_json::json!("Softwareentwickler");
+        let mut beruf_only = listing("2", "", "Beta", "Köln");
+        beruf_only["titel"] = serde_json::Value::Null;
+        beruf_only["beruf"] = serde_json::json!("Softwareentwickler");
+        let empty = serde_json::json!({ "refnr": "3", "arbeitsort": {} });
+        let body = search_body(vec![with_title, beruf_only, empty]);
+        let mock = MockApi::start(move |_| MockResponse::json(body.clone())).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .unwrap();
+        let titles: Vec<(&str, bool)> = result
+            .jobs
+            .iter()
+            .map(|j| (j.title.as_str(), j.title_is_fallback))
+            .collect();
+        assert_eq!(
+            titles,
+            vec![
+                ("Entwickler", false),
+                ("Softwareentwickler", true),
+                ("Stellenangebot", true),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancelling_batch_stops_further_searches() {
+        let mock = MockApi::start(|_| {
+            MockResponse::json(search_body(vec![listing(
+                "1",
+                "Entwickler",
+                "ACME",
+                "Berlin",
+            )]))
+            .with_delay(Duration::from_millis(300))
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let batch = tokio::spawn({
+            let server = server.clone();
+            async move {
+                server
+                    .batch_search_jobs(BatchSearchJobsParams {
+                        searches: vec![
+                            batch_item("first", "Entwickler"),
+                            batch_item("second", "Developer"),
+                            batch_item("third", "Programmierer"),
+                        ],
+                        max_details_per_search: Some(0),
+                        cancel_token: Some("batch-1".to_string()),
+                        detail_fields: None,
+                        correlation_id: None,
+                    })
+                    .await
+            }
+        });
+
+        while mock.requests().is_empty() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(server.cancel("batch-1"));
+
+        let result = batch.await.unwrap().unwrap();
+        assert!(result.cancelled);
+        assert_eq!(result.searches_count, 1);
+        assert_eq!(result.results[0].search_name, "first");
+        assert_eq!(mock.requests().len(), 1);
+        assert!(!server.cancel("batch-1"));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_token_is_exclusive_and_released_on_abort() {
+        let mock = MockApi::start(|_| {
+            MockResponse::json(search_body(vec![])).with_delay(Duration::from_millis(300))
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+        let params = || BatchSearchJobsParams {
+            searches: vec![batch_item("first", "Entwickler")],
+            max_details_per_search: Some(0),
+            cancel_token: Some("batch-1".to_string()),
+            detail_fields: None,
+            correlation_id: None,
+        };
+
+        let batch = tokio::spawn({
+            let server = server.clone();
+            async move { server.batch_search_jobs(params()).await }
+        });
+        while mock.requests().is_empty() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let Err(err) = server.batch_search_jobs(params()).await else {
+            panic!("a second batch with the same token must be rejected");
+        };
+        assert!(err.to_string().contains("already used"), "{}", err);
+
+        batch.abort();
+        assert!(batch.await.unwrap_err().is_cancelled());
+        assert!(!server.cancel("batch-1"));
+    }
+
+    #[tokio::test]
+    async fn test_requests_are_signed_when_secret_configured() {
+        let mock = MockApi::start(|_| MockResponse::json(search_body(vec![]))).await;
+        let server = JobsucheMcpServer::with_config(JobsucheConfig {
+            signing_secret: Some("gateway-secret".to_string()),
+            ..test_config(&mock.url)
+        })
+        .await
+        .unwrap();
+
+        server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .unwrap();
+
+        let request = &mock.requests()[0];
+        let timestamp: u64 = request.headers["x-timestamp"].parse().unwrap();
+        let signature = &request.headers["x-signature"];
+        assert!(request.path.starts_with("/pc/v4/jobs?"));
+        assert!(signing::verify(
+            "gateway-secret",
+            &request.path,
+            timestamp,
+            signature
+        ));
+        assert!(!signing::verify(
+            "wrong-secret",
+            &request.path,
+            timestamp,
+            signature
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_requests_are_unsigned_by_default() {
+        let mock = MockApi::start(|_| MockResponse::json(search_body(vec![]))).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .unwrap();
+
+        let request = &mock.requests()[0];
+        assert!(!request.headers.contains_key("x-signature"));
+        assert!(!request.headers.contains_key("x-timestamp"));
+    }
+
+    #[tokio::test]
+    async fn test_over_range_page_size_is_clamped_by_default() {
+        let mock = MockApi::start(|_| MockResponse::json(search_body(vec![]))).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        server
+            .search_jobs(SearchJobsParams {
+                page_size: Some(500),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
+
+        assert!(mock.requests()[0].path.contains("size=100"));
+    }
+
+    #[tokio::test]
+    async fn test_over_range_page_size_is_rejected_with_strict_params() {
+        let mock = MockApi::start(|_| MockResponse::json(search_body(vec![]))).await;
+        let server = JobsucheMcpServer::with_config(JobsucheConfig {
+            strict_params: true,
+            ..test_config(&mock.url)
+        })
+        .await
+        .unwrap();
+
+        let err = server
+            .search_jobs(SearchJobsParams {
+                page_size: Some(500),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "page_size must be between 1 and 100, got 500"
+        );
+        assert!(mock.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_collect_merges_and_dedupes_pages() {
+        let mock = MockApi::start(|req| {
+            let listings = if req.path.contains("page=2") {
+                vec![
+                    listing("3", "Entwickler", "ACME", "Berlin"),
+                    listing("4", "Entwickler", "Beta", "Köln"),
+                ]
+            } else {
+                vec![
+                    listing("1", "Entwickler", "ACME", "Berlin"),
+                    listing("3", "Entwickler", "ACME", "Berlin"),
+                    listing("2", "Entwickler", "Gamma", "Hamburg"),
+                ]
+            };
+            MockResponse::json(search_body(listings))
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .search_jobs_collect(CollectParams {
+                search: SearchJobsParams {
+                    page_size: Some(3),
+                    ..search_params("Entwickler")
+                },
+                max_pages: 5,
+                max_results: 100,
+                parallel_pages: None,
+            })
+            .await
+            .unwrap();
+
+        let refnrs: Vec<&str> = result
+            .jobs
+            .iter()
+            .map(|j| j.reference_number.as_str())
+            .collect();
+        assert_eq!(refnrs, vec!["1", "3", "2", "4"]);
+        assert_eq!(result.jobs_count, 4);
+        assert_eq!(result.pages_fetched, 2);
+        assert_eq!(result.api_calls, 2);
+    }
+
+    #[tokio::test]
+    async fn test_collect_handles_shifted_page() {
+        // A new posting pushes "3" from the end of page 1 onto page 2
+        let mock = MockApi::start(|req| {
+            let listings = if req.path.contains("page=2") {
+                vec![
+                    listing("3", "Entwickler", "ACME", "Berlin"),
+                    listing("4", "Entwickler", "Beta", "Köln"),
+                ]
+            } else {
+                vec![
+                    listing("1", "Entwickler", "ACME", "Berlin"),
+                    listing("2", "Entwickler", "Gamma", "Hamburg"),
+                    listing("3", "Entwickler", "ACME", "Berlin"),
+                ]
+            };
+            MockResponse::json(search_body(listings))
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .search_jobs_collect(CollectParams {
+                search: SearchJobsParams {
+                    page_size: Some(3),
+                    ..search_params("Entwickler")
+                },
+                max_pages: 5,
+                max_results: 100,
+                parallel_pages: None,
+            })
+            .await
+            .unwrap();
+
+        let refnrs: Vec<&str> = result
+            .jobs
+            .iter()
+            .map(|j| j.reference_number.as_str())
+            .collect();
+        assert_eq!(refnrs, vec!["1", "2", "3", "4"]);
+        assert_eq!(result.cross_page_duplicates, 1);
+    }
+
+    #[tokio::test]
+    async fn test_collect_parallel_pages_matches_sequential() {
+        let mock = MockApi::start(|req| {
+            let page = ["page=2", "page=3", "page=4"]
+                .iter()
+                .position(|p| req.path.contains(p))
+                .map_or(1, |idx| idx + 2);
+            let listings: Vec<_> = (1..=2)
+                .map(|i| (page - 1) * 2 + i)
+                .filter(|id| *id <= 6)
+                .map(|id| listing(&id.to_string(), "Entwickler", "ACME", "Berlin"))
+                .collect();
+            let mut body: serde_json::Value = serde_json::from_str(&search_body(listings)).unwrap();
+            body["maxErgebnisse"] = serde_json::json!(6);
+            MockResponse::json(body.to_string()).with_delay(Duration::from_millis(50))
+        })
+        .await;
+        let collect = |parallel_pages| CollectParams {
+            search: SearchJobsParams {
+                page_size: Some(2),
+                ..search_params("Entwickler")
+            },
+            max_pages: 5,
+            max_results: 100,
+            parallel_pages,
+        };
+
+        // Separate servers, so neither run is served from the other's search cache
+        let sequential = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap()
+            .search_jobs_collect(collect(None))
+            .await
+            .unwrap();
+        let parallel = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap()
+            .search_jobs_collect(collect(Some(3)))
+            .await
+            .unwrap();
+
+        let refnrs = |result: &CollectResult| -> Vec<String> {
+            result
+                .jobs
+                .iter()
+                .map(|j| j.reference_number.clone())
+                .collect()
+        };
+        assert_eq!(refnrs(&sequential), vec!["1", "2", "3", "4", "5", "6"]);
+        assert_eq!(refnrs(&parallel), refnrs(&sequential));
+        assert_eq!(parallel.cross_page_duplicates, 0);
+        assert_eq!(sequential.sequential_rounds, 4);
+        assert_eq!(parallel.sequential_rounds, 2);
+        assert_eq!(parallel.pages_fetched, 3);
+    }
+
+    #[tokio::test]
+    async fn test_collect_ignores_errors_past_the_last_page() {
+        let mock = MockApi::start(|req| {
+            let listings = if req.path.contains("page=2") {
+                vec![listing("3", "Entwickler", "ACME", "Berlin")]
+            } else if req.path.contains("page=3") || req.path.contains("page=4") {
+                return MockResponse::status(500, "");
+            } else {
+                vec![
+                    listing("1", "Entwickler", "ACME", "Berlin"),
+                    listing("2", "Entwickler", "ACME", "Berlin"),
+                ]
+            };
+            let mut body: serde_json::Value = serde_json::from_str(&search_body(listings)).unwrap();
+            body["maxErgebnisse"] = serde_json::json!(8);
+            MockResponse::json(body.to_string())
+        })
+        .await;
+
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+        let result = server
+            .search_jobs_collect(CollectParams {
+                search: SearchJobsParams {
+                    page_size: Some(2),
+                    ..search_params("Entwickler")
+                },
+                max_pages: 5,
+                max_results: 100,
+                parallel_pages: Some(3),
+            })
+            .await
+            .unwrap();
+
+        let refnrs: Vec<&str> = result
+            .jobs
+            .iter()
+            .map(|j| j.reference_number.as_str())
+            .collect();
+        assert_eq!(refnrs, vec!["1", "2", "3"]);
+        assert_eq!(result.pages_fetched, 2);
+        assert_eq!(mock.count_matching("page=4"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_latency_is_recorded_for_every_tool() {
+        let mock = MockApi::start(|req| {
+            if req.path.contains("/jobdetails/") {
+                MockResponse::json(details_body("Entwickler"))
+            } else {
+                MockResponse::json(search_body(vec![listing(
+                    "1",
+                    "Entwickler",
+                    "ACME",
+                    "Berlin",
+                )]))
+            }
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        server
+            .search_jobs_collect(CollectParams {
+                search: search_params("Entwickler"),
+                max_pages: 1,
+                max_results: 10,
+                parallel_pages: None,
+            })
+            .await
+            .unwrap();
+        server
+            .get_job_details_bulk(BulkDetailsParams {
+                reference_numbers: vec!["10000-1234567890-S".to_string()],
+                api_key_override: None,
+            })
+            .await
+            .unwrap();
+        server.list_filters().await.unwrap();
+
+        let latencies = server.get_server_status().await.unwrap().tool_latencies;
+        let count = |tool: &str| {
+            latencies
+                .iter()
+                .find(|l| l.tool == tool)
+                .map_or(0, |l| l.count)
+        };
+        assert_eq!(count("search_jobs_collect"), 1);
+        assert_eq!(count("get_job_details_bulk"), 1);
+        assert_eq!(count("list_filters"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_output_schemas_cover_all_tools() {
+        let server = JobsucheMcpServer::with_config(test_config("http://127.0.0.1:9"))
+            .await
+            .unwrap();
+        let schemas = server.get_output_schemas().await.unwrap();
+
+        assert_eq!(schemas.len(), 24);
+        let search = &schemas["search_jobs"];
+        assert_eq!(search["properties"]["jobs"]["type"], "array");
+        assert!(search["properties"]["jobs"]["items"]["$ref"]
+            .as_str()
+            .is_some_and(|r| r.ends_with("/JobSummary")));
+        assert_eq!(schemas["get_job_details"]["title"], "GetJobDetailsResult");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_application_page() {
+        let page = MockApi::start(|req| {
+            if req.path == "/karriere/42" {
+                return MockResponse::status(302, "").with_header("location", "/karriere/42/de");
+            }
+            MockResponse::json(
+                "<html><body><h1>Entwickler (m/w/d)</h1><script>track();</script><p>Bewirb dich &amp; komm zu uns</p></body></html>",
+            )
+            .with_header("content-type", "text/html; charset=utf-8")
+        })
+        .await;
+        let external_url = format!("{}/karriere/42", page.url);
+        let api = MockApi::start(move |req| {
+            let url = if req.path.contains("/jobdetails/1") {
+                serde_json::json!(external_url)
+            } else {
+                serde_json::json!("mailto:jobs@acme.example")
+            };
+            MockResponse::json(
+                serde_json::json!({ "titel": "Entwickler", "externeUrl": url }).to_string(),
+            )
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(JobsucheConfig {
+            allow_loopback_application_pages: true,
+            ..test_config(&api.url)
+        })
+        .await
+        .unwrap();
+
+        let result = server
+            .fetch_application_page(GetJobDetailsParams {
+                reference_number: "1".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert!(result.fetched);
+        assert_eq!(result.url, format!("{}/karriere/42", page.url));
+        assert_eq!(
+            result.text_excerpt,
+            "Entwickler (m/w/d)\nBewirb dich & komm zu uns"
+        );
+        assert!(page
+            .requests()
+            .iter()
+            .all(|r| !r.headers.contains_key("x-api-key")));
+        assert_eq!(page.requests().len(), 2);
+
+        let result = server
+            .fetch_application_page(GetJobDetailsParams {
+                reference_number: "2".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert!(!result.fetched);
+        assert_eq!(page.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_application_page_rejects_internal_addresses() {
+        let page = MockApi::start(|req| {
+            if req.path == "/weiter" {
+                return MockResponse::status(302, "")
+                    .with_header("location", "http://169.254.169.254/latest/meta-data");
+            }
+            MockResponse::json("<p>intern</p>").with_header("content-type", "text/html")
+        })
+        .await;
+        let page_url = page.url.clone();
+        let api = MockApi::start(move |req| {
+            let path = if req.path.contains("/jobdetails/1") {
+                "/intern"
+            } else {
+                "/weiter"
+            };
+            let url = format!("{}{}", page_url, path);
+            MockResponse::json(
+                serde_json::json!({ "titel": "Entwickler", "externeUrl": url }).to_string(),
+            )
+        })
+        .await;
+        let params = |refnr: &str| GetJobDetailsParams {
+            reference_number: refnr.to_string(),
+            ..Default::default()
+        };
+
+        // Loopback pages are refused unless explicitly allowed
+        let server = JobsucheMcpServer::with_config(test_config(&api.url))
+            .await
+            .unwrap();
+        assert!(
+            !server
+                .fetch_application_page(params("1"))
+                .await
+                .unwrap()
+                .fetched
+        );
+        assert!(page.requests().is_empty());
+
+        // Redirects are checked hop by hop
+        let server = JobsucheMcpServer::with_config(JobsucheConfig {
+            allow_loopback_application_pages: true,
+            ..test_config(&api.url)
+        })
+        .await
+        .unwrap();
+        assert!(
+            !server
+                .fetch_application_page(params("2"))
+                .await
+                .unwrap()
+                .fetched
+        );
+        assert_eq!(page.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_is_public_ip() {
+        for public in ["93.184.216.34", "2a00:1450:4001:80b::200e"] {
+            assert!(is_public_ip(public.parse().unwrap()), "{}", public);
+        }
+        for internal in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.178.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:10.0.0.1",
+        ] {
+            assert!(!is_public_ip(internal.parse().unwrap()), "{}", internal);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_field_weights_rerank_results() {
+        let body = search_body(vec![
+            listing("1", "Buchhalter", "ACME", "Berlin"),
+            listing("2", "Java Entwickler", "Beta", "Berlin"),
+        ]);
+        let mock = MockApi::start(move |_| MockResponse::json(body.clone())).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+        let search = |title: f64, employer: f64| SearchJobsParams {
+            employer: Some("ACME".to_string()),
+            field_weights: Some(FieldWeights {
+                title: Some(title),
+                employer: Some(employer),
+                branch: None,
+            }),
+            ..search_params("Java")
+        };
+
+        let by_title = server.search_jobs(search(3.0, 1.0)).await.unwrap();
+        let refnrs: Vec<&str> = by_title
+            .jobs
+            .iter()
+            .map(|j| j.reference_number.as_str())
+            .collect();
+        assert_eq!(refnrs, vec!["2", "1"]);
+        assert_eq!(by_title.jobs[0].relevance_score, Some(0.75));
+        assert_eq!(by_title.jobs[1].relevance_score, Some(0.25));
+
+        let by_employer = server.search_jobs(search(1.0, 3.0)).await.unwrap();
+        let refnrs: Vec<&str> = by_employer
+            .jobs
+            .iter()
+            .map(|j| j.reference_number.as_str())
+            .collect();
+        assert_eq!(refnrs, vec!["1", "2"]);
+
+        let plain = server.search_jobs(search_params("Java")).await.unwrap();
+        assert_eq!(plain.jobs[0].relevance_score, None);
+
+        // Rejected before anything is sent to the API
+        let requests = mock.requests().len();
+        let invalid = SearchJobsParams {
+            job_title: Some("Python".to_string()),
+            ..search(-1.0, 1.0)
+        };
+        assert!(server.search_jobs(invalid).await.is_err());
+        assert_eq!(mock.requests().len(), requests);
+    }
+
+    #[test]
+    fn test_haversine_known_distances() {
+        let berlin = (52.5200, 13.4050);
+        let munich = (48.1351, 11.5820);
+        let hamburg = (53.5511, 9.9937);
+        assert!((haversine_km(berlin, munich) - 504.4).abs() < 1.0);
+        assert!((haversine_km(berlin, hamburg) - 255.3).abs() < 1.0);
+        assert_eq!(haversine_km(berlin, berlin), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_distance_from_origin_sorts_jobs() {
+        let mut munich = listing("1", "Entwickler", "ACME", "München");
+        munich["arbeitsort"]["koordinaten"] = serde_json::json!({ "lat": 48.1351, "lon": 11.5820 });
+        let unknown = listing("2", "Entwickler", "Beta", "Irgendwo");
+        let mut hamburg = listing("3", "Entwickler", "Gamma", "Hamburg");
+        hamburg["arbeitsort"]["koordinaten"] = serde_json::json!({ "lat": 53.5511, "lon": 9.9937 });
+        let body = search_body(vec![munich, unknown, hamburg]);
+        let mock = MockApi::start(move |_| MockResponse::json(body.clone())).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .search_jobs(SearchJobsParams {
+                origin_lat: Some(52.5200),
+                origin_lon: Some(13.4050),
+                sort_by_distance: Some(true),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
+
+        let refnrs: Vec<&str> = result
+            .jobs
+            .iter()
+            .map(|j| j.reference_number.as_str())
+            .collect();
+        assert_eq!(refnrs, vec!["3", "1", "2"]);
+        assert!((result.jobs[0].distance_from_origin_km.unwrap() - 255.3).abs() < 1.0);
+        assert_eq!(result.jobs[2].distance_from_origin_km, None);
+
+        // Rejected before anything is sent to the API
+        let requests = mock.requests().len();
+        let err = server
+            .search_jobs(SearchJobsParams {
+                origin_lat: Some(52.52),
+                ..search_params("Koch")
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "origin_lat and origin_lon must be given together"
+        );
+        assert_eq!(mock.requests().len(), requests);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_details_lists_retryable_failures() {
+        let mock = MockApi::start(|req| {
+            if req.path.ends_with("/limited") {
+                MockResponse::status(429, "").with_header("Retry-After", "0")
+            } else if req.path.ends_with("/gone") {
+                MockResponse::status(404, "")
+            } else {
+                MockResponse::json(details_body("Entwickler"))
+            }
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .get_job_details_bulk(BulkDetailsParams {
+                reference_numbers: ["ok", "limited", "gone"].map(String::from).to_vec(),
+                api_key_override: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.jobs_count, 1);
+        assert_eq!(result.jobs[0].reference_number, "ok");
+        assert_eq!(result.retry_reference_numbers, vec!["limited".to_string()]);
+        let failures: Vec<(&str, bool)> = result
+            .failed
+            .iter()
+            .map(|f| (f.reference_number.as_str(), f.retryable))
+            .collect();
+        assert_eq!(failures, vec![("limited", true), ("gone", false)]);
+        assert_eq!(result.failed[1].error, "API error: 404 Not Found");
+    }
+
+    #[tokio::test]
+    async fn test_compare_locations_sorts_counts() {
+        let mock = MockApi::start(|req| {
+            let count = if req.path.contains("wo=Hamburg") {
+                1
+            } else if req.path.contains("wo=Berlin") {
+                3
+            } else if req.path.contains("wo=Bremen") {
+                return MockResponse::status(500, "");
+            } else {
+                0
+            };
+            let listings = (1..=count)
+                .map(|i| listing(&i.to_string(), "Pflegekraft", "Klinikum", "Ort"))
+                .collect();
+            MockResponse::json(search_body(listings))
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .compare_locations(CompareLocationsParams {
+                job_title: "Pflegekraft".to_string(),
+                locations: ["Hamburg", "Bremen", "Kiel", "Berlin"]
+                    .map(String::from)
+                    .to_vec(),
+                radius_km: Some(20),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result.counts,
+            vec![
+                ("Berlin".to_string(), Some(3)),
+                ("Hamburg".to_string(), Some(1)),
+                ("Kiel".to_string(), Some(0)),
+                ("Bremen".to_string(), None),
+            ]
+        );
+        assert_eq!(mock.count_matching("size=1"), 4);
+        assert_eq!(mock.count_matching("umkreis=20"), 4);
+    }
+
+    #[tokio::test]
+    async fn test_status_counts_recovered_retries() {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = calls.clone();
+        let mock = MockApi::start(move |_| {
+            if counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 0 {
+                MockResponse::status(429, "").with_header("Retry-After", "0")
+            } else {
+                MockResponse::json(search_body(vec![]))
+            }
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .unwrap();
+        let status = server.get_server_status().await.unwrap();
+
+        assert_eq!(status.retries_succeeded, 1);
+        assert_eq!(status.retries_exhausted, 0);
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_boolean_or_query_merges_searches() {
+        let mock = MockApi::start(|req| {
+            let listings = if req.path.contains("was=Data%20Scientist") {
+                vec![
+                    listing("1", "Data Scientist", "ACME", "Berlin"),
+                    listing("2", "Data Scientist / ML Engineer", "Beta", "Berlin"),
+                ]
+            } else {
+                vec![
+                    listing("2", "Data Scientist / ML Engineer", "Beta", "Berlin"),
+                    listing("3", "ML Engineer", "Gamma", "Berlin"),
+                    listing("4", "ML Engineer Praktikum", "Delta", "Berlin"),
+                ]
+            };
+            MockResponse::json(search_body(listings))
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .search_jobs(SearchJobsParams {
+                query_syntax: Some("boolean".to_string()),
+                ..search_params(r#""Data Scientist" OR "ML Engineer" NOT praktikum"#)
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(mock.requests().len(), 2);
+        assert_eq!(mock.count_matching("was=ML%20Engineer"), 1);
+        let refnrs: Vec<&str> = result
+            .jobs
+            .iter()
+            .map(|j| j.reference_number.as_str())
+            .collect();
+        assert_eq!(refnrs, vec!["1", "2", "3"]);
+        assert_eq!(result.jobs[2].matched_term.as_deref(), Some("ML Engineer"));
+        assert_eq!(result.filtered_out_count, 1);
+
+        let err = server
+            .search_jobs(SearchJobsParams {
+                query_syntax: Some("boolean".to_string()),
+                ..search_params("Koch OR")
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid boolean query: OR needs a term on both sides"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prioritize_salary_orders_salaried_first() {
+        let body = search_body(vec![
+            listing("1", "Entwickler", "ACME", "Berlin"),
+            listing("2", "Entwickler", "Beta", "Berlin"),
+            listing("3", "Entwickler", "Gamma", "Berlin"),
+            listing("4", "Entwickler", "Delta", "Berlin"),
+        ]);
+        let mock = MockApi::start(move |req| {
+            if req.path.ends_with("/2") || req.path.ends_with("/4") {
+                MockResponse::json(
+                    serde_json::json!({ "titel": "Entwickler", "verguetung": "4.000 EUR" })
+                        .to_string(),
+                )
+            } else if req.path.contains("/jobdetails/") {
+                MockResponse::json(
+                    serde_json::json!({ "titel": "Entwickler", "verguetung": " " }).to_string(),
+                )
+            } else {
+                MockResponse::json(body.clone())
+            }
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .search_jobs(SearchJobsParams {
+                prioritize_salary: Some(true),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
+
+        let refnrs: Vec<&str> = result
+            .jobs
+            .iter()
+            .map(|j| j.reference_number.as_str())
+            .collect();
+        assert_eq!(refnrs, vec!["2", "4", "1", "3"]);
+        assert_eq!(mock.count_matching("/jobdetails/"), 4);
+        assert!(!result.detail_fetch_limit_reached);
+    }
+
+    #[test]
+    fn test_correlation_id_is_sent_and_logged() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let (requests, logs) = capture_logs(|| {
+            runtime.block_on(async {
+                let mock = MockApi::start(|req| {
+                    if req.path.contains("/jobdetails/") {
+                        MockResponse::json(details_body("Entwickler"))
+                    } else {
+                        MockResponse::json(search_body(vec![listing(
+                            "1",
+                            "Entwickler",
+                            "ACME",
+                            "Berlin",
+                        )]))
+                    }
+                })
+                .await;
+                let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+                    .await
+                    .unwrap();
+
+                server
+                    .search_jobs(SearchJobsParams {
+                        correlation_id: Some("req-42".to_string()),
+                        ..search_params("Entwickler")
+                    })
+                    .await
+                    .unwrap();
+                server
+                    .get_job_details(GetJobDetailsParams {
+                        reference_number: "1".to_string(),
+                        correlation_id: Some("req-43".to_string()),
+                        ..Default::default()
+                    })
+                    .await
+                    .unwrap();
+                server.search_jobs(search_params("Koch")).await.unwrap();
+                mock.requests()
+            })
+        });
+
+        let ids: Vec<Option<&str>> = requests
+            .iter()
+            .map(|req| req.headers.get("x-correlation-id").map(String::as_str))
+            .collect();
+        assert_eq!(ids, vec![Some("req-42"), Some("req-43"), None]);
+        assert!(logs.contains("correlation_id=\"req-42\""));
+        assert!(logs.contains("correlation_id=\"req-43\""));
+    }
+
+    #[tokio::test]
+    async fn test_min_description_chars_drops_thin_listings() {
+        let mock = MockApi::start(|req| {
+            let description = if req.path.ends_with("/1") {
+                "Wir suchen eine erfahrene Entwicklerin für unser Team in Berlin. ".repeat(5)
+            } else {
+                "Entwickler gesucht".to_string()
+            };
+            if req.path.contains("/jobdetails/") {
+                MockResponse::json(
+                    serde_json::json!({ "titel": "Entwickler", "stellenbeschreibung": description }).to_string(),
+                )
+            } else {
+                MockResponse::json(search_body(vec![
+                    listing("1", "Entwickler", "ACME", "Berlin"),
+                    listing("2", "Entwickler", "Beta", "Berlin"),
+                ]))
+            }
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .search_jobs(SearchJobsParams {
+                min_description_chars: Some(100),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
+
+        let refnrs: Vec<&str> = result
+            .jobs
+            .iter()
+            .map(|job| job.reference_number.as_str())
+            .collect();
+        assert_eq!(refnrs, vec!["1"]);
+        assert_eq!(result.filtered_out_count, 1);
+        assert!(!result.detail_fetch_limit_reached);
+    }
+
+    #[tokio::test]
+    async fn test_batch_search_digest_lists_top_titles_and_employers() {
+        let mock = MockApi::start(|req| {
+            let listings = if req.path.contains("was=Pflege") {
+                vec![
+                    listing("1", "Pflegefachkraft", "Klinikum Nord", "Hamburg"),
+                    listing("2", "Pflegehelfer", "Klinikum Nord", "Hamburg"),
+                    listing("3", "Pflegehelfer", "Seniorenheim Elbe", "Hamburg"),
+                    listing("4", "Pflegehelfer", "Pflegedienst Alster", "Hamburg"),
+                ]
+            } else {
+                vec![listing("5", "Koch", "Hotel Hafen", "Hamburg")]
+            };
+            MockResponse::json(search_body(listings))
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let digest = server
+            .batch_search_digest(BatchSearchJobsParams {
+                searches: vec![batch_item("pflege", "Pflege"), batch_item("kueche", "Koch")],
+                max_details_per_search: None,
+                cancel_token: None,
+                detail_fields: None,
+                correlation_id: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(digest.searches_count, 2);
+        let pflege = &digest.searches[0];
+        assert_eq!(pflege.search_name, "pflege");
+        assert_eq!(pflege.total_results, Some(4));
+        assert_eq!(pflege.top_titles, vec!["Pflegehelfer", "Pflegefachkraft"]);
+        assert_eq!(
+            pflege.top_employers,
+            vec!["Klinikum Nord", "Seniorenheim Elbe", "Pflegedienst Alster"]
+        );
+        let kueche = &digest.searches[1];
+        assert_eq!(kueche.top_titles, vec!["Koch"]);
+        assert_eq!(kueche.top_employers, vec!["Hotel Hafen"]);
+        assert_eq!(mock.count_matching("/jobdetails/"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_search_terms_are_sent_once() {
+        let mock = MockApi::start(|_| MockResponse::json(search_body(vec![]))).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        server
+            .search_jobs(SearchJobsParams {
+                employer: Some("siemens".to_string()),
+                employment_type: Some(vec![
+                    "fulltime".to_string(),
+                    "vollzeit".to_string(),
+                    "teilzeit".to_string(),
+                ]),
+                ..search_params("Siemens")
+            })
+            .await
+            .unwrap();
+
+        let path = &mock.requests()[0].path;
+        assert!(path.contains("was=Siemens&"), "{}", path);
+        assert_eq!(path.matches("arbeitszeit=vz").count(), 1, "{}", path);
+        assert_eq!(path.matches("arbeitszeit=tz").count(), 1, "{}", path);
+    }
+
+    #[tokio::test]
+    async fn test_startup_check_rejects_unauthorized_key() {
+        let mock = MockApi::start(|_| MockResponse::status(401, "")).await;
+
+        let Err(err) = JobsucheMcpServer::with_config(JobsucheConfig {
+            run_startup_check: true,
+            ..test_config(&mock.url)
+        })
+        .await
+        else {
+            panic!("startup check passed despite 401");
+        };
+        assert!(err.to_string().contains("Startup check failed"), "{}", err);
+        assert!(err.to_string().contains("401"), "{}", err);
+
+        assert!(JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .is_ok());
+        assert_eq!(mock.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_details_warms_cache() {
+        let mock = MockApi::start(|req| {
+            if req.path.ends_with("/404") {
+                MockResponse::status(404, "")
+            } else {
+                MockResponse::json(details_body("Entwickler"))
+            }
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .prefetch_details(PrefetchParams {
+                reference_numbers: vec!["1".to_string(), "2".to_string(), "404".to_string()],
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.prefetched, 2);
+        assert_eq!(result.failed, 1);
+        assert_eq!(mock.count_matching("/jobdetails/"), 3);
+
+        let details = server
+            .get_job_details(GetJobDetailsParams {
+                reference_number: "2".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(details.title.as_deref(), Some("Entwickler"));
+        assert_eq!(mock.count_matching("/jobdetails/"), 3);
+    }
+
+    #[tokio::test]
+    async fn test_strict_batch_limits() {
+        let mock = MockApi::start(|req| {
+            if req.path.contains("/jobdetails/") {
+                MockResponse::json(details_body("Entwickler"))
+            } else {
+                MockResponse::json(search_body(vec![listing(
+                    "1",
+                    "Entwickler",
+                    "ACME",
+                    "Berlin",
+                )]))
+            }
+        })
+        .await;
+        let over_limit = |max_details_per_search| BatchSearchJobsParams {
+            searches: (1..=6)
+                .map(|i| batch_item(&format!("search {}", i), "Entwickler"))
+                .collect(),
+            max_details_per_search,
+            cancel_token: None,
+            detail_fields: None,
+            correlation_id: None,
+        };
+
+        let lenient = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+        let result = lenient
+            .batch_search_jobs(over_limit(Some(0)))
+            .await
+            .unwrap();
+        assert_eq!(result.searches_count, 5);
+
+        let strict = JobsucheMcpServer::with_config(JobsucheConfig {
+            strict_batch_limits: true,
+            ..test_config(&mock.url)
+        })
+        .await
+        .unwrap();
+        let requests_before = mock.requests().len();
+        let err = strict
+            .batch_search_jobs(over_limit(Some(0)))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "searches exceeds the batch limit of 5, got 6"
+        );
+
+        let mut params = over_limit(Some(6));
+        params.searches.truncate(1);
+        let err = strict.batch_search_jobs(params).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "max_details_per_search exceeds the batch limit of 5, got 6"
+        );
+        assert_eq!(mock.requests().len(), requests_before);
+    }
+
+    #[tokio::test]
+    async fn test_executed_query_is_reported_without_secrets() {
+        let mock = MockApi::start(|_| MockResponse::json(search_body(vec![]))).await;
+        let api_url = mock.url.replace("http://", "http://svc:hunter2@");
+        let server = JobsucheMcpServer::with_config(JobsucheConfig {
+            api_key: Some("secret-key".to_string()),
+            ..test_config(&api_url)
+        })
+        .await
+        .unwrap();
+
+        let result = server
+            .search_jobs(SearchJobsParams {
+                location: Some("München".to_string()),
+                ..search_params("Data Scientist")
+            })
+            .await
+            .unwrap();
+
+        let query = &result.executed_query;
+        assert!(
+            query.contains("/pc/v4/jobs?was=Data%20Scientist&wo=M%C3%BCnchen"),
+            "{}",
+            query
+        );
+        assert!(!query.contains("hunter2"), "{}", query);
+        assert!(!query.contains("svc"), "{}", query);
+        assert!(!query.contains("secret-key"), "{}", query);
+    }
+
+    #[tokio::test]
+    async fn test_connection_errors_are_retried() {
+        // Reserve a port and release it so the first attempt is refused
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .to_string();
+        let server = JobsucheMcpServer::with_config(test_config(&format!("http://{}", addr)))
+            .await
+            .unwrap();
+        let search = {
+            let server = server.clone();
+            tokio::spawn(async move { server.search_jobs(search_params("Entwickler")).await })
+        };
+        // Come up while the client is backing off after the refused attempt
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let mock = MockApi::start_at(&addr, |_| {
+            MockResponse::json(search_body(vec![listing(
+                "1",
+                "Entwickler",
+                "ACME",
+                "Berlin",
+            )]))
+        })
+        .await;
+
+        let result = search.await.unwrap().unwrap();
+        assert_eq!(result.jobs_count, 1);
+        assert_eq!(mock.requests().len(), 1);
+        let status = server.get_server_status().await.unwrap();
+        assert_eq!(status.retries_succeeded, 1);
+
+        // A connection dropped after the request was sent is not retried
+        let mock = MockApi::start(|_| MockResponse::dropped_connection()).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+        assert!(server.search_jobs(search_params("Koch")).await.is_err());
+        assert_eq!(mock.count_matching("was=Koch"), 1);
+
+        let server = JobsucheMcpServer::with_config(JobsucheConfig {
+            retry_on_connection_errors: false,
+            ..test_config("http://127.0.0.1:1")
+        })
+        .await
+        .unwrap();
+        assert!(server.search_jobs(search_params("Koch")).await.is_err());
+        assert_eq!(
+            server.get_server_status().await.unwrap().retries_exhausted,
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_group_by_week_buckets_jobs() {
+        let dated = |refnr: &str, date: &str| {
+            let mut job = listing(refnr, "Entwickler", "ACME", "Berlin");
+            job["aktuelleVeroeffentlichungsdatum"] = serde_json::json!(date);
+            job
+        };
+        let body = search_body(vec![
+            dated("1", "2024-01-15"),
+            dated("2", "2024-01-21"),
+            dated("3", "2024-01-08"),
+            dated("4", "2024-01-22"),
+            dated("5", "unbekannt"),
+            listing("6", "Entwickler", "ACME", "Berlin"),
+        ]);
+        let mock = MockApi::start(move |_| MockResponse::json(body.clone())).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .search_jobs(SearchJobsParams {
+                group_by_week: Some(true),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
+
+        let bucket = |iso_week: &str, count| WeekBucket {
+            iso_week: iso_week.to_string(),
+            count,
+        };
+        assert_eq!(
+            result.weekly_buckets,
+            vec![
+                bucket("2024-W02", 1),
+                bucket("2024-W03", 2),
+                bucket("2024-W04", 1),
+                bucket("unknown", 2),
+            ]
+        );
     }
-}
 
-impl JobsucheMcpServer {
-    #[instrument]
-    pub async fn new() -> anyhow::Result<Self> {
-        info!("Initializing Jobsuche MCP Server");
+    #[tokio::test]
+    async fn test_unreachable_primary_falls_back() {
+        // Bind and release a port so nothing is listening on it
+        let unreachable = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            format!("http://{}", listener.local_addr().unwrap())
+        };
+        let body = search_body(vec![listing("1", "Koch", "Gasthaus", "Berlin")]);
+        let fallback = MockApi::start(move |_| MockResponse::json(body.clone())).await;
+        let server = JobsucheMcpServer::with_config(JobsucheConfig {
+            fallback_api_url: Some(fallback.url.clone()),
+            retry_on_connection_errors: false,
+            ..test_config(&unreachable)
+        })
+        .await
+        .unwrap();
 
-        let config = Arc::new(JobsucheConfig::load()?);
-        config.validate()?;
+        let result = server.search_jobs(search_params("Koch")).await.unwrap();
 
-        info!("Configuration loaded: API URL = {}", config.api_url);
+        assert_eq!(result.jobs_count, 1);
+        assert_eq!(result.served_by, "fallback");
+        assert_eq!(fallback.count_matching("/pc/v4/jobs?was=Koch"), 1);
+    }
 
-        let client = JobsucheClient::new(&config.api_url, config.api_key.as_deref())?;
+    #[test]
+    fn test_seeded_jitter_is_reproducible() {
+        let config = JobsucheConfig {
+            random_seed: Some(42),
+            ..JobsucheConfig::default()
+        };
+        let sequence = |client: &JobsucheClient| {
+            (0..5)
+                .map(|attempt| client.jitter(retry_delay(None, attempt, Duration::from_secs(30))))
+                .collect::<Vec<_>>()
+        };
 
-        info!("Jobsuche MCP Server initialized successfully");
+        let first = sequence(&JobsucheClient::new(&config, None).unwrap());
+        let second = sequence(&JobsucheClient::new(&config, None).unwrap());
 
-        Ok(Self {
-            start_time: Instant::now(),
-            client: Arc::new(client),
-            config,
+        assert_eq!(first, second);
+        for (attempt, delay) in first.iter().enumerate() {
+            let backoff = RATE_LIMIT_BACKOFF * 2u32.pow(attempt as u32);
+            assert!(
+                *delay >= backoff.mul_f64(0.9) && *delay <= backoff.mul_f64(1.1),
+                "{:?}",
+                delay
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_min_results_escalates_radius() {
+        let mock = MockApi::start(|req| {
+            let count = if req.path.contains("umkreis=50") {
+                3
+            } else {
+                1
+            };
+            let listings = (1..=count)
+                .map(|i| listing(&i.to_string(), "Entwickler", "ACME", "Potsdam"))
+                .collect();
+            MockResponse::json(search_body(listings))
         })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .search_jobs(SearchJobsParams {
+                location: Some("Berlin".to_string()),
+                radius_km: Some(25),
+                min_results: Some(3),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.radius_escalations, 1);
+        assert_eq!(result.applied_filters.radius_km, Some(50));
+        assert_eq!(result.jobs_count, 3);
+        assert_eq!(mock.count_matching("umkreis=25"), 1);
+        assert_eq!(mock.count_matching("umkreis=50"), 1);
+        assert_eq!(mock.count_matching("umkreis=100"), 0);
     }
 
-    fn get_uptime_seconds(&self) -> u64 {
-        self.start_time.elapsed().as_secs()
+    #[tokio::test]
+    async fn test_unknown_location_is_flagged() {
+        let mock = MockApi::start(|req| {
+            if req.path.contains("wo=") {
+                MockResponse::json(search_body(vec![]))
+            } else {
+                MockResponse::json(search_body(vec![listing(
+                    "1",
+                    "Entwickler",
+                    "ACME",
+                    "Berlin",
+                )]))
+            }
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(JobsucheConfig {
+            detect_invalid_location: true,
+            ..test_config(&mock.url)
+        })
+        .await
+        .unwrap();
+
+        let result = server
+            .search_jobs(SearchJobsParams {
+                location: Some("Berlni".to_string()),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.jobs_count, 0);
+        assert!(result.possible_invalid_location);
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(!requests[1].path.contains("wo="));
+        assert!(requests[1]
+            .path
+            .split(['?', '&'])
+            .any(|part| part == "size=1"));
     }
 
-    fn parse_employment_type(emp_type: &str) -> Option<String> {
-        match emp_type.to_lowercase().as_str() {
-            "fulltime" | "full" | "vollzeit" | "vz" => Some("vz".to_string()),
-            "parttime" | "part" | "teilzeit" | "tz" => Some("tz".to_string()),
-            "mini" | "minijob" | "mini_job" => Some("minijob".to_string()),
-            "home" | "homeoffice" | "home_office" | "ho" => Some("ho".to_string()),
-            "shift" | "schicht" | "snw" => Some("snw".to_string()),
-            _ => None,
-        }
+    #[tokio::test]
+    async fn test_location_probe_is_off_by_default() {
+        let mock = MockApi::start(|_| MockResponse::json(search_body(vec![]))).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .search_jobs(SearchJobsParams {
+                location: Some("Berlni".to_string()),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
+
+        assert!(!result.possible_invalid_location);
+        assert_eq!(mock.requests().len(), 1);
     }
-}
 
-#[mcp_tools]
-impl JobsucheMcpServer {
-    /// Search for jobs in Germany using the Federal Employment Agency database
-    #[instrument(skip(self))]
-    pub async fn search_jobs(&self, params: SearchJobsParams) -> anyhow::Result<SearchJobsResult> {
-        info!("Searching jobs with params: {:?}", params);
-        let start = Instant::now();
+    #[test]
+    fn test_retry_after_is_honored_within_cap() {
+        let cap = Duration::from_secs(30);
+        assert_eq!(retry_delay(Some("5"), 0, cap), Duration::from_secs(5));
+        assert_eq!(retry_delay(Some(" 0 "), 1, cap), Duration::ZERO);
+    }
 
-        // Build search query
-        let mut search_terms = Vec::new();
-        if let Some(ref title) = params.job_title {
-            search_terms.push(title.clone());
-        }
-        if let Some(ref employer) = params.employer {
-            search_terms.push(employer.clone());
-        }
-        if let Some(ref branch) = params.branch {
-            search_terms.push(branch.clone());
-        }
+    #[test]
+    fn test_excessive_retry_after_is_clamped() {
+        let (delay, logs) = capture_logs(|| retry_delay(Some("3600"), 0, Duration::from_secs(30)));
+        assert_eq!(delay, Duration::from_secs(30));
+        assert!(logs.contains("Clamping Retry-After of 3600s to 30s"));
+    }
 
-        let arbeitszeit = params.employment_type.as_ref().map(|types| {
-            types.iter()
-                .filter_map(|t| Self::parse_employment_type(t))
-                .collect()
-        });
+    #[test]
+    fn test_malformed_retry_after_falls_back_to_backoff() {
+        let cap = Duration::from_secs(30);
+        assert_eq!(
+            retry_delay(Some("soon"), 0, cap),
+            Duration::from_millis(500)
+        );
+        assert_eq!(retry_delay(Some("-1"), 1, cap), Duration::from_millis(1000));
+        assert_eq!(retry_delay(None, 2, cap), Duration::from_millis(2000));
+    }
 
-        let page_size = params
-            .page_size
-            .unwrap_or(self.config.default_page_size)
-            .min(self.config.max_page_size);
+    #[tokio::test]
+    async fn test_rate_limited_search_is_retried() {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mock = MockApi::start({
+            let calls = calls.clone();
+            move |_| {
+                if calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 0 {
+                    MockResponse::status(429, "{}").with_header("Retry-After", "0")
+                } else {
+                    MockResponse::json(search_body(vec![listing(
+                        "1",
+                        "Entwickler",
+                        "ACME",
+                        "Berlin",
+                    )]))
+                }
+            }
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
 
-        let search_params = SearchParams {
-            was: if search_terms.is_empty() { None } else { Some(search_terms.join(" ")) },
-            wo: params.location,
-            umkreis: params.radius_km,
-            size: Some(page_size),
-            page: params.page,
-            veroeffentlichtseit: params.published_since_days,
-            arbeitszeit,
-        };
+        let result = server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .unwrap();
+        assert_eq!(result.jobs_count, 1);
+        assert_eq!(mock.requests().len(), 2);
+    }
 
-        let response = self.client.search(&search_params).await?;
+    #[tokio::test]
+    async fn test_compact_search_keeps_core_fields_only() {
+        let mut job = listing("10000-1234567890-S", "Entwickler", "ACME", "Berlin");
+        job["arbeitsort"]["plz"] = serde_json::json!("10115");
+        job["externeUrl"] = serde_json::json!("https://acme.example/jobs/1");
+        let body = search_body(vec![job]);
+        let mock = MockApi::start(move |_| MockResponse::json(body.clone())).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
 
-        let jobs: Vec<JobSummary> = response
-            .stellenangebote
-            .iter()
-            .map(|job| {
-                let location = format!(
-                    "{}{}",
-                    job.arbeitsort.ort.as_deref().unwrap_or(""),
-                    job.arbeitsort.plz.as_ref().map(|plz| format!(" ({})", plz)).unwrap_or_default()
-                );
+        let result = server
+            .search_jobs(SearchJobsParams {
+                compact: Some(true),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
 
-                // Generate application_url with fallback hierarchy
-                let application_url = job.externe_url
-                    .clone()
-                    .unwrap_or_else(|| {
-                        format!("https://www.arbeitsagentur.de/jobsuche/jobdetail/{}", 
-                            &job.refnr)
-                    });
+        assert!(result.jobs.is_empty());
+        assert_eq!(result.jobs_count, 1);
+        let value = serde_json::to_value(&result).unwrap();
+        assert_eq!(
+            value["compact_jobs"],
+            serde_json::json!([{
+                "refnr": "10000-1234567890-S",
+                "title": "Entwickler",
+                "employer": "ACME",
+                "city": "Berlin",
+            }])
+        );
+        let output = value.to_string();
+        assert!(!output.contains("application_url"));
+        assert!(!output.contains("external_url"));
+    }
 
-                JobSummary {
-                    reference_number: job.refnr.clone(),
-                    title: job.titel.clone().unwrap_or_else(|| job.beruf.clone()),
-                    employer: job.arbeitgeber.clone(),
-                    location,
-                    published_date: job.aktuelle_veroeffentlichungsdatum.clone(),
-                    external_url: job.externe_url.clone(),
-                    application_url,
-                }
+    #[tokio::test]
+    async fn test_batch_reports_duration_per_search() {
+        let mock = MockApi::start(|req| {
+            let response = if req.path.contains("/jobdetails/") {
+                MockResponse::json(details_body("Entwickler"))
+            } else {
+                MockResponse::json(search_body(vec![listing(
+                    "10000-1234567890-S",
+                    "Entwickler",
+                    "ACME",
+                    "Berlin",
+                )]))
+            };
+            response.with_delay(Duration::from_millis(20))
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .batch_search_jobs(BatchSearchJobsParams {
+                searches: vec![
+                    batch_item("first", "Entwickler"),
+                    batch_item("second", "Developer"),
+                ],
+                max_details_per_search: Some(1),
+                cancel_token: None,
+                detail_fields: None,
+                correlation_id: None,
             })
-            .collect();
+            .await
+            .unwrap();
 
-        let duration = start.elapsed();
-        info!("Search completed: {} jobs found in {:?}", jobs.len(), duration);
+        assert_eq!(result.results.len(), 2);
+        // The first search pays for the search and a detail fetch, the second hits the details cache
+        assert!(result.results[0].duration_ms >= 40);
+        assert!(result.results[1].duration_ms >= 20);
+    }
 
-        Ok(SearchJobsResult {
-            total_results: response.max_ergebnisse,
-            current_page: response.page,
-            page_size: response.size,
-            jobs_count: jobs.len(),
-            jobs,
-            search_duration_ms: duration.as_millis() as u64,
+    fn search_body_with_unknown_fields() -> String {
+        serde_json::json!({
+            "maxErgebnisse": 1,
+            "stellenangebote": [listing("1", "Entwickler", "ACME", "Berlin")],
+            "neuesFeld": true,
         })
+        .to_string()
     }
 
-    /// Get detailed information about a specific job posting
-    #[instrument(skip(self))]
-    pub async fn get_job_details(&self, params: GetJobDetailsParams) -> anyhow::Result<GetJobDetailsResult> {
-        info!("Getting job details for: {}", params.reference_number);
+    #[tokio::test]
+    async fn test_unknown_fields_fail_with_strict_schema() {
+        let mock = MockApi::start(|_| MockResponse::json(search_body_with_unknown_fields())).await;
+        let server = JobsucheMcpServer::with_config(JobsucheConfig {
+            strict_schema: true,
+            ..test_config(&mock.url)
+        })
+        .await
+        .unwrap();
 
-        let details = self.client.job_details(&params.reference_number).await?;
+        let err = server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unexpected fields in search response (strict_schema is enabled): neuesFeld"
+        );
+    }
 
-        let location_str = details.arbeitsorte.as_ref().and_then(|locs| {
-            locs.first().and_then(|loc| {
-                loc.adresse.as_ref().and_then(|addr| {
-                    addr.ort.clone().map(|ort| {
-                        if let Some(ref plz) = addr.plz {
-                            format!("{} ({})", ort, plz)
-                        } else {
-                            ort
-                        }
+    #[tokio::test]
+    async fn test_documented_fields_pass_strict_schema() {
+        let mock = MockApi::start(|req| {
+            if req.path.contains("/jobdetails/") {
+                MockResponse::json(
+                    serde_json::json!({ "titel": "Entwickler", "hashId": "abc", "branche": "IT" })
+                        .to_string(),
+                )
+            } else {
+                MockResponse::json(
+                    serde_json::json!({
+                        "maxErgebnisse": 1,
+                        "stellenangebote": [listing("1", "Entwickler", "ACME", "Berlin")],
+                        "facetten": { "arbeitsort": {} },
                     })
-                })
+                    .to_string(),
+                )
+            }
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(JobsucheConfig {
+            strict_schema: true,
+            ..test_config(&mock.url)
+        })
+        .await
+        .unwrap();
+
+        let result = server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .unwrap();
+        assert_eq!(result.jobs_count, 1);
+        let details = server
+            .get_job_details(GetJobDetailsParams {
+                reference_number: "1".to_string(),
+                ..Default::default()
             })
-        });
+            .await
+            .unwrap();
+        assert_eq!(details.title.as_deref(), Some("Entwickler"));
+    }
 
-        let entry_period = details.eintrittszeitraum.as_ref().map(|dr| {
-            match (&dr.von, &dr.bis) {
-                (Some(von), Some(bis)) => format!("{} - {}", von, bis),
-                (Some(von), None) => format!("ab {}", von),
-                (None, Some(bis)) => format!("bis {}", bis),
-                (None, None) => String::new(),
+    #[tokio::test]
+    async fn test_unknown_fields_are_ignored_by_default() {
+        let mock = MockApi::start(|_| MockResponse::json(search_body_with_unknown_fields())).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .unwrap();
+        assert_eq!(result.jobs_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_batch_projects_detail_fields() {
+        let mock = MockApi::start(|req| {
+            if req.path.contains("/jobdetails/") {
+                MockResponse::json(details_body("Entwickler"))
+            } else {
+                MockResponse::json(search_body(vec![listing(
+                    "10000-1234567890-S",
+                    "Entwickler",
+                    "ACME",
+                    "Berlin",
+                )]))
             }
-        });
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
 
-        // Determine the best application URL with fallback hierarchy:
-        // 1. external_url (employer's application page)
-        // 2. partner_url (partner job board)
-        // 3. Internal Bundesagentur für Arbeit URL (always available)
-        let application_url = details.externe_url
-            .clone()
-            .or_else(|| details.allianzpartner_url.clone())
-            .unwrap_or_else(|| {
-                format!("https://www.arbeitsagentur.de/jobsuche/jobdetail/{}", 
-                    &params.reference_number)
-            });
+        let result = server
+            .batch_search_jobs(BatchSearchJobsParams {
+                searches: vec![batch_item("first", "Entwickler")],
+                max_details_per_search: Some(1),
+                cancel_token: None,
+                detail_fields: Some(vec!["title".to_string(), "employer".to_string()]),
+                correlation_id: None,
+            })
+            .await
+            .unwrap();
 
-        let result = GetJobDetailsResult {
-            reference_number: params.reference_number.clone(),
-            title: details.titel,
-            description: details.stellenbeschreibung,
-            employer: details.arbeitgeber,
-            location: location_str,
-            employment_type: details.arbeitszeit_vollzeit.map(|vz| if vz { "Vollzeit" } else { "Teilzeit" }.to_string()),
-            salary: details.verguetung,
-            contract_duration: details.vertragsdauer,
-            job_type: details.stellenangebots_art,
-            first_published: details.erste_veroeffentlichungsdatum,
-            only_for_disabled: details.nur_fuer_schwerbehinderte,
-            fulltime: details.arbeitszeit_vollzeit,
-            entry_period,
-            is_minor_employment: details.ist_geringfuegige_beschaeftigung,
-            is_temp_agency: details.ist_arbeitnehmer_ueberlassung,
-            career_changer_suitable: details.quereinstieg_geeignet,
-            external_url: details.externe_url,
-            partner_url: details.allianzpartner_url,
-            application_url,
+        let item = &result.results[0];
+        assert!(item.jobs.is_empty());
+        assert_eq!(item.jobs_count, 1);
+        assert_eq!(
+            item.projected_jobs,
+            Some(vec![serde_json::json!({
+                "reference_number": "10000-1234567890-S",
+                "title": "Entwickler",
+                "employer": "Beispiel GmbH",
+            })])
+        );
+        let output = serde_json::to_string(&result).unwrap();
+        assert!(!output.contains("description"));
+        assert!(!output.contains("application_url"));
+    }
+
+    #[tokio::test]
+    async fn test_server_status_probe_bypasses_search_cache() {
+        let api_down = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let mock = {
+            let api_down = api_down.clone();
+            MockApi::start(move |_| {
+                if api_down.load(std::sync::atomic::Ordering::SeqCst) {
+                    MockResponse::status(500, "")
+                } else {
+                    MockResponse::json(search_body(vec![]))
+                }
+            })
+            .await
         };
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
 
-        info!("Job details retrieved successfully");
-        Ok(result)
+        assert_eq!(
+            server
+                .get_server_status()
+                .await
+                .unwrap()
+                .api_connection_status,
+            "Connected"
+        );
+        api_down.store(true, std::sync::atomic::Ordering::SeqCst);
+        let status = server
+            .get_server_status()
+            .await
+            .unwrap()
+            .api_connection_status;
+        assert!(status.starts_with("Connection Error"), "{}", status);
     }
 
-    /// Perform multiple job searches in a single operation
-    ///
-    /// This tool allows you to search for different types of jobs simultaneously,
-    /// making it perfect for comparing opportunities across employers, locations,
-    /// or job types. Each search can have different parameters and will return
-    /// results independently.
-    ///
-    /// # Examples
-    /// - Compare employers: `{"searches": [{"name": "BARMER", "employer": "BARMER", "location": "Wuppertal"}, {"name": "Siemens", "employer": "Siemens", "location": "Wuppertal"}], "max_details_per_search": 3}`
-    /// - Different job types: `{"searches": [{"name": "Sekretariat", "job_title": "Sekretärin"}, {"name": "Sport", "job_title": "Schwimm"}]}`
-    #[instrument(skip(self))]
-    pub async fn batch_search_jobs(
-        &self,
-        params: BatchSearchJobsParams,
-    ) -> anyhow::Result<BatchSearchJobsResult> {
-        let start = Instant::now();
-        let searches_count = params.searches.len().min(5); // Limit to 5 searches to respect rate limits
-
-        info!("Performing batch search with {} searches", searches_count);
-
-        let max_details = params.max_details_per_search.unwrap_or(2).min(5);
-        let mut results = Vec::new();
-
-        // Process each search
-        for (search_idx, search_item) in params.searches.iter().take(searches_count).enumerate() {
-            // Small delay between searches to avoid rate limiting (except first)
-            if search_idx > 0 {
-                tokio::time::sleep(std::time::Duration::from_millis(200)).await;
-            }
-            info!("Processing search: {}", search_item.name);
-
-            // Convert to SearchJobsParams
-            let search_params = SearchJobsParams {
-                job_title: search_item.job_title.clone(),
-                location: search_item.location.clone(),
-                radius_km: search_item.radius_km,
-                employment_type: search_item.employment_type.clone(),
-                contract_type: search_item.contract_type.clone(),
-                published_since_days: search_item.published_since_days,
-                page_size: Some(max_details),
-                page: None,
-                employer: search_item.employer.clone(),
-                branch: search_item.branch.clone(),
-            };
+    #[tokio::test]
+    async fn test_server_status_reports_default_api_key() {
+        let mock = MockApi::start(|_| MockResponse::json(search_body(vec![]))).await;
 
-            // Perform search
-            let search_result = match self.search_jobs(search_params).await {
-                Ok(result) => result,
-                Err(e) => {
-                    // If search fails, add error result and continue
-                    results.push(BatchSearchItemResult {
-                        search_name: search_item.name.clone(),
-                        total_results: None,
-                        jobs_count: 0,
-                        jobs: Vec::new(),
-                        error: Some(format!("Search failed: {}", e)),
-                    });
-                    continue;
-                }
-            };
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+        assert!(
+            server
+                .get_server_status()
+                .await
+                .unwrap()
+                .using_default_api_key
+        );
 
-            // Fetch details if requested (with delay to respect rate limits)
-            let mut jobs_with_details = Vec::new();
-            if max_details > 0 {
-                for (detail_idx, job) in search_result.jobs.iter().take(max_details as usize).enumerate() {
-                    // Small delay between detail fetches (except first in this search)
-                    if detail_idx > 0 {
-                        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-                    }
+        let server = JobsucheMcpServer::with_config(JobsucheConfig {
+            api_key: Some("dedicated-key".to_string()),
+            ..test_config(&mock.url)
+        })
+        .await
+        .unwrap();
+        assert!(
+            !server
+                .get_server_status()
+                .await
+                .unwrap()
+                .using_default_api_key
+        );
+    }
 
-                    match self
-                        .get_job_details(GetJobDetailsParams {
-                            reference_number: job.reference_number.clone(),
-                        })
-                        .await
-                    {
-                        Ok(details) => jobs_with_details.push(details),
-                        Err(e) => {
-                            info!(
-                                "Failed to fetch details for {} in search '{}': {}",
-                                job.reference_number, search_item.name, e
-                            );
-                            // Continue with other jobs even if one fails
-                        }
-                    }
-                }
+    #[tokio::test]
+    async fn test_camel_case_output() {
+        let mock = MockApi::start(|req| {
+            if req.path.contains("/jobdetails/") {
+                MockResponse::json(details_body("Entwickler"))
+            } else {
+                MockResponse::json(search_body(vec![listing(
+                    "10000-1234567890-S",
+                    "Entwickler",
+                    "ACME",
+                    "Berlin",
+                )]))
             }
+        })
+        .await;
+        let server = JobsucheMcpServer::with_config(JobsucheConfig {
+            output_case: config::OutputCase::Camel,
+            ..test_config(&mock.url)
+        })
+        .await
+        .unwrap();
 
-            results.push(BatchSearchItemResult {
-                search_name: search_item.name.clone(),
-                total_results: search_result.total_results,
-                jobs_count: jobs_with_details.len(),
-                jobs: jobs_with_details,
-                error: None,
-            });
-        }
+        let result = server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .unwrap();
+        let value = serde_json::to_value(&result).unwrap();
+        assert_eq!(value["jobsCount"], 1);
+        assert_eq!(value["jobs"][0]["referenceNumber"], "10000-1234567890-S");
+        assert!(value["jobs"][0].get("reference_number").is_none());
 
-        let duration = start.elapsed();
-        info!(
-            "Batch search completed: {} searches in {:?}",
-            results.len(),
-            duration
-        );
+        let details = server
+            .get_job_details(GetJobDetailsParams {
+                reference_number: "10000-1234567890-S".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let value = serde_json::to_value(&details).unwrap();
+        assert_eq!(value["referenceNumber"], "10000-1234567890-S");
+        assert!(value.get("applicationUrl").is_some());
+        assert!(value.get("application_url").is_none());
+    }
 
-        Ok(BatchSearchJobsResult {
-            searches_count: results.len(),
-            results,
-            total_duration_ms: duration.as_millis() as u64,
+    #[tokio::test]
+    async fn test_refine_search_keeps_keyword_matches() {
+        let mock = MockApi::start(|req| {
+            let body = if req.path.ends_with("/10000-0000000001-S") {
+                serde_json::json!({ "titel": "Entwickler", "stellenbeschreibung": "Wir arbeiten mit Rust und Go." })
+            } else if req.path.ends_with("/10000-0000000002-S") {
+                serde_json::json!({ "titel": "Entwickler", "stellenbeschreibung": "Java und Spring Boot." })
+            } else {
+                serde_json::json!({ "titel": "Projektleiter", "stellenbeschreibung": "Agile Teams führen." })
+            };
+            MockResponse::json(body.to_string())
         })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .refine_search(RefineParams {
+                previous_reference_numbers: vec![
+                    "10000-0000000001-S".to_string(),
+                    "10000-0000000002-S".to_string(),
+                    "10000-0000000003-S".to_string(),
+                ],
+                keyword: "RUST".to_string(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.checked_count, 3);
+        assert_eq!(result.jobs_count, 1);
+        assert_eq!(result.jobs[0].reference_number, "10000-0000000001-S");
+        assert!(result.failed_reference_numbers.is_empty());
+        assert_eq!(mock.requests().len(), 3);
     }
 
-    /// Get server status and connection information
-    #[instrument(skip(self))]
-    pub async fn get_server_status(&self) -> anyhow::Result<JobsucheServerStatus> {
-        info!("Getting server status");
+    #[tokio::test]
+    async fn test_exclude_employers_drops_matching_listings() {
+        let body = search_body(vec![
+            listing("1", "Entwickler", "Zeitarbeit Müller GmbH", "Berlin"),
+            listing("2", "Entwickler", "ACME AG", "Berlin"),
+        ]);
+        let mock = MockApi::start(move |_| MockResponse::json(body.clone())).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
 
-        // Test API connectivity
-        let search_params = SearchParams {
-            was: None,
-            wo: Some("Berlin".to_string()),
-            umkreis: None,
-            size: Some(1),
-            page: None,
-            veroeffentlichtseit: None,
-            arbeitszeit: None,
+        let result = server
+            .search_jobs(SearchJobsParams {
+                exclude_employers: Some(vec!["zeitarbeit mueller".to_string()]),
+                ..search_params("Entwickler")
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.jobs_count, 1);
+        assert_eq!(result.jobs[0].employer, "ACME AG");
+        assert_eq!(result.filtered_out_count, 1);
+    }
+
+    #[test]
+    fn test_client_builds_with_custom_pool_settings() {
+        let config = JobsucheConfig {
+            pool_max_idle_per_host: 2,
+            pool_idle_timeout_secs: 5,
+            ..Default::default()
         };
+        assert!(JobsucheClient::new(&config, None).is_ok());
+    }
 
-        let connection_status = match self.client.search(&search_params).await {
-            Ok(_) => "Connected".to_string(),
-            Err(e) => format!("Connection Error: {}", e),
+    #[tokio::test]
+    async fn test_oversized_response_is_rejected() {
+        let mock = MockApi::start(|_| MockResponse::json("x".repeat(4096))).await;
+        let config = JobsucheConfig {
+            max_response_bytes: 1024,
+            ..test_config(&mock.url)
         };
+        let server = JobsucheMcpServer::with_config(config).await.unwrap();
 
-        Ok(JobsucheServerStatus {
-            server_name: "Jobsuche MCP Server".to_string(),
-            version: "0.3.1".to_string(),
-            uptime_seconds: self.get_uptime_seconds(),
-            api_url: self.config.api_url.clone(),
-            api_connection_status: connection_status,
-            tools_count: 4,
+        let err = server
+            .search_jobs(search_params("Entwickler"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("API response too large"));
+
+        let err = server
+            .get_job_details(GetJobDetailsParams {
+                reference_number: "10000-1234567890-S".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("API response too large"));
+    }
+
+    #[tokio::test]
+    async fn test_save_favorite_is_idempotent() {
+        let mock = MockApi::start(|_| MockResponse::json(details_body("Entwickler"))).await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+        let params = GetJobDetailsParams {
+            reference_number: "10000-1234567890-S".to_string(),
+            ..Default::default()
+        };
+
+        let first = server.save_favorite(params.clone()).await.unwrap();
+        let second = server.save_favorite(params.clone()).await.unwrap();
+        assert!(!first.updated);
+        assert!(second.updated);
+        assert_eq!(server.list_favorites().await.unwrap().favorites_count, 1);
+
+        let removed = server.remove_favorite(params).await.unwrap();
+        assert!(removed.removed);
+        assert!(server.list_favorites().await.unwrap().favorites.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_batch_search_shares_details_cache() {
+        let mock = MockApi::start(|req| {
+            if req.path.contains("/jobdetails/") {
+                MockResponse::json(details_body("Shared Job"))
+            } else {
+                MockResponse::json(search_body(vec![listing(
+                    "10000-1234567890-S",
+                    "Shared Job",
+                    "ACME",
+                    "Berlin",
+                )]))
+            }
         })
+        .await;
+        let server = JobsucheMcpServer::with_config(test_config(&mock.url))
+            .await
+            .unwrap();
+
+        let result = server
+            .batch_search_jobs(BatchSearchJobsParams {
+                searches: vec![
+                    batch_item("first", "Entwickler"),
+                    batch_item("second", "Developer"),
+                ],
+                max_details_per_search: Some(1),
+                cancel_token: None,
+                detail_fields: None,
+                correlation_id: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.results[0].jobs_count, 1);
+        assert_eq!(result.results[1].jobs_count, 1);
+        assert_eq!(result.cache_hits, 1);
+        assert_eq!(mock.count_matching("/jobdetails/10000-1234567890-S"), 1);
     }
 }
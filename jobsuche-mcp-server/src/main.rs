@@ -37,12 +37,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     info!("Starting MCP server with STDIO transport...");
 
+    // Keep a handle to drain in-flight tool calls once the transport has stopped
+    let shutdown_handle = jobsuche_server.clone();
+
     // Start the server using the macro-generated infrastructure
     let mut server = jobsuche_server.serve_stdio().await?;
 
     info!("Jobsuche MCP Server is running and ready to serve requests");
 
-    server.run().await?;
+    let result = server.run().await;
+    shutdown_handle.shutdown().await;
+    result?;
 
     Ok(())
 }
@@ -0,0 +1,185 @@
+//! Runtime metrics reported by `get_server_status`
+//!
+//! Latencies are cumulative since server start; there is no reset. Each tool
+//! keeps a bounded reservoir sample (Algorithm R) so memory stays constant while
+//! the percentiles remain representative of every call since start.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Maximum number of latency samples kept per tool
+const RESERVOIR_SIZE: usize = 1024;
+
+/// Latency percentiles for a single tool
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ToolLatency {
+    pub tool: String,
+    /// Number of calls since server start
+    pub count: u64,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub p99_ms: u64,
+}
+
+#[derive(Debug)]
+struct Reservoir {
+    count: u64,
+    samples: Vec<u64>,
+    rng_state: u64,
+}
+
+impl Reservoir {
+    fn new() -> Self {
+        Self {
+            count: 0,
+            samples: Vec::new(),
+            rng_state: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+
+    // xorshift64: statistically fine for sampling, no external dependency
+    fn next_random(&mut self) -> u64 {
+        let mut x = self.rng_state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.rng_state = x;
+        x
+    }
+
+    fn add(&mut self, value: u64) {
+        self.count += 1;
+        if self.samples.len() < RESERVOIR_SIZE {
+            self.samples.push(value);
+        } else {
+            let slot = self.next_random() % self.count;
+            if (slot as usize) < RESERVOIR_SIZE {
+                self.samples[slot as usize] = value;
+            }
+        }
+    }
+}
+
+/// Nearest-rank percentile of an ascending-sorted slice
+fn percentile(sorted: &[u64], p: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Records per-tool call latencies
+#[derive(Debug, Default)]
+pub struct LatencyRecorder {
+    tools: Mutex<BTreeMap<String, Reservoir>>,
+}
+
+impl LatencyRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one call of `tool` that took `latency`
+    pub fn record(&self, tool: &str, latency: Duration) {
+        self.tools
+            .lock()
+            .unwrap()
+            .entry(tool.to_string())
+            .or_insert_with(Reservoir::new)
+            .add(latency.as_millis() as u64);
+    }
+
+    /// Start timing a call; the latency is recorded when the timer is dropped
+    pub fn start(&self, tool: &'static str) -> LatencyTimer<'_> {
+        LatencyTimer {
+            recorder: self,
+            tool,
+            start: Instant::now(),
+        }
+    }
+
+    /// Percentiles for every tool called so far, ordered by tool name
+    pub fn snapshot(&self) -> Vec<ToolLatency> {
+        self.tools
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(tool, reservoir)| {
+                let mut sorted = reservoir.samples.clone();
+                sorted.sort_unstable();
+                ToolLatency {
+                    tool: tool.clone(),
+                    count: reservoir.count,
+                    p50_ms: percentile(&sorted, 50.0),
+                    p95_ms: percentile(&sorted, 95.0),
+                    p99_ms: percentile(&sorted, 99.0),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Guard returned by [`LatencyRecorder::start`]
+pub struct LatencyTimer<'a> {
+    recorder: &'a LatencyRecorder,
+    tool: &'static str,
+    start: Instant,
+}
+
+impl Drop for LatencyTimer<'_> {
+    fn drop(&mut self) {
+        self.recorder.record(self.tool, self.start.elapsed());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles_from_synthetic_latencies() {
+        let recorder = LatencyRecorder::new();
+        for ms in 1..=100 {
+            recorder.record("search_jobs", Duration::from_millis(ms));
+        }
+
+        let stats = recorder.snapshot();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].tool, "search_jobs");
+        assert_eq!(stats[0].count, 100);
+        assert_eq!(stats[0].p50_ms, 50);
+        assert_eq!(stats[0].p95_ms, 95);
+        assert_eq!(stats[0].p99_ms, 99);
+    }
+
+    #[test]
+    fn test_reservoir_stays_bounded() {
+        let recorder = LatencyRecorder::new();
+        for _ in 0..(RESERVOIR_SIZE * 3) {
+            recorder.record("get_job_details", Duration::from_millis(20));
+        }
+
+        let stats = recorder.snapshot();
+        assert_eq!(stats[0].count, (RESERVOIR_SIZE * 3) as u64);
+        assert_eq!(stats[0].p50_ms, 20);
+        assert_eq!(
+            recorder.tools.lock().unwrap()["get_job_details"]
+                .samples
+                .len(),
+            RESERVOIR_SIZE
+        );
+    }
+
+    #[test]
+    fn test_timer_records_on_drop() {
+        let recorder = LatencyRecorder::new();
+        {
+            let _timer = recorder.start("batch_search_jobs");
+        }
+        assert_eq!(recorder.snapshot()[0].count, 1);
+    }
+}
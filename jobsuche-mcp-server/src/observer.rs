@@ -0,0 +1,29 @@
+//! Hooks for observing upstream API calls
+//!
+//! Library consumers can plug in their own logging or metrics by implementing
+//! [`JobsucheObserver`] and passing it to
+//! [`JobsucheMcpServer::with_observer`](crate::JobsucheMcpServer::with_observer).
+
+use std::time::Duration;
+
+/// Receives events around each HTTP call to the Jobsuche API
+///
+/// All methods default to doing nothing, so implementors only override the
+/// events they care about. Callbacks run inline on the request path and
+/// should return quickly.
+pub trait JobsucheObserver: Send + Sync {
+    /// Called right before a request is sent
+    fn on_request(&self, _url: &str) {}
+
+    /// Called when response headers arrive, whatever the status code
+    fn on_response(&self, _status: u16, _duration: Duration) {}
+
+    /// Called when a request fails without a response (connection error, timeout, ...)
+    fn on_error(&self, _err: &str) {}
+}
+
+/// Observer that ignores all events
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopObserver;
+
+impl JobsucheObserver for NoopObserver {}
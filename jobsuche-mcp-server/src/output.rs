@@ -0,0 +1,121 @@
+//! Tool output with configurable key naming
+//!
+//! Result types declare snake_case fields. [`Cased`] wraps a result and, for
+//! [`OutputCase::Camel`], renames the keys of every nested object while
+//! serializing. Unprocessed API responses under `raw` are left as received.
+
+use crate::config::OutputCase;
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+use std::ops::{Deref, DerefMut};
+
+/// A tool result serialized with the configured [`OutputCase`]
+///
+/// Dereferences to the wrapped result, so fields can be read as usual.
+#[derive(Debug, Clone)]
+pub struct Cased<T> {
+    value: T,
+    case: OutputCase,
+}
+
+impl<T> Cased<T> {
+    pub fn new(value: T, case: OutputCase) -> Self {
+        Self { value, case }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Deref for Cased<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for Cased<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T: Serialize> Serialize for Cased<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.case {
+            OutputCase::Snake => self.value.serialize(serializer),
+            OutputCase::Camel => {
+                let value = serde_json::to_value(&self.value).map_err(serde::ser::Error::custom)?;
+                camel_case_keys(value).serialize(serializer)
+            }
+        }
+    }
+}
+
+fn camel_case_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| {
+                    let value = if key == "raw" {
+                        value
+                    } else {
+                        camel_case_keys(value)
+                    };
+                    (to_camel_case(&key), value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(camel_case_keys).collect()),
+        other => other,
+    }
+}
+
+/// `reference_number` -> `referenceNumber`
+fn to_camel_case(key: &str) -> String {
+    let mut camel = String::with_capacity(key.len());
+    let mut upper_next = false;
+    for c in key.chars() {
+        if c == '_' {
+            upper_next = !camel.is_empty();
+        } else if upper_next {
+            camel.extend(c.to_uppercase());
+            upper_next = false;
+        } else {
+            camel.push(c);
+        }
+    }
+    camel
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_to_camel_case() {
+        assert_eq!(to_camel_case("reference_number"), "referenceNumber");
+        assert_eq!(to_camel_case("newest_count_last_7d"), "newestCountLast7d");
+        assert_eq!(to_camel_case("title"), "title");
+    }
+
+    #[test]
+    fn test_camel_case_keeps_raw_untouched() {
+        let value = json!({
+            "jobs_count": 1,
+            "jobs": [{ "reference_number": "1" }],
+            "raw": { "max_ergebnisse": 1 },
+        });
+        assert_eq!(
+            camel_case_keys(value),
+            json!({
+                "jobsCount": 1,
+                "jobs": [{ "referenceNumber": "1" }],
+                "raw": { "max_ergebnisse": 1 },
+            })
+        );
+    }
+}
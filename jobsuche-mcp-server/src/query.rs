@@ -0,0 +1,392 @@
+//! Typed construction of [`SearchParams`] for library users
+//!
+//! [`SearchQueryBuilder`] collects search inputs with fluent setters and
+//! validates them once in [`SearchQueryBuilder::build`], so invalid
+//! combinations surface as a [`ValidationError`] instead of being silently
+//! dropped or clamped like in the MCP tools.
+//!
+//! [`parse_boolean_query`] parses the `AND`/`OR`/`NOT` subset accepted by
+//! search_jobs with `query_syntax: "boolean"`.
+
+use crate::config::JobsucheConfig;
+use crate::{JobsucheMcpServer, SearchParams, MAX_PUBLISHED_SINCE_DAYS, MAX_RADIUS_KM};
+use std::fmt;
+
+/// Why a [`SearchQueryBuilder`] could not build a search
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// A radius was set but no location to measure it from
+    RadiusWithoutLocation,
+    /// The radius exceeds what the API accepts
+    RadiusTooLarge(u64),
+    /// An employment type that the API does not know
+    UnknownEmploymentType(String),
+    /// `published_since_days` exceeds what the API accepts
+    PublishedSinceTooLarge(u64),
+    /// The page size is 0 or larger than the configured `max_page_size`
+    InvalidPageSize { size: u64, max: u64 },
+    /// Pages are numbered from 1
+    InvalidPage(u64),
+    /// A `query_syntax: "boolean"` title that does not parse
+    InvalidBooleanQuery(String),
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RadiusWithoutLocation => write!(f, "a radius requires a location"),
+            Self::RadiusTooLarge(radius) => {
+                write!(
+                    f,
+                    "radius {} km exceeds the maximum of {} km",
+                    radius, MAX_RADIUS_KM
+                )
+            }
+            Self::UnknownEmploymentType(value) => write!(f, "unknown employment type: {}", value),
+            Self::PublishedSinceTooLarge(days) => write!(
+                f,
+                "published_since_days {} exceeds the maximum of {}",
+                days, MAX_PUBLISHED_SINCE_DAYS
+            ),
+            Self::InvalidPageSize { size, max } => {
+                write!(f, "page size must be between 1 and {}, got {}", max, size)
+            }
+            Self::InvalidPage(page) => write!(f, "page must be at least 1, got {}", page),
+            Self::InvalidBooleanQuery(reason) => write!(f, "invalid boolean query: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Fluent builder for [`SearchParams`]
+///
+/// ```
+/// use jobsuche_mcp_server::query::SearchQueryBuilder;
+///
+/// let params = SearchQueryBuilder::new()
+///     .title("Softwareentwickler")
+///     .location("Berlin")
+///     .radius(25)
+///     .employment_type("fulltime")
+///     .build()
+///     .unwrap();
+/// assert_eq!(params.arbeitszeit, Some(vec!["vz".to_string()]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SearchQueryBuilder {
+    title: Option<String>,
+    location: Option<String>,
+    radius: Option<u64>,
+    employment_types: Vec<String>,
+    published_since_days: Option<u64>,
+    page_size: Option<u64>,
+    page: Option<u64>,
+    max_page_size: u64,
+}
+
+impl Default for SearchQueryBuilder {
+    fn default() -> Self {
+        Self::for_config(&JobsucheConfig::default())
+    }
+}
+
+impl SearchQueryBuilder {
+    /// Builder with the default configuration's limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder validating against the limits of `config`, such as `max_page_size`
+    pub fn for_config(config: &JobsucheConfig) -> Self {
+        Self {
+            title: None,
+            location: None,
+            radius: None,
+            employment_types: Vec::new(),
+            published_since_days: None,
+            page_size: None,
+            page: None,
+            max_page_size: config.max_page_size,
+        }
+    }
+
+    /// Job title or keywords (`was`)
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Place or postal code (`wo`)
+    pub fn location(mut self, location: impl Into<String>) -> Self {
+        self.location = Some(location.into());
+        self
+    }
+
+    /// Radius around the location in km (`umkreis`)
+    pub fn radius(mut self, radius_km: u64) -> Self {
+        self.radius = Some(radius_km);
+        self
+    }
+
+    /// Add an employment type such as "fulltime", "teilzeit" or "home_office"
+    pub fn employment_type(mut self, employment_type: impl Into<String>) -> Self {
+        self.employment_types.push(employment_type.into());
+        self
+    }
+
+    /// Only jobs published within the last `days` days (`veroeffentlichtseit`)
+    pub fn published_since_days(mut self, days: u64) -> Self {
+        self.published_since_days = Some(days);
+        self
+    }
+
+    pub fn page_size(mut self, size: u64) -> Self {
+        self.page_size = Some(size);
+        self
+    }
+
+    pub fn page(mut self, page: u64) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    /// Validate the inputs and turn them into API query parameters
+    ///
+    /// Title and location are trimmed; blank values count as unset.
+    pub fn build(self) -> Result<SearchParams, ValidationError> {
+        fn non_blank(value: Option<String>) -> Option<String> {
+            value
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        }
+
+        let was = non_blank(self.title);
+        let wo = non_blank(self.location);
+
+        if let Some(radius) = self.radius {
+            if wo.is_none() {
+                return Err(ValidationError::RadiusWithoutLocation);
+            }
+            if radius > MAX_RADIUS_KM {
+                return Err(ValidationError::RadiusTooLarge(radius));
+            }
+        }
+        if let Some(days) = self
+            .published_since_days
+            .filter(|&d| d > MAX_PUBLISHED_SINCE_DAYS)
+        {
+            return Err(ValidationError::PublishedSinceTooLarge(days));
+        }
+        if let Some(size) = self.page_size.filter(|&s| s == 0 || s > self.max_page_size) {
+            return Err(ValidationError::InvalidPageSize {
+                size,
+                max: self.max_page_size,
+            });
+        }
+        if let Some(page) = self.page.filter(|&p| p == 0) {
+            return Err(ValidationError::InvalidPage(page));
+        }
+
+        let mut arbeitszeit = Vec::new();
+        for employment_type in &self.employment_types {
+            let code = JobsucheMcpServer::parse_employment_type(employment_type.trim())
+                .ok_or_else(|| ValidationError::UnknownEmploymentType(employment_type.clone()))?;
+            if !arbeitszeit.contains(&code) {
+                arbeitszeit.push(code);
+            }
+        }
+
+        Ok(SearchParams {
+            was,
+            wo,
+            umkreis: self.radius,
+            size: self.page_size,
+            page: self.page,
+            veroeffentlichtseit: self.published_since_days,
+            arbeitszeit: if arbeitszeit.is_empty() {
+                None
+            } else {
+                Some(arbeitszeit)
+            },
+            befristung: None,
+        })
+    }
+}
+
+/// Most `OR` alternatives a boolean query may have; each costs one search
+pub const MAX_BOOLEAN_ALTERNATIVES: usize = 5;
+
+/// A parsed boolean title query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BooleanQuery {
+    /// Search terms, one search each; words joined by `AND` form one term
+    pub any_of: Vec<String>,
+    /// Terms whose jobs are dropped from all results (matched against the title)
+    pub none_of: Vec<String>,
+}
+
+/// Parse a title like `"Data Scientist" OR "ML Engineer" NOT Praktikum`
+///
+/// Supported: double-quoted phrases, `AND` (also implied between words), `OR`
+/// and `NOT` before a single term. Operators must be upper case; there are no
+/// parentheses, and `OR` binds loosest.
+pub fn parse_boolean_query(input: &str) -> Result<BooleanQuery, ValidationError> {
+    let invalid = |reason: &str| ValidationError::InvalidBooleanQuery(reason.to_string());
+
+    let mut tokens = Vec::new();
+    let mut rest = input.trim_start();
+    while !rest.is_empty() {
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let end = quoted
+                .find('"')
+                .ok_or_else(|| invalid("unterminated quote"))?;
+            tokens.push((quoted[..end].trim().to_string(), true));
+            rest = quoted[end + 1..].trim_start();
+        } else {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            tokens.push((rest[..end].to_string(), false));
+            rest = rest[end..].trim_start();
+        }
+    }
+
+    let mut branches: Vec<Vec<String>> = vec![Vec::new()];
+    let mut none_of = Vec::new();
+    let mut negate_next = false;
+    for (token, quoted) in tokens {
+        match token.as_str() {
+            _ if quoted && token.is_empty() => return Err(invalid("empty phrase")),
+            "AND" if !quoted => {}
+            "OR" if !quoted => {
+                if negate_next || branches.last().is_some_and(Vec::is_empty) {
+                    return Err(invalid("OR needs a term on both sides"));
+                }
+                branches.push(Vec::new());
+            }
+            "NOT" if !quoted => negate_next = true,
+            _ if negate_next => {
+                none_of.push(token);
+                negate_next = false;
+            }
+            _ => branches
+                .last_mut()
+                .expect("starts with one branch")
+                .push(token),
+        }
+    }
+    if negate_next {
+        return Err(invalid("NOT needs a term"));
+    }
+    if branches.len() > 1 && branches.last().is_some_and(Vec::is_empty) {
+        return Err(invalid("OR needs a term on both sides"));
+    }
+    if branches.iter().any(Vec::is_empty) {
+        return Err(invalid("no search term"));
+    }
+
+    let mut any_of: Vec<String> = Vec::new();
+    for term in branches.into_iter().map(|terms| terms.join(" ")) {
+        if !any_of.contains(&term) {
+            any_of.push(term);
+        }
+    }
+    if any_of.len() > MAX_BOOLEAN_ALTERNATIVES {
+        return Err(invalid(&format!(
+            "at most {} OR alternatives are supported",
+            MAX_BOOLEAN_ALTERNATIVES
+        )));
+    }
+    Ok(BooleanQuery { any_of, none_of })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_build_normalizes_inputs() {
+        let params = SearchQueryBuilder::new()
+            .title("  Pflegefachkraft ")
+            .location("Köln")
+            .radius(30)
+            .employment_type("Teilzeit")
+            .employment_type("parttime")
+            .employment_type("home_office")
+            .published_since_days(7)
+            .page_size(25)
+            .build()
+            .unwrap();
+
+        assert_eq!(params.was.as_deref(), Some("Pflegefachkraft"));
+        assert_eq!(params.wo.as_deref(), Some("Köln"));
+        assert_eq!(params.umkreis, Some(30));
+        assert_eq!(
+            params.arbeitszeit,
+            Some(vec!["tz".to_string(), "ho".to_string()])
+        );
+        assert_eq!(params.veroeffentlichtseit, Some(7));
+        assert_eq!(params.size, Some(25));
+        assert_eq!(params.page, None);
+    }
+
+    #[test]
+    fn test_invalid_inputs_are_rejected() {
+        let err = SearchQueryBuilder::new()
+            .title("Entwickler")
+            .location("   ")
+            .radius(25)
+            .build()
+            .unwrap_err();
+        assert_eq!(err, ValidationError::RadiusWithoutLocation);
+        assert_eq!(err.to_string(), "a radius requires a location");
+
+        let err = SearchQueryBuilder::new()
+            .employment_type("freelance")
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ValidationError::UnknownEmploymentType("freelance".to_string())
+        );
+
+        let err = SearchQueryBuilder::new().page_size(0).build().unwrap_err();
+        assert_eq!(err, ValidationError::InvalidPageSize { size: 0, max: 100 });
+    }
+
+    #[test]
+    fn test_page_size_limit_follows_config() {
+        let config = JobsucheConfig {
+            max_page_size: 50,
+            ..Default::default()
+        };
+        let err = SearchQueryBuilder::for_config(&config)
+            .page_size(75)
+            .build()
+            .unwrap_err();
+        assert_eq!(err, ValidationError::InvalidPageSize { size: 75, max: 50 });
+        assert!(SearchQueryBuilder::new().page_size(75).build().is_ok());
+    }
+
+    #[test]
+    fn test_parse_boolean_query() {
+        let query =
+            parse_boolean_query(r#""Data Scientist" OR ML AND Engineer NOT Praktikum"#).unwrap();
+        assert_eq!(query.any_of, vec!["Data Scientist", "ML Engineer"]);
+        assert_eq!(query.none_of, vec!["Praktikum"]);
+
+        let query = parse_boolean_query("Koch or Kellner").unwrap();
+        assert_eq!(query.any_of, vec!["Koch or Kellner"]);
+        assert!(query.none_of.is_empty());
+
+        for invalid in ["OR Koch", "Koch OR", "Koch NOT", r#""Koch"#, "NOT Koch"] {
+            assert!(
+                matches!(
+                    parse_boolean_query(invalid),
+                    Err(ValidationError::InvalidBooleanQuery(_))
+                ),
+                "{}",
+                invalid
+            );
+        }
+    }
+}
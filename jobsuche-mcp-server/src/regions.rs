@@ -0,0 +1,131 @@
+//! Mapping of API region names to the 16 German federal states (Bundesländer)
+//!
+//! `arbeitsort.region` is free text and comes in several spellings, e.g.
+//! `Nordrhein-Westfalen`, `NRW` or `nordrhein westfalen`.
+
+use std::collections::HashMap;
+
+/// Group used for regions that are not a known Bundesland
+pub const UNKNOWN_BUNDESLAND: &str = "Sonstige";
+
+const BUNDESLAENDER: [(&str, &[&str]); 16] = [
+    (
+        "Baden-Württemberg",
+        &["bw", "badenwurttemberg", "badenwuerttemberg"],
+    ),
+    ("Bayern", &["by", "bayern", "bavaria", "freistaatbayern"]),
+    ("Berlin", &["be", "berlin"]),
+    ("Brandenburg", &["bb", "brandenburg"]),
+    ("Bremen", &["hb", "bremen", "freiehansestadtbremen"]),
+    ("Hamburg", &["hh", "hamburg", "freieundhansestadthamburg"]),
+    ("Hessen", &["he", "hessen", "hesse"]),
+    ("Mecklenburg-Vorpommern", &["mv", "mecklenburgvorpommern"]),
+    (
+        "Niedersachsen",
+        &["ni", "nds", "niedersachsen", "lowersaxony"],
+    ),
+    (
+        "Nordrhein-Westfalen",
+        &["nw", "nrw", "nordrheinwestfalen", "northrhinewestphalia"],
+    ),
+    ("Rheinland-Pfalz", &["rp", "rlp", "rheinlandpfalz"]),
+    ("Saarland", &["sl", "saarland"]),
+    ("Sachsen", &["sn", "sachsen", "saxony", "freistaatsachsen"]),
+    ("Sachsen-Anhalt", &["st", "sachsenanhalt", "saxonyanhalt"]),
+    ("Schleswig-Holstein", &["sh", "schleswigholstein"]),
+    (
+        "Thüringen",
+        &[
+            "th",
+            "thuringen",
+            "thueringen",
+            "thuringia",
+            "freistaatthuringen",
+        ],
+    ),
+];
+
+/// Standard name of the Bundesland for an API region, or [`UNKNOWN_BUNDESLAND`]
+pub fn normalize_bundesland(region: &str) -> &'static str {
+    let key: String = region
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            'ä' => Some('a'),
+            'ö' => Some('o'),
+            'ü' => Some('u'),
+            'ß' => Some('s'),
+            c if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .collect();
+
+    BUNDESLAENDER
+        .iter()
+        .find(|(_, spellings)| spellings.contains(&key.as_str()))
+        .map(|(name, _)| *name)
+        .unwrap_or(UNKNOWN_BUNDESLAND)
+}
+
+/// Count regions per Bundesland, most frequent first (ties by name)
+pub fn tally_bundeslaender<'a>(
+    regions: impl IntoIterator<Item = Option<&'a str>>,
+) -> Vec<(String, u64)> {
+    let mut counts: HashMap<&'static str, u64> = HashMap::new();
+    for region in regions {
+        let state = region
+            .map(normalize_bundesland)
+            .unwrap_or(UNKNOWN_BUNDESLAND);
+        *counts.entry(state).or_default() += 1;
+    }
+
+    let mut tally: Vec<(String, u64)> = counts
+        .into_iter()
+        .map(|(state, count)| (state.to_string(), count))
+        .collect();
+    tally.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    tally
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_common_spellings() {
+        assert_eq!(
+            normalize_bundesland("Nordrhein-Westfalen"),
+            "Nordrhein-Westfalen"
+        );
+        assert_eq!(normalize_bundesland("NRW"), "Nordrhein-Westfalen");
+        assert_eq!(normalize_bundesland("thueringen"), "Thüringen");
+        assert_eq!(
+            normalize_bundesland("Baden Württemberg"),
+            "Baden-Württemberg"
+        );
+        assert_eq!(normalize_bundesland("Tirol"), UNKNOWN_BUNDESLAND);
+    }
+
+    #[test]
+    fn test_tally_mixed_sample() {
+        let tally = tally_bundeslaender([
+            Some("Bayern"),
+            Some("NRW"),
+            Some("Nordrhein-Westfalen"),
+            Some("bayern"),
+            Some("Nordrhein Westfalen"),
+            Some("Berlin"),
+            Some("Wien"),
+            None,
+        ]);
+        assert_eq!(
+            tally,
+            vec![
+                ("Nordrhein-Westfalen".to_string(), 3),
+                ("Bayern".to_string(), 2),
+                ("Sonstige".to_string(), 2),
+                ("Berlin".to_string(), 1),
+            ]
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Tracking of in-flight tool calls for graceful shutdown
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// Counts in-flight tool calls and rejects new ones once shutdown has begun
+#[derive(Debug)]
+pub struct RequestTracker {
+    /// Guarded together so no call can start between a drain's check and its flag
+    state: Mutex<TrackerState>,
+    idle: Notify,
+}
+
+#[derive(Debug)]
+struct TrackerState {
+    accepting: bool,
+    in_flight: usize,
+}
+
+impl Default for RequestTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RequestTracker {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(TrackerState {
+                accepting: true,
+                in_flight: 0,
+            }),
+            idle: Notify::new(),
+        }
+    }
+
+    /// Register a new tool call, failing if the server is shutting down
+    pub fn begin(self: &Arc<Self>) -> anyhow::Result<InFlightGuard> {
+        let mut state = self.state.lock().unwrap();
+        if !state.accepting {
+            anyhow::bail!("Server is shutting down and no longer accepts requests");
+        }
+        state.in_flight += 1;
+        Ok(InFlightGuard {
+            tracker: self.clone(),
+        })
+    }
+
+    /// Number of tool calls currently running
+    pub fn in_flight(&self) -> usize {
+        self.state.lock().unwrap().in_flight
+    }
+
+    /// Stop accepting new calls and wait up to `grace` for running ones to finish
+    ///
+    /// Returns `true` if all calls completed within the grace period.
+    pub async fn drain(&self, grace: Duration) -> bool {
+        self.state.lock().unwrap().accepting = false;
+        let deadline = tokio::time::Instant::now() + grace;
+
+        loop {
+            // Register interest before checking to not miss a wake-up in between
+            let idle = self.idle.notified();
+            if self.in_flight() == 0 {
+                return true;
+            }
+            if tokio::time::timeout_at(deadline, idle).await.is_err() {
+                return self.in_flight() == 0;
+            }
+        }
+    }
+}
+
+/// Marks a tool call as in flight until dropped
+#[derive(Debug)]
+pub struct InFlightGuard {
+    tracker: Arc<RequestTracker>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        let mut state = self.tracker.state.lock().unwrap();
+        state.in_flight -= 1;
+        if state.in_flight == 0 {
+            self.tracker.idle.notify_waiters();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_drain_without_requests_returns_immediately() {
+        let tracker = Arc::new(RequestTracker::new());
+        assert!(tracker.drain(Duration::from_secs(5)).await);
+        assert!(tracker.begin().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_drain_gives_up_after_grace_period() {
+        let tracker = Arc::new(RequestTracker::new());
+        let _guard = tracker.begin().unwrap();
+        assert!(!tracker.drain(Duration::from_millis(50)).await);
+        assert_eq!(tracker.in_flight(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_no_call_starts_once_draining() {
+        let tracker = Arc::new(RequestTracker::new());
+        let guard = tracker.begin().unwrap();
+        let drain = {
+            let tracker = tracker.clone();
+            tokio::spawn(async move { tracker.drain(Duration::from_secs(5)).await })
+        };
+        while tracker.begin().is_ok() {
+            tokio::task::yield_now().await;
+        }
+
+        drop(guard);
+        assert!(drain.await.unwrap());
+        assert_eq!(tracker.in_flight(), 0);
+    }
+}
@@ -0,0 +1,126 @@
+//! Keyword-based extraction of skills and qualifications from job descriptions
+//!
+//! Scans the text for a curated list of programming languages, frameworks,
+//! tools, certifications and common German qualification phrases. This is a
+//! heuristic: it finds what a posting names, not what it requires.
+
+/// Skills with the lowercase spellings that indicate them
+///
+/// Spellings must match whole words; a trailing `*` also matches German
+/// compounds that start with it ("führerschein*" matches "Führerscheinklasse").
+const SKILLS: &[(&str, &[&str])] = &[
+    // Programming languages
+    ("Python", &["python"]),
+    ("Java", &["java"]),
+    ("JavaScript", &["javascript"]),
+    ("TypeScript", &["typescript"]),
+    ("C++", &["c++"]),
+    ("C#", &["c#"]),
+    ("Rust", &["rust"]),
+    ("Kotlin", &["kotlin"]),
+    ("Swift", &["swift"]),
+    ("PHP", &["php"]),
+    ("Ruby", &["ruby"]),
+    ("Scala", &["scala"]),
+    ("SQL", &["sql", "mysql", "postgresql", "t-sql", "pl/sql"]),
+    // Frameworks and tools
+    ("React", &["react"]),
+    ("Angular", &["angular"]),
+    ("Vue.js", &["vue"]),
+    ("Node.js", &["node.js", "nodejs"]),
+    ("Spring", &["spring"]),
+    (".NET", &[".net"]),
+    ("Django", &["django"]),
+    ("Docker", &["docker"]),
+    ("Kubernetes", &["kubernetes"]),
+    ("AWS", &["aws"]),
+    ("Azure", &["azure"]),
+    ("Linux", &["linux"]),
+    ("Git", &["git"]),
+    ("SAP", &["sap"]),
+    ("DATEV", &["datev"]),
+    ("MS Office", &["ms office", "ms-office", "microsoft office"]),
+    ("Excel", &["excel"]),
+    // Certifications and methods
+    ("Scrum", &["scrum"]),
+    ("ITIL", &["itil"]),
+    ("PRINCE2", &["prince2"]),
+    ("ISTQB", &["istqb"]),
+    ("ISO 9001", &["iso 9001"]),
+    // German qualifications
+    ("Führerschein", &["führerschein*", "fahrerlaubnis*"]),
+    ("Staplerschein", &["staplerschein*", "gabelstaplerschein*"]),
+    ("Berufsausbildung", &["berufsausbildung*", "ausbildung als"]),
+    ("Studium", &["studium*", "hochschulabschluss*"]),
+    (
+        "Meister",
+        &["meisterbrief*", "meisterprüfung*", "meistertitel*"],
+    ),
+    ("Deutschkenntnisse", &["deutschkenntnisse*"]),
+    ("Englischkenntnisse", &["englischkenntnisse*"]),
+    ("Erste Hilfe", &["erste hilfe", "ersthelfer*"]),
+];
+
+/// Skills named in `description`, in order of first mention
+pub fn extract_skills(description: &str) -> Vec<String> {
+    let text = description.to_lowercase();
+    let mut found: Vec<(usize, &str)> = SKILLS
+        .iter()
+        .filter_map(|(skill, spellings)| {
+            spellings
+                .iter()
+                .filter_map(|spelling| first_match(&text, spelling))
+                .min()
+                .map(|pos| (pos, *skill))
+        })
+        .collect();
+    found.sort_by_key(|(pos, _)| *pos);
+    found
+        .into_iter()
+        .map(|(_, skill)| skill.to_string())
+        .collect()
+}
+
+/// Byte offset of the first whole-word occurrence of `spelling` in `text`
+fn first_match(text: &str, spelling: &str) -> Option<usize> {
+    let (word, prefix_only) = match spelling.strip_suffix('*') {
+        Some(word) => (word, true),
+        None => (spelling, false),
+    };
+    let is_word_char = |c: char| c.is_alphanumeric();
+
+    let mut from = 0;
+    while let Some(offset) = text[from..].find(word) {
+        let start = from + offset;
+        let end = start + word.len();
+        let starts_word = !text[..start].chars().next_back().is_some_and(is_word_char);
+        let ends_word = prefix_only || !text[end..].chars().next().is_some_and(is_word_char);
+        if starts_word && ends_word {
+            return Some(start);
+        }
+        from = start + text[start..].chars().next().map_or(1, char::len_utf8);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_whole_words_only() {
+        assert_eq!(
+            extract_skills("Erfahrung mit JavaScript und TypeScript"),
+            vec!["JavaScript", "TypeScript"]
+        );
+        assert_eq!(
+            extract_skills("Kenntnisse in C++ und C#"),
+            vec!["C++", "C#"]
+        );
+        assert!(extract_skills("Wir bieten eine Rustikale Kantine").is_empty());
+        assert_eq!(
+            extract_skills("Führerscheinklasse B erforderlich"),
+            vec!["Führerschein"]
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Job title synonyms used to broaden searches
+//!
+//! Synonyms are organized in groups of equivalent titles. A small set of
+//! groups is bundled; more can be supplied as a JSON file containing an array
+//! of groups, e.g. `[["Softwareentwickler", "Software Engineer"]]`.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+/// Maximum number of synonyms returned for a single term
+pub const MAX_SYNONYMS: usize = 4;
+
+const BUNDLED: &[&[&str]] = &[
+    &[
+        "Softwareentwickler",
+        "Software Engineer",
+        "Software Developer",
+        "Programmierer",
+    ],
+    &["Data Scientist", "Datenwissenschaftler", "Data Analyst"],
+    &[
+        "Pflegefachkraft",
+        "Krankenpfleger",
+        "Altenpfleger",
+        "Gesundheits- und Krankenpfleger",
+    ],
+    &["Buchhalter", "Finanzbuchhalter", "Accountant"],
+    &["Projektmanager", "Projektleiter", "Project Manager"],
+    &[
+        "Vertriebsmitarbeiter",
+        "Sales Manager",
+        "Außendienstmitarbeiter",
+    ],
+    &[
+        "Lagerarbeiter",
+        "Lagermitarbeiter",
+        "Lagerhelfer",
+        "Kommissionierer",
+    ],
+    &["Elektriker", "Elektroniker", "Elektroinstallateur"],
+    &["Kraftfahrer", "Berufskraftfahrer", "LKW-Fahrer"],
+    &["Erzieher", "Kinderpfleger", "pädagogische Fachkraft"],
+];
+
+/// Groups of equivalent job titles
+#[derive(Debug, Clone)]
+pub struct SynonymMap {
+    groups: Vec<Vec<String>>,
+}
+
+impl SynonymMap {
+    /// The bundled synonym groups
+    pub fn bundled() -> Self {
+        Self {
+            groups: BUNDLED
+                .iter()
+                .map(|group| group.iter().map(|t| t.to_string()).collect())
+                .collect(),
+        }
+    }
+
+    /// The bundled groups plus the groups from a JSON file, if a path is given
+    ///
+    /// Groups from the file are consulted before the bundled ones.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let mut map = Self::bundled();
+        if let Some(path) = path {
+            let data = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read synonyms file {}", path.display()))?;
+            let groups: Vec<Vec<String>> = serde_json::from_str(&data)
+                .with_context(|| format!("Failed to parse synonyms file {}", path.display()))?;
+            map.groups.splice(0..0, groups);
+        }
+        Ok(map)
+    }
+
+    /// Synonyms of `term` (case-insensitive), excluding the term itself
+    ///
+    /// Returns at most [`MAX_SYNONYMS`] entries.
+    pub fn lookup(&self, term: &str) -> Vec<String> {
+        let term = term.trim().to_lowercase();
+        let Some(group) = self
+            .groups
+            .iter()
+            .find(|group| group.iter().any(|t| t.to_lowercase() == term))
+        else {
+            return Vec::new();
+        };
+
+        group
+            .iter()
+            .filter(|t| t.to_lowercase() != term)
+            .take(MAX_SYNONYMS)
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_is_case_insensitive_and_excludes_term() {
+        let map = SynonymMap::bundled();
+        let synonyms = map.lookup("software engineer");
+        assert!(synonyms.contains(&"Softwareentwickler".to_string()));
+        assert!(!synonyms.contains(&"Software Engineer".to_string()));
+        assert!(map.lookup("Astronaut").is_empty());
+    }
+
+    #[test]
+    fn test_lookup_is_bounded() {
+        let map = SynonymMap {
+            groups: vec![(0..10).map(|i| format!("Titel {}", i)).collect()],
+        };
+        assert_eq!(map.lookup("Titel 0").len(), MAX_SYNONYMS);
+    }
+
+    #[test]
+    fn test_file_groups_take_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("synonyms.json");
+        fs::write(&path, r#"[["Softwareentwickler", "Coder"]]"#).unwrap();
+
+        let map = SynonymMap::load(Some(&path)).unwrap();
+        assert_eq!(map.lookup("Softwareentwickler"), vec!["Coder".to_string()]);
+        assert!(!map.lookup("Data Scientist").is_empty());
+    }
+}
@@ -0,0 +1,235 @@
+//! Test helpers: a minimal HTTP server standing in for the Jobsuche API
+
+// Not every helper is used by every test module
+#![allow(dead_code)]
+
+use crate::config::JobsucheConfig;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// A canned response returned by [`MockApi`]
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    pub delay: Option<Duration>,
+    /// Close the connection without answering, like a connection reset
+    pub drop_connection: bool,
+}
+
+impl MockResponse {
+    pub fn json(body: impl Into<String>) -> Self {
+        Self {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.into().into_bytes(),
+            delay: None,
+            drop_connection: false,
+        }
+    }
+
+    pub fn dropped_connection() -> Self {
+        Self {
+            drop_connection: true,
+            ..Self::json("")
+        }
+    }
+
+    pub fn status(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            ..Self::json(body)
+        }
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.retain(|(n, _)| !n.eq_ignore_ascii_case(name));
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+}
+
+/// A request received by [`MockApi`]
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    /// Path including the query string
+    pub path: String,
+    /// Header names are lowercased
+    pub headers: HashMap<String, String>,
+}
+
+/// Minimal HTTP/1.1 server answering every request via a handler closure
+pub struct MockApi {
+    pub url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockApi {
+    pub async fn start<F>(handler: F) -> Self
+    where
+        F: Fn(&RecordedRequest) -> MockResponse + Send + Sync + 'static,
+    {
+        Self::start_at("127.0.0.1:0", handler).await
+    }
+
+    /// Like [`MockApi::start`], but listening on a fixed address
+    pub async fn start_at<F>(addr: &str, handler: F) -> Self
+    where
+        F: Fn(&RecordedRequest) -> MockResponse + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler = Arc::new(handler);
+
+        let recorded = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else {
+                    break;
+                };
+                let handler = handler.clone();
+                let recorded = recorded.clone();
+                tokio::spawn(async move {
+                    let mut buf = Vec::new();
+                    let mut chunk = [0u8; 4096];
+                    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match socket.read(&mut chunk).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => buf.extend_from_slice(&chunk[..n]),
+                        }
+                    }
+
+                    let head = String::from_utf8_lossy(&buf).to_string();
+                    let mut lines = head.split("\r\n");
+                    let path = lines
+                        .next()
+                        .and_then(|l| l.split_whitespace().nth(1))
+                        .unwrap_or("/")
+                        .to_string();
+                    let headers = lines
+                        .take_while(|l| !l.is_empty())
+                        .filter_map(|l| l.split_once(':'))
+                        .map(|(k, v)| (k.trim().to_lowercase(), v.trim().to_string()))
+                        .collect();
+                    let request = RecordedRequest { path, headers };
+
+                    recorded.lock().unwrap().push(request.clone());
+                    let response = handler(&request);
+
+                    if let Some(delay) = response.delay {
+                        tokio::time::sleep(delay).await;
+                    }
+                    if response.drop_connection {
+                        return;
+                    }
+
+                    let mut out = format!("HTTP/1.1 {} Mock\r\n", response.status);
+                    for (name, value) in &response.headers {
+                        out.push_str(&format!("{}: {}\r\n", name, value));
+                    }
+                    out.push_str(&format!(
+                        "Content-Length: {}\r\nConnection: close\r\n\r\n",
+                        response.body.len()
+                    ));
+                    let _ = socket.write_all(out.as_bytes()).await;
+                    let _ = socket.write_all(&response.body).await;
+                    let _ = socket.shutdown().await;
+                });
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Number of received requests whose path contains `needle`
+    pub fn count_matching(&self, needle: &str) -> usize {
+        self.requests()
+            .iter()
+            .filter(|r| r.path.contains(needle))
+            .count()
+    }
+}
+
+/// Default configuration pointed at a mock server
+pub fn test_config(url: &str) -> JobsucheConfig {
+    JobsucheConfig {
+        api_url: url.to_string(),
+        ..Default::default()
+    }
+}
+
+/// A search listing in the upstream API format
+pub fn listing(refnr: &str, title: &str, employer: &str, city: &str) -> Value {
+    json!({
+        "refnr": refnr,
+        "titel": title,
+        "beruf": title,
+        "arbeitgeber": employer,
+        "arbeitsort": { "ort": city },
+    })
+}
+
+/// A search response body in the upstream API format
+pub fn search_body(listings: Vec<Value>) -> String {
+    json!({
+        "maxErgebnisse": listings.len(),
+        "page": 1,
+        "size": listings.len(),
+        "stellenangebote": listings,
+    })
+    .to_string()
+}
+
+/// A job details response body in the upstream API format
+pub fn details_body(title: &str) -> String {
+    json!({
+        "titel": title,
+        "stellenbeschreibung": format!("Beschreibung für {}", title),
+        "arbeitgeber": "Beispiel GmbH",
+    })
+    .to_string()
+}
+
+#[derive(Clone, Default)]
+struct LogBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl std::io::Write for LogBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Run `f` with a thread-local subscriber and return its result and the captured log output
+pub fn capture_logs<R>(f: impl FnOnce() -> R) -> (R, String) {
+    let buffer = LogBuffer::default();
+    let writer = buffer.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer(move || writer.clone())
+        .with_ansi(false)
+        .with_max_level(tracing::Level::TRACE)
+        .finish();
+
+    let result = tracing::subscriber::with_default(subscriber, f);
+    let logs = String::from_utf8_lossy(&buffer.0.lock().unwrap()).to_string();
+    (result, logs)
+}
@@ -0,0 +1,267 @@
+//! Text helpers for German job data: matching for client-side filters and
+//! plain-text extraction from HTML pages and descriptions
+
+/// Fold text for umlaut- and case-insensitive comparison
+///
+/// Lower-cases and spells out umlauts and ß the way they are written without
+/// a German keyboard, so "München", "MUENCHEN" and "muenchen" fold alike.
+pub fn fold_german(s: &str) -> String {
+    let mut folded = String::with_capacity(s.len());
+    for c in s.chars().flat_map(char::to_lowercase) {
+        match c {
+            'ä' => folded.push_str("ae"),
+            'ö' => folded.push_str("oe"),
+            'ü' => folded.push_str("ue"),
+            'ß' => folded.push_str("ss"),
+            c => folded.push(c),
+        }
+    }
+    folded
+}
+
+/// Whether `haystack` contains `needle` after [`fold_german`] on both
+pub fn contains_folded(haystack: &str, needle: &str) -> bool {
+    fold_german(haystack).contains(&fold_german(needle))
+}
+
+/// Legal-form words dropped when comparing employer names
+const LEGAL_FORMS: &[&str] = &[
+    "gmbh",
+    "mbh",
+    "ag",
+    "se",
+    "kg",
+    "kgaa",
+    "ohg",
+    "ug",
+    "co",
+    "ev",
+    "eg",
+    "gbr",
+    "ltd",
+    "inc",
+    "haftungsbeschraenkt",
+];
+
+/// Normalize an employer name for comparison
+///
+/// Folds case and umlauts, turns punctuation into spaces and drops legal
+/// forms, so "Müller GmbH & Co. KG" and "mueller" normalize alike.
+pub fn normalize_employer(name: &str) -> String {
+    fold_german(name)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty() && !LEGAL_FORMS.contains(word))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether `employer` is `excluded` or contains it as whole words, after normalization
+pub fn employer_matches(employer: &str, excluded: &str) -> bool {
+    let excluded = normalize_employer(excluded);
+    !excluded.is_empty()
+        && format!(" {} ", normalize_employer(employer)).contains(&format!(" {} ", excluded))
+}
+
+/// Tags that start a new line in the plain text
+const BLOCK_TAGS: &[&str] = &[
+    "address", "article", "br", "dd", "div", "dl", "dt", "footer", "h1", "h2", "h3", "h4", "h5",
+    "h6", "header", "hr", "li", "main", "ol", "p", "section", "table", "td", "th", "tr", "ul",
+];
+
+/// Named entities decoded by [`decode_entities`]; `&amp;` goes last so "&amp;lt;" stays "&lt;"
+const ENTITIES: &[(&str, &str)] = &[
+    ("&nbsp;", " "),
+    ("&lt;", "<"),
+    ("&gt;", ">"),
+    ("&quot;", "\""),
+    ("&#39;", "'"),
+    ("&apos;", "'"),
+    ("&auml;", "ä"),
+    ("&ouml;", "ö"),
+    ("&uuml;", "ü"),
+    ("&Auml;", "Ä"),
+    ("&Ouml;", "Ö"),
+    ("&Uuml;", "Ü"),
+    ("&szlig;", "ß"),
+    ("&euro;", "€"),
+    ("&amp;", "&"),
+];
+
+/// Convert an HTML page to readable plain text
+///
+/// Drops tags, comments and the contents of `<script>`, `<style>` and
+/// `<noscript>`, starts a new line at block elements, decodes common entities
+/// and collapses whitespace. Good enough to summarize a posting, not a full
+/// HTML parser.
+pub fn html_to_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len() / 2);
+    let mut rest = html;
+
+    while let Some(open) = rest.find('<') {
+        text.push_str(&rest[..open]);
+        rest = &rest[open..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let Some(close) = rest.find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = &rest[1..close];
+        rest = &rest[close + 1..];
+
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if !tag.starts_with('/') && matches!(name.as_str(), "script" | "style" | "noscript") {
+            // ASCII lower-casing keeps byte offsets intact
+            let end = format!("</{}", name);
+            rest = rest
+                .to_ascii_lowercase()
+                .find(&end)
+                .map_or("", |i| &rest[i..]);
+            continue;
+        }
+        if BLOCK_TAGS.contains(&name.as_str()) {
+            text.push('\n');
+        }
+    }
+    text.push_str(rest);
+
+    decode_entities(&text)
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Decode numeric (`&#252;`, `&#xFC;`) and common named HTML entities
+pub fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("&#") {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let numeric = rest.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let digits = &rest[2..end];
+            let code = match digits.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                None => digits.parse().ok(),
+            };
+            code.and_then(char::from_u32).map(|c| (c, end))
+        });
+        match numeric {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push_str("&#");
+                rest = &rest[2..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+
+    ENTITIES
+        .iter()
+        .fold(decoded, |text, (entity, replacement)| {
+            text.replace(entity, replacement)
+        })
+}
+
+/// Tidy a job description for reading
+///
+/// Decodes HTML entities, turns literal `\n`/`\r` escapes and CRLF into real
+/// line breaks, collapses runs of spaces within lines and keeps at most one
+/// blank line between paragraphs.
+pub fn clean_description(text: &str) -> String {
+    let text = decode_entities(text)
+        .replace("\\r\\n", "\n")
+        .replace("\\n", "\n")
+        .replace("\\r", "\n")
+        .replace("\r\n", "\n")
+        .replace('\r', "\n");
+
+    let mut cleaned: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if line.is_empty() && cleaned.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+        cleaned.push(line);
+    }
+    while cleaned.last().is_some_and(|last| last.is_empty()) {
+        cleaned.pop();
+    }
+    cleaned.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_umlaut_pairs_match() {
+        assert!(contains_folded("Pflegekraft in München", "muenchen"));
+        assert!(contains_folded("Muenchen", "MÜNCHEN"));
+        assert!(contains_folded("Bäckerei Müller", "baeckerei mueller"));
+        assert!(contains_folded("Köln-Süd", "koeln-sued"));
+        assert!(contains_folded("Hauptstraße 1", "hauptstrasse"));
+        assert!(contains_folded("Hauptstrasse 1", "Straße"));
+    }
+
+    #[test]
+    fn test_employer_matching() {
+        assert_eq!(normalize_employer("Müller GmbH & Co. KG"), "mueller");
+        assert!(employer_matches(
+            "Randstad Deutschland GmbH & Co. KG",
+            "randstad"
+        ));
+        assert!(employer_matches("ACME AG", "Acme GmbH"));
+        assert!(!employer_matches("Randstadt Bau", "Randstad"));
+        assert!(!employer_matches("ACME AG", "GmbH"));
+    }
+
+    #[test]
+    fn test_html_to_text() {
+        let html = r#"<html><head><title>Job</title><style>p { color: red; }</style></head>
+            <body><!-- nav --><h1>Entwickler (m/w/d)</h1>
+            <script>var x = "<p>";</script>
+            <p>Wir bieten:<br>30 Tage Urlaub &amp; <b>Homeoffice</b></p>
+            <ul><li>M&uuml;nchen</li><li>Berlin</li></ul></body></html>"#;
+        assert_eq!(
+            html_to_text(html),
+            "Job\nEntwickler (m/w/d)\nWir bieten:\n30 Tage Urlaub & Homeoffice\nMünchen\nBerlin"
+        );
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(
+            decode_entities("M&uuml;ller &amp; S&#246;hne &#xFC; &#; &amp;lt;"),
+            "Müller & Söhne ü &#; &lt;"
+        );
+    }
+
+    #[test]
+    fn test_clean_description_collapses_whitespace() {
+        let raw = "  Wir suchen   dich!\\n\\n\\n\\nAufgaben:\r\n- Entwicklung\r\n\r\n\r\n\r\nProfil:\t Java  \n\n";
+        assert_eq!(
+            clean_description(raw),
+            "Wir suchen dich!\n\nAufgaben:\n- Entwicklung\n\nProfil: Java"
+        );
+    }
+
+    #[test]
+    fn test_non_matches_stay_distinct() {
+        assert!(!contains_folded("Münster", "muenchen"));
+        assert_eq!(fold_german("ÄÖÜ"), "aeoeue");
+    }
+}
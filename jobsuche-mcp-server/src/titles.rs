@@ -0,0 +1,175 @@
+//! Cleanup of job titles as delivered by the API
+
+/// Acronyms kept in upper case when fixing all-caps titles
+const ACRONYMS: &[&str] = &[
+    "IT", "SAP", "SQL", "EDV", "HR", "CNC", "SPS", "UX", "UI", "PHP", "AWS", "CAD", "ERP", "KFZ",
+    "LKW", "PKW", "BWL", "QA", "CRM", "API", "KI", "AI", "MFA", "ZFA", "PTA", "MTA", "OP", "TV",
+    "GMBH",
+];
+
+/// Words kept in lower case inside a title
+const LOWERCASE_WORDS: &[&str] = &[
+    "und", "oder", "für", "in", "im", "mit", "der", "die", "das", "den", "dem", "des", "zur",
+    "zum", "von", "vom", "bei", "als", "an", "am", "auf", "aus",
+];
+
+/// Title for a search listing, falling back when the API sends none
+///
+/// Uses `titel`, then the occupation (`beruf`), then "employer, location",
+/// and finally `placeholder`. Returns the title and whether it is a fallback
+/// rather than the posting's own title.
+pub fn listing_title(
+    titel: Option<&str>,
+    beruf: &str,
+    employer: &str,
+    location: &str,
+    placeholder: &str,
+) -> (String, bool) {
+    if let Some(titel) = titel.map(str::trim).filter(|t| !t.is_empty()) {
+        return (titel.to_string(), false);
+    }
+    if !beruf.trim().is_empty() {
+        return (beruf.trim().to_string(), true);
+    }
+
+    let synthesized = match (employer.trim(), location.trim()) {
+        ("", _) => placeholder.to_string(),
+        (employer, "") => employer.to_string(),
+        (employer, location) => format!("{}, {}", employer, location),
+    };
+    (synthesized, true)
+}
+
+/// Normalize a job title
+///
+/// Collapses whitespace, decodes leftover HTML entities and converts titles
+/// written entirely in capitals to title case, keeping known acronyms like
+/// "IT" or "SAP" and lowercasing gender markers like "(M/W/D)".
+pub fn clean_title(title: &str) -> String {
+    let decoded = title
+        .replace("&amp;", "&")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'");
+    let collapsed = decoded.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if !is_all_caps(&collapsed) {
+        return collapsed;
+    }
+
+    collapsed
+        .split(' ')
+        .enumerate()
+        .map(|(idx, word)| title_case_word(word, idx == 0))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether a title has enough letters to judge and none of them in lower case
+fn is_all_caps(title: &str) -> bool {
+    let letters: Vec<char> = title.chars().filter(|c| c.is_alphabetic()).collect();
+    letters.len() >= 4 && letters.iter().all(|c| !c.is_lowercase())
+}
+
+fn title_case_word(word: &str, first: bool) -> String {
+    let mut out = String::with_capacity(word.len());
+    let mut segment = String::new();
+
+    // Hyphens, slashes and brackets separate independently cased segments
+    for c in word.chars() {
+        if c.is_alphanumeric() {
+            segment.push(c);
+        } else {
+            out.push_str(&case_segment(&segment, first && out.is_empty()));
+            segment.clear();
+            out.push(c);
+        }
+    }
+    out.push_str(&case_segment(&segment, first && out.is_empty()));
+    out
+}
+
+fn case_segment(segment: &str, first: bool) -> String {
+    if segment.is_empty() {
+        return String::new();
+    }
+    if ACRONYMS.contains(&segment) {
+        return if segment == "GMBH" {
+            "GmbH".to_string()
+        } else {
+            segment.to_string()
+        };
+    }
+
+    let lower = segment.to_lowercase();
+    if segment.chars().count() == 1 || (!first && LOWERCASE_WORDS.contains(&lower.as_str())) {
+        return lower;
+    }
+
+    let mut chars = lower.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => lower,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_listing_title_fallbacks() {
+        assert_eq!(
+            listing_title(
+                Some("Entwickler (m/w/d)"),
+                "Softwareentwickler",
+                "ACME",
+                "Berlin",
+                "Stellenangebot"
+            ),
+            ("Entwickler (m/w/d)".to_string(), false)
+        );
+        assert_eq!(
+            listing_title(
+                Some("  "),
+                "Softwareentwickler",
+                "ACME",
+                "Berlin",
+                "Stellenangebot"
+            ),
+            ("Softwareentwickler".to_string(), true)
+        );
+        assert_eq!(
+            listing_title(None, "", "ACME", "Berlin (10115)", "Stellenangebot"),
+            ("ACME, Berlin (10115)".to_string(), true)
+        );
+        assert_eq!(
+            listing_title(None, "", "", "Berlin", "Stellenangebot"),
+            ("Stellenangebot".to_string(), true)
+        );
+    }
+
+    #[test]
+    fn test_all_caps_title() {
+        assert_eq!(
+            clean_title("SACHBEARBEITER  FÜR   BUCHHALTUNG (M/W/D)"),
+            "Sachbearbeiter für Buchhaltung (m/w/d)"
+        );
+    }
+
+    #[test]
+    fn test_acronyms_are_preserved() {
+        assert_eq!(
+            clean_title("IT-BERATER SAP UND SQL-ENTWICKLER"),
+            "IT-Berater SAP und SQL-Entwickler"
+        );
+    }
+
+    #[test]
+    fn test_mixed_case_title_only_collapses_whitespace() {
+        assert_eq!(
+            clean_title("  Software\u{a0}Engineer  (m/w/d) &amp; Team Lead "),
+            "Software Engineer (m/w/d) & Team Lead"
+        );
+        assert_eq!(clean_title("SAP Berater"), "SAP Berater");
+    }
+}